@@ -0,0 +1,168 @@
+use sqlx::SqlitePool;
+use chrono::Utc;
+
+use crate::errors::AppError;
+use crate::models::auction::{Auction, AuctionKind, Bid};
+use crate::models::contract::Contract;
+use crate::models::notification::NotificationKind;
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::open_escrow;
+use crate::blockchain::notifications::alert_escrow_to_fund;
+use crate::ws::hub::Hub;
+
+// ─── Buscar leilão por id ────────────────────────────────────
+pub async fn find_auction(pool: &SqlitePool, id: &str) -> Result<Auction, AppError> {
+    sqlx::query_as::<_, Auction>("SELECT * FROM auctions WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Leilão não encontrado".into()))
+}
+
+// ─── Encerrar leilões que atingiram o bloco final ────────────
+// Chamado a cada bloco aceito, como `process_expired_contracts`.
+pub async fn process_ended_auctions(pool: &SqlitePool, hub: &Hub) -> Result<u64, AppError> {
+    let (_, current_height, _) = get_latest_block(pool).await?;
+    let mut settled = 0u64;
+
+    let ended = sqlx::query_as::<_, Auction>(
+        "SELECT * FROM auctions WHERE status = 'OPEN' AND end_block <= ?",
+    )
+    .bind(current_height)
+    .fetch_all(pool)
+    .await?;
+
+    for auction in ended {
+        match settle_auction(pool, hub, &auction).await {
+            Ok(_)  => settled += 1,
+            Err(e) => tracing::warn!("Falha ao encerrar leilão {}: {}", auction.id, e),
+        }
+    }
+
+    Ok(settled)
+}
+
+// ─── Encerrar leilão no bloco final ──────────────────────────
+// Inglês: maior lance ≥ reserva vence e recebe o escrow; senão, UNSOLD.
+// Holandês que chega ao fim sem comprador também termina UNSOLD.
+pub async fn settle_auction(
+    pool:    &SqlitePool,
+    hub:     &Hub,
+    auction: &Auction,
+) -> Result<Option<Contract>, AppError> {
+    let top_bid = sqlx::query_as::<_, Bid>(
+        "SELECT * FROM bids WHERE auction_id = ?
+         ORDER BY amount_sats DESC, created_at ASC
+         LIMIT 1",
+    )
+    .bind(&auction.id)
+    .fetch_optional(pool)
+    .await?;
+
+    match top_bid {
+        Some(bid) if auction.kind == AuctionKind::English
+            && bid.amount_sats >= auction.reserve_price_sats =>
+        {
+            let contract = award_auction(pool, hub, auction, &bid.bidder_id, bid.amount_sats).await?;
+            Ok(Some(contract))
+        }
+        _ => {
+            close_unsold(pool, &auction.id).await?;
+            Ok(None)
+        }
+    }
+}
+
+// ─── Declarar vencedor e abrir o escrow ──────────────────────
+// O escrow reserva 1 unidade; se o estoque já foi tomado por outra
+// venda (Conflict), a premiação falha e o leilão termina UNSOLD.
+// O vencedor é avisado para travar os fundos do contrato.
+pub async fn award_auction(
+    pool:        &SqlitePool,
+    hub:         &Hub,
+    auction:     &Auction,
+    winner_id:   &str,
+    amount_sats: i64,
+) -> Result<Contract, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    // Reivindicar o leilão antes de abrir o escrow (evita escrow duplicado)
+    let affected = sqlx::query(
+        "UPDATE auctions SET status = 'SETTLED', winner_id = ?, updated_at = ?
+         WHERE id = ? AND status = 'OPEN'",
+    )
+    .bind(winner_id)
+    .bind(&now)
+    .bind(&auction.id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Conflict("Leilão já foi encerrado".into()));
+    }
+
+    let winner_address = sqlx::query_scalar::<_, String>(
+        "SELECT address FROM wallets WHERE user_id = ?",
+    )
+    .bind(winner_id)
+    .fetch_one(pool)
+    .await?;
+
+    let contract = match open_escrow(pool, &winner_address, &auction.product_id, amount_sats, 1).await {
+        Ok(contract) => contract,
+        Err(e) => {
+            // Produto indisponível ou sem estoque: leilão encerra sem venda
+            sqlx::query(
+                "UPDATE auctions SET status = 'UNSOLD', winner_id = NULL, updated_at = ?
+                 WHERE id = ? AND status = 'SETTLED' AND contract_id IS NULL",
+            )
+            .bind(&now)
+            .bind(&auction.id)
+            .execute(pool)
+            .await?;
+            return Err(e);
+        }
+    };
+
+    sqlx::query("UPDATE auctions SET contract_id = ?, updated_at = ? WHERE id = ?")
+        .bind(&contract.id)
+        .bind(&now)
+        .bind(&auction.id)
+        .execute(pool)
+        .await?;
+
+    tracing::info!(
+        "Leilão {} encerrado: vencedor {} por {} sats (contrato {})",
+        auction.id, winner_id, amount_sats, contract.id
+    );
+
+    // O leilão já está premiado: falha no aviso só é registrada
+    let alert = alert_escrow_to_fund(
+        pool,
+        hub,
+        &contract,
+        NotificationKind::AuctionWon,
+        "Você venceu o leilão".into(),
+        Some(auction.id.clone()),
+    )
+    .await;
+    if let Err(e) = alert {
+        tracing::warn!("Falha ao avisar vencedor do leilão {}: {}", auction.id, e);
+    }
+
+    Ok(contract)
+}
+
+// ─── Encerrar sem venda ──────────────────────────────────────
+async fn close_unsold(pool: &SqlitePool, id: &str) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE auctions SET status = 'UNSOLD', updated_at = ? WHERE id = ? AND status = 'OPEN'",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
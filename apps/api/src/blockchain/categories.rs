@@ -0,0 +1,58 @@
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::category::Category;
+
+// ─── Categoria ativa para novos produtos ─────────────────────
+pub async fn active_category(pool: &SqlitePool, slug: &str) -> Result<Category, AppError> {
+    let category = sqlx::query_as::<_, Category>("SELECT * FROM categories WHERE slug = ?")
+        .bind(slug.trim())
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::Validation(format!("Categoria '{}' não existe", slug)))?;
+
+    if !category.active {
+        return Err(AppError::Validation(format!("Categoria '{}' está inativa", slug)));
+    }
+
+    Ok(category)
+}
+
+// ─── Filtro de categoria com subcategorias ───────────────────
+// Produtos anteriores à taxonomia (sem category_id) casam pelo texto.
+// `param` é um placeholder (`?1`) ou uma coluna (`s.category`).
+pub fn category_filter(param: &str) -> String {
+    format!(
+        "(p.category = {param} OR p.category_id IN (
+             WITH RECURSIVE sub(id) AS (
+                 SELECT id FROM categories WHERE slug = {param}
+                 UNION ALL
+                 SELECT c.id FROM categories c JOIN sub ON c.parent_id = sub.id
+             )
+             SELECT id FROM sub
+         ))"
+    )
+}
+
+// ─── `candidate` está na subárvore de `ancestor`? ────────────
+// Usado para impedir ciclos ao mover uma categoria.
+pub async fn is_in_subtree(
+    pool:      &SqlitePool,
+    ancestor:  &str,
+    candidate: &str,
+) -> Result<bool, AppError> {
+    let found = sqlx::query_scalar::<_, i64>(
+        "WITH RECURSIVE sub(id) AS (
+             SELECT ?1
+             UNION ALL
+             SELECT c.id FROM categories c JOIN sub ON c.parent_id = sub.id
+         )
+         SELECT COUNT(*) FROM sub WHERE id = ?2",
+    )
+    .bind(ancestor)
+    .bind(candidate)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(found > 0)
+}
@@ -18,7 +18,7 @@ pub async fn get_latest_block(
     .await?;
 
     match row {
-        Some(b) => Ok((b.id, b.height, b.difficulty)),
+        Some(b) => Ok((b.id.unwrap_or_default(), b.height, b.difficulty)),
         None    => {
             // Chain vazia — retornar valores do bloco gênesis
             Ok((genesis_hash(), 0, initial_difficulty()))
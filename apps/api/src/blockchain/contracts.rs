@@ -1,144 +1,357 @@
 use sqlx::SqlitePool;
 use chrono::Utc;
+use std::env;
 
 use crate::errors::AppError;
-use crate::models::contract::{Contract, ContractEvent, ContractState};
+use crate::models::contract::{Contract, ContractEvent, ContractState, ContractSummary, EscrowTerms};
 use crate::blockchain::chain::get_latest_block;
+use crate::models::product::Reservation;
+use crate::models::transaction::Transaction;
+use crate::blockchain::pow::sha256_hex;
+use crate::blockchain::utxo::create_change_utxo;
+use crate::blockchain::inventory::{
+    consume_reservation, create_reservation, release_reservation, reserve_stock, unreserve_stock,
+};
+use crate::blockchain::revisions::snapshot_revision;
+use crate::blockchain::roles::pick_arbiter;
 
-// ─── Verificar contratos expirados e reembolsar ──────────────
-pub async fn process_expired_contracts(pool: &SqlitePool) -> Result<u64, AppError> {
-    let (_, current_height, _) = get_latest_block(pool).await?;
-    let now = Utc::now().to_rfc3339();
-    let mut refunded = 0u64;
+// ─── Endereço que guarda os fundos travados ──────────────────
+// A TX de bloqueio paga este endereço; a liberação e o reembolso saem dele.
+pub fn escrow_address() -> String {
+    env::var("ESCROW_ADDRESS").unwrap_or_else(|_| "PAPERMARKET_ESCROW_01".into())
+}
 
-    // Buscar contratos PENDING ou LOCKED que expiraram
-    let expired = sqlx::query_as::<_, Contract>(
-        "SELECT * FROM contracts
-         WHERE state IN ('PENDING', 'LOCKED')
-         AND expires_at_block <= ?",
+// ─── Abrir contrato escrow para um produto ───────────────────
+// Usado por POST /api/contracts, ordens, leilões e ofertas. Reserva
+// `quantity` unidades antes de gravar o contrato: sem estoque livre o
+// escrow não abre (Conflict).
+pub async fn open_escrow(
+    pool:          &SqlitePool,
+    buyer_address: &str,
+    product_id:    &str,
+    amount_sats:   i64,
+    quantity:      i64,
+) -> Result<Contract, AppError> {
+    // 1. Buscar pubkey do comprador
+    let buyer_pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
     )
-    .bind(current_height)
-    .fetch_all(pool)
+    .bind(buyer_address)
+    .fetch_one(pool)
     .await?;
 
-    for contract in expired {
-        // Atualizar estado para REFUNDED
-        sqlx::query(
-            "UPDATE contracts SET state = 'REFUNDED', updated_at = ? WHERE id = ?",
-        )
-        .bind(&now)
-        .bind(&contract.id)
-        .execute(pool)
-        .await?;
+    // 2. Verificar se produto existe
+    let product = sqlx::query!(
+        "SELECT id, title, description, price_sats, seller_id FROM products WHERE id = ? AND status = 'active'",
+        product_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
 
-        // Registrar evento REFUNDED
-        let event = ContractEvent::new(
-            contract.id.clone(),
-            "REFUNDED".into(),
-            Some(format!(
-                "Contrato expirado no bloco {}. Fundos devolvidos ao comprador.",
-                current_height
-            )),
-        );
+    // 3. Validar que comprador não é o vendedor
+    let seller_address = sqlx::query_scalar::<_, String>(
+        "SELECT address FROM wallets WHERE user_id = ?",
+    )
+    .bind(&product.seller_id)
+    .fetch_one(pool)
+    .await?;
 
-        sqlx::query(
-            "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-             VALUES (?, ?, ?, ?, ?)",
-        )
-        .bind(&event.id)
-        .bind(&event.contract_id)
-        .bind(&event.event_type)
-        .bind(&event.description)
-        .bind(&event.created_at)
-        .execute(pool)
-        .await?;
+    if seller_address == buyer_address {
+        return Err(AppError::Validation("Você não pode comprar seu próprio produto".into()));
+    }
+
+    // 4. Calcular taxa do escrow
+    let escrow_fee_percent: f64 = env::var("ESCROW_FEE_PERCENT")
+        .unwrap_or_else(|_| "0.5".into())
+        .parse()
+        .unwrap_or(0.5);
+
+    let fee_sats = (amount_sats as f64 * escrow_fee_percent / 100.0) as i64;
+
+    // 5. Buscar pubkey do vendedor
+    let seller_pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE user_id = ?",
+    )
+    .bind(&product.seller_id)
+    .fetch_one(pool)
+    .await?;
+
+    // 6. Pubkey do árbitro (usuário com papel arbiter ou o da plataforma)
+    let arbiter_pubkey = pick_arbiter(pool).await?;
+
+    // 7. Revisão atual do anúncio — o item_hash dela fica no contrato
+    let revision = snapshot_revision(pool, product_id).await?;
+
+    // 8. Altura atual e expiração (100 blocos)
+    let (_, current_height, _) = get_latest_block(pool).await?;
+    let created_at_block = current_height;
+    let expires_at_block = current_height + 100;
 
-        refunded += 1;
-        tracing::info!("Contrato {} reembolsado (expirado no bloco {})", contract.id, current_height);
+    // 9. Criar contrato
+    let contract = Contract::new(
+        product_id.to_string(),
+        buyer_pubkey,
+        seller_pubkey,
+        arbiter_pubkey,
+        EscrowTerms {
+            amount_sats,
+            fee_sats,
+            item_hash:   revision.item_hash,
+            revision_id: Some(revision.id),
+            created_at_block,
+            expires_at_block,
+        },
+    );
+
+    // 10. Reservar estoque — devolvido se o contrato não for gravado
+    reserve_stock(pool, product_id, quantity).await?;
+
+    if let Err(e) = insert_escrow(pool, &contract, quantity).await {
+        unreserve_stock(pool, product_id, quantity).await?;
+        return Err(e);
     }
 
-    Ok(refunded)
+    Ok(contract)
 }
 
-// ─── Travar fundos do contrato (PENDING → LOCKED) ────────────
-pub async fn lock_contract(
-    pool:      &SqlitePool,
-    id:        &str,
-    lock_tx_id: &str,
+// ─── Gravar contrato, reserva e evento CREATED ───────────────
+async fn insert_escrow(
+    pool:     &SqlitePool,
+    contract: &Contract,
+    quantity: i64,
 ) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO contracts (id, version, product_id, buyer_pubkey, seller_pubkey, arbiter_pubkey,
+         amount_sats, fee_sats, item_hash, revision_id, state, created_at_block, expires_at_block,
+         lock_tx_id, release_tx_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, NULL, ?, ?)",
+    )
+    .bind(&contract.id)
+    .bind(&contract.version)
+    .bind(&contract.product_id)
+    .bind(&contract.buyer_pubkey)
+    .bind(&contract.seller_pubkey)
+    .bind(&contract.arbiter_pubkey)
+    .bind(contract.amount_sats)
+    .bind(contract.fee_sats)
+    .bind(&contract.item_hash)
+    .bind(&contract.revision_id)
+    .bind(contract.state)
+    .bind(contract.created_at_block)
+    .bind(contract.expires_at_block)
+    .bind(&contract.created_at)
+    .bind(&contract.updated_at)
+    .execute(pool)
+    .await?;
+
+    let reservation = Reservation::new(
+        contract.product_id.clone(),
+        None,
+        contract.id.clone(),
+        quantity,
+    );
+    create_reservation(pool, &reservation).await?;
+
+    let event = ContractEvent::new(
+        contract.id.clone(),
+        "CREATED".into(),
+        Some(format!("Contrato criado pelo comprador. Expira no bloco {}.", contract.expires_at_block)),
+    );
+    record_event(pool, &event).await
+}
+
+// ─── Aplicar transição de estado ─────────────────────────────
+// Único ponto de escrita do campo `state`: valida a transição contra a
+// tabela de `ContractState::can_transition_to`, grava de forma atômica
+// (só aplica se o estado no banco ainda for o lido) e registra o evento.
+pub async fn transition_contract(
+    pool:        &SqlitePool,
+    contract:    &Contract,
+    next:        ContractState,
+    tx_id:       Option<&str>,
+    description: String,
+) -> Result<(), AppError> {
+    if !contract.state.can_transition_to(next) {
+        return Err(AppError::InvalidContractState(format!(
+            "Transição {} → {} não permitida",
+            contract.state, next
+        )));
+    }
+
     let now = Utc::now().to_rfc3339();
 
+    // TX de bloqueio/liberação conforme o estado de destino
+    let (lock_tx_id, release_tx_id) = match next {
+        ContractState::Locked   => (tx_id, None),
+        ContractState::Released => (None, tx_id),
+        _                       => (None, None),
+    };
+
     let affected = sqlx::query(
-        "UPDATE contracts SET state = 'LOCKED', lock_tx_id = ?, updated_at = ?
-         WHERE id = ? AND state = 'PENDING'",
+        "UPDATE contracts
+         SET state = ?,
+             lock_tx_id    = COALESCE(?, lock_tx_id),
+             release_tx_id = COALESCE(?, release_tx_id),
+             updated_at = ?
+         WHERE id = ? AND state = ?",
     )
+    .bind(next)
     .bind(lock_tx_id)
+    .bind(release_tx_id)
     .bind(&now)
-    .bind(id)
+    .bind(&contract.id)
+    .bind(contract.state)
     .execute(pool)
     .await?
     .rows_affected();
 
     if affected == 0 {
-        return Err(AppError::InvalidContractState(
-            "Contrato não encontrado ou não está em estado PENDING".into(),
-        ));
+        return Err(AppError::InvalidContractState(format!(
+            "Contrato {} não está mais em estado {}",
+            contract.id, contract.state
+        )));
     }
 
-    // Registrar evento LOCKED
     let event = ContractEvent::new(
-        id.to_string(),
-        "LOCKED".into(),
-        Some(format!("Fundos travados na TX {}", lock_tx_id)),
+        contract.id.clone(),
+        next.as_str().into(),
+        Some(description),
+    );
+    record_event(pool, &event).await?;
+
+    sync_order(pool, contract, next).await?;
+    pay_out_escrow(pool, contract, next).await
+}
+
+// ─── Pagar a saída do escrow (RELEASED / REFUNDED) ───────────
+// Com fundos travados, a liberação paga ao vendedor o valor menos a
+// taxa (que fica com o escrow) e o reembolso devolve tudo ao comprador.
+// A TX é da plataforma e entra na mempool como qualquer outra; o id
+// derivado do contrato impede pagar duas vezes.
+async fn pay_out_escrow(
+    pool:     &SqlitePool,
+    contract: &Contract,
+    next:     ContractState,
+) -> Result<(), AppError> {
+    let Some(lock_tx_id) = contract.lock_tx_id.as_deref() else {
+        return Ok(());
+    };
+
+    let (receiver_pubkey, amount_sats) = match next {
+        ContractState::Released => (&contract.seller_pubkey, contract.amount_sats - contract.fee_sats),
+        ContractState::Refunded => (&contract.buyer_pubkey, contract.amount_sats),
+        _                       => return Ok(()),
+    };
+
+    let receiver = sqlx::query_scalar::<_, String>(
+        "SELECT address FROM wallets WHERE pubkey = ?",
+    )
+    .bind(receiver_pubkey)
+    .fetch_one(pool)
+    .await?;
+
+    let escrow = escrow_address();
+    let tx = Transaction::new(
+        sha256_hex(&format!("ESCROW_PAYOUT:{}:{}", contract.id, next)),
+        escrow.clone(),
+        receiver,
+        amount_sats,
+        0,
+        format!("ESCROW:{}", contract.id),
     );
 
     sqlx::query(
-        "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-         VALUES (?, ?, ?, ?, ?)",
+        "INSERT INTO transactions (id, block_id, sender, receiver, amount_sats, fee_sats, signature, status, created_at)
+         VALUES (?, NULL, ?, ?, ?, ?, ?, ?, ?)",
     )
-    .bind(&event.id)
-    .bind(&event.contract_id)
-    .bind(&event.event_type)
-    .bind(&event.description)
-    .bind(&event.created_at)
+    .bind(&tx.id)
+    .bind(&tx.sender)
+    .bind(&tx.receiver)
+    .bind(tx.amount_sats)
+    .bind(tx.fee_sats)
+    .bind(&tx.signature)
+    .bind(&tx.status)
+    .bind(&tx.created_at)
     .execute(pool)
     .await?;
 
+    // Gastar o UTXO travado; a taxa volta ao escrow como troco
+    sqlx::query(
+        "UPDATE utxos SET spent = 1, spent_tx_id = ?
+         WHERE tx_id = ? AND owner = ? AND spent = 0",
+    )
+    .bind(&tx.id)
+    .bind(lock_tx_id)
+    .bind(&escrow)
+    .execute(pool)
+    .await?;
+
+    create_change_utxo(pool, &tx.id, &escrow, contract.amount_sats - amount_sats).await?;
+
+    sqlx::query("UPDATE contracts SET release_tx_id = ? WHERE id = ?")
+        .bind(&tx.id)
+        .bind(&contract.id)
+        .execute(pool)
+        .await?;
+
     Ok(())
 }
 
-// ─── Liberar fundos do contrato (→ RELEASED) ─────────────────
-pub async fn release_contract(
-    pool:           &SqlitePool,
-    id:             &str,
-    release_tx_id:  &str,
+// ─── Propagar estado do escrow para ordem e estoque ──────────
+async fn sync_order(
+    pool:     &SqlitePool,
+    contract: &Contract,
+    next:     ContractState,
 ) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
 
-    let affected = sqlx::query(
-        "UPDATE contracts SET state = 'RELEASED', release_tx_id = ?, updated_at = ?
-         WHERE id = ? AND state IN ('LOCKED', 'DISPUTED')",
-    )
-    .bind(release_tx_id)
-    .bind(&now)
-    .bind(id)
-    .execute(pool)
-    .await?
-    .rows_affected();
+    match next {
+        ContractState::Locked => {
+            sqlx::query(
+                "UPDATE orders SET status = 'FUNDED', updated_at = ?
+                 WHERE contract_id = ? AND status = 'PENDING'",
+            )
+            .bind(&now)
+            .bind(&contract.id)
+            .execute(pool)
+            .await?;
+        }
+        ContractState::Released => {
+            sqlx::query(
+                "UPDATE orders SET status = 'COMPLETED', updated_at = ?
+                 WHERE contract_id = ? AND status IN ('FUNDED', 'SHIPPED', 'DELIVERED')",
+            )
+            .bind(&now)
+            .bind(&contract.id)
+            .execute(pool)
+            .await?;
 
-    if affected == 0 {
-        return Err(AppError::InvalidContractState(
-            "Contrato não encontrado ou não pode ser liberado".into(),
-        ));
+            consume_reservation(pool, &contract.id, &contract.product_id).await?;
+        }
+        ContractState::Refunded => {
+            // Sem pagamento a ordem é apenas cancelada
+            sqlx::query(
+                "UPDATE orders
+                 SET status = CASE status WHEN 'PENDING' THEN 'CANCELLED' ELSE 'REFUNDED' END,
+                     updated_at = ?
+                 WHERE contract_id = ? AND status IN ('PENDING', 'FUNDED', 'SHIPPED', 'DELIVERED')",
+            )
+            .bind(&now)
+            .bind(&contract.id)
+            .execute(pool)
+            .await?;
+
+            release_reservation(pool, &contract.id).await?;
+        }
+        ContractState::Pending | ContractState::Disputed => {}
     }
 
-    // Registrar evento RELEASED
-    let event = ContractEvent::new(
-        id.to_string(),
-        "RELEASED".into(),
-        Some(format!("Fundos liberados ao vendedor na TX {}", release_tx_id)),
-    );
+    Ok(())
+}
 
+// ─── Registrar evento do contrato ────────────────────────────
+pub async fn record_event(pool: &SqlitePool, event: &ContractEvent) -> Result<(), AppError> {
     sqlx::query(
         "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
          VALUES (?, ?, ?, ?, ?)",
@@ -154,6 +367,128 @@ pub async fn release_contract(
     Ok(())
 }
 
+// ─── Buscar contrato por id ──────────────────────────────────
+pub async fn find_contract(pool: &SqlitePool, id: &str) -> Result<Contract, AppError> {
+    sqlx::query_as::<_, Contract>("SELECT * FROM contracts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Contrato não encontrado".into()))
+}
+
+// ─── Verificar contratos expirados e reembolsar ──────────────
+pub async fn process_expired_contracts(pool: &SqlitePool) -> Result<u64, AppError> {
+    let (_, current_height, _) = get_latest_block(pool).await?;
+    let mut refunded = 0u64;
+
+    // Buscar contratos PENDING ou LOCKED que expiraram
+    let expired = sqlx::query_as::<_, Contract>(
+        "SELECT * FROM contracts
+         WHERE state IN ('PENDING', 'LOCKED')
+         AND expires_at_block <= ?",
+    )
+    .bind(current_height)
+    .fetch_all(pool)
+    .await?;
+
+    // Uma falha (ex.: reserva ausente) não pode derrubar o bloco: registra
+    // e segue para os demais, que voltam a ser tentados no próximo bloco.
+    for contract in expired {
+        let result = transition_contract(
+            pool,
+            &contract,
+            ContractState::Refunded,
+            None,
+            format!(
+                "Contrato expirado no bloco {}. Fundos devolvidos ao comprador.",
+                current_height
+            ),
+        )
+        .await;
+
+        match result {
+            Ok(_) => {
+                refunded += 1;
+                tracing::info!("Contrato {} reembolsado (expirado no bloco {})", contract.id, current_height);
+            }
+            Err(e) => tracing::warn!("Falha ao reembolsar contrato {}: {}", contract.id, e),
+        }
+    }
+
+    Ok(refunded)
+}
+
+// ─── Travar fundos do contrato (PENDING → LOCKED) ────────────
+// Usado por POST /api/orders/:id/fund e POST /api/contracts/:id/fund
+// (escrow direto, leilão, oferta). A TX de bloqueio tem que sair do
+// comprador para o endereço do escrow, confirmada (o UTXO travado já
+// existe), no valor exato do contrato e não usada em outro contrato.
+pub async fn fund_escrow(
+    pool:          &SqlitePool,
+    contract:      &Contract,
+    buyer_address: &str,
+    lock_tx_id:    &str,
+) -> Result<(), AppError> {
+    let buyer_pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
+    )
+    .bind(buyer_address)
+    .fetch_one(pool)
+    .await?;
+
+    if buyer_pubkey != contract.buyer_pubkey {
+        return Err(AppError::Unauthorized);
+    }
+
+    let tx = sqlx::query_as::<_, Transaction>(
+        "SELECT * FROM transactions WHERE id = ?",
+    )
+    .bind(lock_tx_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Transação não encontrada".into()))?;
+
+    if tx.sender != buyer_address {
+        return Err(AppError::InvalidTransaction("TX não foi enviada pelo comprador".into()));
+    }
+    if tx.receiver != escrow_address() {
+        return Err(AppError::InvalidTransaction(format!(
+            "TX deve ser enviada ao endereço do escrow ({})",
+            escrow_address()
+        )));
+    }
+    if tx.status != "confirmed" {
+        return Err(AppError::InvalidTransaction("TX ainda não foi confirmada em um bloco".into()));
+    }
+    if tx.amount_sats != contract.amount_sats {
+        return Err(AppError::InvalidTransaction(format!(
+            "TX de {} sats difere do valor do contrato ({} sats)",
+            tx.amount_sats, contract.amount_sats
+        )));
+    }
+
+    let already_used = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM contracts WHERE lock_tx_id = ?",
+    )
+    .bind(&tx.id)
+    .fetch_one(pool)
+    .await?;
+
+    if already_used > 0 {
+        return Err(AppError::AlreadyExists("TX já usada em outro contrato".into()));
+    }
+
+    // PENDING → LOCKED (a ordem, se houver, acompanha o escrow)
+    transition_contract(
+        pool,
+        contract,
+        ContractState::Locked,
+        Some(&tx.id),
+        format!("Fundos travados na TX {}", tx.id),
+    )
+    .await
+}
+
 // ─── Estatísticas de contratos ───────────────────────────────
 pub async fn contract_stats(pool: &SqlitePool) -> Result<serde_json::Value, AppError> {
     let total = sqlx::query_scalar::<_, i64>(
@@ -182,8 +517,8 @@ pub async fn contract_stats(pool: &SqlitePool) -> Result<serde_json::Value, AppE
 
     let states: serde_json::Value = by_state
         .iter()
-        .map(|r| (r.state.clone(), r.count))
-        .collect::<std::collections::HashMap<_, _>>()
+        .map(|r| (r.state.clone(), serde_json::Value::from(r.count)))
+        .collect::<serde_json::Map<_, _>>()
         .into();
 
     Ok(serde_json::json!({
@@ -192,4 +527,71 @@ pub async fn contract_stats(pool: &SqlitePool) -> Result<serde_json::Value, AppE
         "volume_sats":  volume,
         "fees_sats":    fees,
     }))
-}
\ No newline at end of file
+}
+// ─── Resumo dos contratos de um usuário ──────────────────────
+pub async fn user_contract_summary(
+    pool:          &SqlitePool,
+    pubkey:        &str,
+    within_blocks: i64,
+) -> Result<ContractSummary, AppError> {
+    let (_, current_height, _) = get_latest_block(pool).await?;
+
+    // Contagem por papel
+    let (as_buyer, as_seller, as_arbiter) = sqlx::query_as::<_, (i64, i64, i64)>(
+        "SELECT
+            COALESCE(SUM(buyer_pubkey   = ?1), 0),
+            COALESCE(SUM(seller_pubkey  = ?1), 0),
+            COALESCE(SUM(arbiter_pubkey = ?1), 0)
+         FROM contracts",
+    )
+    .bind(pubkey)
+    .fetch_one(pool)
+    .await?;
+
+    // Valor travado em contratos onde o usuário é comprador ou vendedor
+    let locked_sats = sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(SUM(amount_sats), 0) FROM contracts
+         WHERE state IN ('LOCKED', 'DISPUTED')
+         AND (buyer_pubkey = ?1 OR seller_pubkey = ?1)",
+    )
+    .bind(pubkey)
+    .fetch_one(pool)
+    .await?;
+
+    // Contratos liberáveis que ainda não têm a assinatura do usuário
+    let pending_signatures = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM contracts c
+         WHERE c.state IN ('LOCKED', 'DISPUTED')
+         AND (c.buyer_pubkey = ?1 OR c.seller_pubkey = ?1 OR c.arbiter_pubkey = ?1)
+         AND NOT EXISTS (
+             SELECT 1 FROM contract_signatures s
+             WHERE s.contract_id = c.id AND s.signer_pubkey = ?1
+         )",
+    )
+    .bind(pubkey)
+    .fetch_one(pool)
+    .await?;
+
+    // Contratos que expiram nos próximos `within_blocks` blocos
+    let expiring = sqlx::query_as::<_, Contract>(
+        "SELECT * FROM contracts
+         WHERE state IN ('PENDING', 'LOCKED')
+         AND (buyer_pubkey = ?1 OR seller_pubkey = ?1 OR arbiter_pubkey = ?1)
+         AND expires_at_block <= ?2
+         ORDER BY expires_at_block ASC",
+    )
+    .bind(pubkey)
+    .bind(current_height + within_blocks)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ContractSummary {
+        as_buyer,
+        as_seller,
+        as_arbiter,
+        locked_sats,
+        pending_signatures,
+        within_blocks,
+        expiring,
+    })
+}
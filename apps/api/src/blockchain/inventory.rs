@@ -0,0 +1,204 @@
+use sqlx::SqlitePool;
+use chrono::Utc;
+
+use crate::errors::AppError;
+use crate::models::product::Reservation;
+
+// Tentativas antes de desistir de uma reserva por conflito de versão
+const MAX_RESERVE_ATTEMPTS: usize = 3;
+
+// ─── Reservar unidades de um produto ─────────────────────────
+// Controle otimista: a reserva só é gravada se `version` não mudou
+// desde a leitura, então dois compradores nunca levam a última unidade.
+pub async fn reserve_stock(
+    pool:       &SqlitePool,
+    product_id: &str,
+    quantity:   i64,
+) -> Result<(), AppError> {
+    for _ in 0..MAX_RESERVE_ATTEMPTS {
+        let (stock, reserved, version) = sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT stock, reserved, version FROM products WHERE id = ? AND status = 'active'",
+        )
+        .bind(product_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+        let available = stock - reserved;
+        if available < quantity {
+            return Err(AppError::Conflict(format!(
+                "Estoque insuficiente: {} unidade(s) disponível(is)",
+                available
+            )));
+        }
+
+        let affected = sqlx::query(
+            "UPDATE products
+             SET reserved = reserved + ?, version = version + 1, updated_at = ?
+             WHERE id = ? AND version = ?",
+        )
+        .bind(quantity)
+        .bind(Utc::now().to_rfc3339())
+        .bind(product_id)
+        .bind(version)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+        if affected == 1 {
+            return Ok(());
+        }
+    }
+
+    Err(AppError::Conflict(
+        "Estoque alterado por outra compra, tente novamente".into(),
+    ))
+}
+
+// ─── Devolver unidades reservadas sem registro de reserva ────
+// Compensação quando a abertura do escrow falha depois de `reserve_stock`.
+pub async fn unreserve_stock(
+    pool:       &SqlitePool,
+    product_id: &str,
+    quantity:   i64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE products
+         SET reserved = reserved - ?, version = version + 1, updated_at = ?
+         WHERE id = ?",
+    )
+    .bind(quantity)
+    .bind(Utc::now().to_rfc3339())
+    .bind(product_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Registrar reserva de um escrow ──────────────────────────
+pub async fn create_reservation(
+    pool:        &SqlitePool,
+    reservation: &Reservation,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO reservations (id, product_id, order_id, contract_id, quantity, status, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&reservation.id)
+    .bind(&reservation.product_id)
+    .bind(&reservation.order_id)
+    .bind(&reservation.contract_id)
+    .bind(reservation.quantity)
+    .bind(&reservation.status)
+    .bind(&reservation.created_at)
+    .bind(&reservation.updated_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Vincular a reserva do escrow à ordem criada depois ──────
+pub async fn attach_order(
+    pool:        &SqlitePool,
+    contract_id: &str,
+    order_id:    &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE reservations SET order_id = ?, updated_at = ? WHERE contract_id = ?",
+    )
+    .bind(order_id)
+    .bind(Utc::now().to_rfc3339())
+    .bind(contract_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Consumir reserva (escrow RELEASED) ──────────────────────
+// Baixa estoque e reserva no mesmo UPDATE; o produto vira `sold` quando
+// chega a zero. Todo escrow reserva ao abrir (`open_escrow`), então
+// contrato sem reserva HELD é inconsistência, não venda sem estoque.
+pub async fn consume_reservation(
+    pool:        &SqlitePool,
+    contract_id: &str,
+    product_id:  &str,
+) -> Result<(), AppError> {
+    let reservation = close_reservation(pool, contract_id, "CONSUMED")
+        .await?
+        .ok_or_else(|| AppError::Internal(format!(
+            "Contrato {} liberado sem reserva de estoque",
+            contract_id
+        )))?;
+
+    // Decremento relativo e atômico; `version` sobe para que um
+    // `reserve_stock` concorrente releia o estoque antes de gravar.
+    let affected = sqlx::query(
+        "UPDATE products
+         SET reserved = reserved - ?1,
+             stock    = stock - ?1,
+             status   = CASE WHEN stock <= ?1 THEN 'sold' ELSE status END,
+             version  = version + 1,
+             updated_at = ?2
+         WHERE id = ?3 AND reserved >= ?1 AND stock >= ?1",
+    )
+    .bind(reservation.quantity)
+    .bind(Utc::now().to_rfc3339())
+    .bind(product_id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Internal(format!(
+            "Estoque do produto {} não cobre a reserva do contrato {}",
+            product_id, contract_id
+        )));
+    }
+
+    Ok(())
+}
+
+// ─── Liberar reserva (escrow REFUNDED ou expirado) ───────────
+pub async fn release_reservation(
+    pool:        &SqlitePool,
+    contract_id: &str,
+) -> Result<(), AppError> {
+    if let Some(reservation) = close_reservation(pool, contract_id, "RELEASED").await? {
+        unreserve_stock(pool, &reservation.product_id, reservation.quantity).await?;
+    }
+
+    Ok(())
+}
+
+// ─── Encerrar reserva HELD de um contrato ────────────────────
+// Retorna a reserva apenas se esta chamada foi a que a encerrou.
+async fn close_reservation(
+    pool:        &SqlitePool,
+    contract_id: &str,
+    status:      &str,
+) -> Result<Option<Reservation>, AppError> {
+    let Some(reservation) = sqlx::query_as::<_, Reservation>(
+        "SELECT * FROM reservations WHERE contract_id = ? AND status = 'HELD'",
+    )
+    .bind(contract_id)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let affected = sqlx::query(
+        "UPDATE reservations SET status = ?, updated_at = ? WHERE id = ? AND status = 'HELD'",
+    )
+    .bind(status)
+    .bind(Utc::now().to_rfc3339())
+    .bind(&reservation.id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok((affected == 1).then_some(reservation))
+}
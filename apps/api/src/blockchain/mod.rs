@@ -2,4 +2,17 @@ pub mod chain;
 pub mod pow;
 pub mod mempool;
 pub mod utxo;
-pub mod contracts;
\ No newline at end of file
+pub mod contracts;
+pub mod inventory;
+pub mod auctions;
+pub mod offers;
+pub mod reputation;
+pub mod revisions;
+pub mod categories;
+pub mod notifications;
+pub mod moderation;
+pub mod shipping;
+pub mod roles;
+pub mod sessions;
+pub mod security;
+pub mod two_factor;
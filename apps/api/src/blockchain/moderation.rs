@@ -0,0 +1,271 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::moderation::{
+    find_banned_term, ModerationAction, ModerationActionKind, ModerationTarget,
+};
+use crate::models::notification::{Notification, NotificationKind};
+use crate::blockchain::notifications::notify;
+use crate::ws::hub::Hub;
+
+// ─── Termos proibidos em título/descrição ────────────────────
+// Chamado por create_product/update_product antes de gravar.
+pub async fn check_banned_terms(pool: &SqlitePool, texts: &[&str]) -> Result<(), AppError> {
+    let terms = sqlx::query_scalar::<_, String>("SELECT term FROM banned_terms")
+        .fetch_all(pool)
+        .await?;
+
+    for text in texts {
+        if let Some(term) = find_banned_term(text, &terms) {
+            return Err(AppError::Validation(format!(
+                "Anúncio contém termo proibido: '{}'",
+                term
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+// ─── Vendedor suspenso não cria nem edita anúncios ───────────
+pub async fn ensure_not_suspended(pool: &SqlitePool, user_id: &str) -> Result<(), AppError> {
+    let suspended = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT suspended_at FROM users WHERE id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    if suspended.is_some() {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok(())
+}
+
+// ─── Gravar na trilha de auditoria ───────────────────────────
+pub async fn record_action(pool: &SqlitePool, action: &ModerationAction) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO moderation_actions (id, moderator_address, action, target_type, target_id,
+         target_user_id, reason, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&action.id)
+    .bind(&action.moderator_address)
+    .bind(action.action)
+    .bind(action.target_type)
+    .bind(&action.target_id)
+    .bind(&action.target_user_id)
+    .bind(&action.reason)
+    .bind(&action.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Dono do alvo (vendedor, autor da avaliação ou o usuário) ─
+pub async fn target_owner(
+    pool:        &SqlitePool,
+    target_type: ModerationTarget,
+    target_id:   &str,
+) -> Result<String, AppError> {
+    let (query, missing) = match target_type {
+        ModerationTarget::Product => ("SELECT seller_id FROM products WHERE id = ?", "Produto não encontrado"),
+        ModerationTarget::Review  => ("SELECT reviewer_id FROM reviews WHERE id = ?", "Avaliação não encontrada"),
+        ModerationTarget::User    => ("SELECT id FROM users WHERE id = ?", "Usuário não encontrado"),
+        _ => return Err(AppError::Validation(format!("Alvo '{}' não tem dono", target_type))),
+    };
+
+    sqlx::query_scalar::<_, String>(query)
+        .bind(target_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(missing.into()))
+}
+
+// ─── Aplicar ação de moderação ───────────────────────────────
+// Muda o conteúdo, grava a auditoria, resolve as denúncias abertas
+// do alvo e avisa o dono pela caixa de notificações.
+pub async fn apply_action(
+    pool:   &SqlitePool,
+    hub:    &Hub,
+    action: ModerationAction,
+) -> Result<ModerationAction, AppError> {
+    use ModerationActionKind::*;
+    use ModerationTarget::*;
+
+    if !action.action.applies_to(action.target_type) {
+        return Err(AppError::Validation(format!(
+            "Ação {} não se aplica a {}",
+            action.action, action.target_type
+        )));
+    }
+
+    let owner = action
+        .target_user_id
+        .clone()
+        .ok_or_else(|| AppError::Internal("Ação sem usuário alvo".into()))?;
+    let now = Utc::now().to_rfc3339();
+
+    let affected = match (action.action, action.target_type) {
+        (Hide, Product) => sqlx::query(
+            "UPDATE products SET status = 'hidden', hidden_by = ?, updated_at = ?
+             WHERE id = ? AND status IN ('active', 'paused', 'sold')",
+        )
+        .bind(&action.id)
+        .bind(&now)
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?
+        .rows_affected(),
+
+        (Hide, Review) => sqlx::query(
+            "UPDATE reviews SET hidden_at = ? WHERE id = ? AND hidden_at IS NULL",
+        )
+        .bind(&now)
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?
+        .rows_affected(),
+
+        (Remove, Product) => sqlx::query(
+            "UPDATE products SET status = 'removed', hidden_by = NULL, updated_at = ?
+             WHERE id = ? AND status != 'removed'",
+        )
+        .bind(&now)
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?
+        .rows_affected(),
+
+        (Remove, Review) => sqlx::query("DELETE FROM reviews WHERE id = ?")
+            .bind(&action.target_id)
+            .execute(pool)
+            .await?
+            .rows_affected(),
+
+        (Warn, _) => 1,
+
+        // Suspensão: conta marcada e anúncios ativos ocultos por esta ação
+        (Suspend, _) => {
+            let suspended = sqlx::query(
+                "UPDATE users SET suspended_at = ?, suspended_by = ?
+                 WHERE id = ? AND suspended_at IS NULL",
+            )
+            .bind(&now)
+            .bind(&action.id)
+            .bind(&owner)
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+            if suspended > 0 {
+                sqlx::query(
+                    "UPDATE products SET status = 'hidden', hidden_by = ?, updated_at = ?
+                     WHERE seller_id = ? AND status = 'active'",
+                )
+                .bind(&action.id)
+                .bind(&now)
+                .bind(&owner)
+                .execute(pool)
+                .await?;
+            }
+
+            suspended
+        }
+
+        // Anúncio oculto volta a ativo — exceto se o vendedor segue suspenso
+        (Restore, Product) => sqlx::query(
+            "UPDATE products SET status = 'active', hidden_by = NULL, updated_at = ?
+             WHERE id = ? AND status = 'hidden'
+               AND (SELECT suspended_at FROM users WHERE id = products.seller_id) IS NULL",
+        )
+        .bind(&now)
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?
+        .rows_affected(),
+
+        (Restore, Review) => sqlx::query(
+            "UPDATE reviews SET hidden_at = NULL WHERE id = ? AND hidden_at IS NOT NULL",
+        )
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?
+        .rows_affected(),
+
+        // Fim da suspensão reativa só os anúncios que ela ocultou
+        (Restore, User) => {
+            sqlx::query(
+                "UPDATE products SET status = 'active', hidden_by = NULL, updated_at = ?1
+                 WHERE seller_id = ?2 AND status = 'hidden'
+                   AND hidden_by = (SELECT suspended_by FROM users WHERE id = ?2)",
+            )
+            .bind(&now)
+            .bind(&owner)
+            .execute(pool)
+            .await?;
+
+            sqlx::query(
+                "UPDATE users SET suspended_at = NULL, suspended_by = NULL
+                 WHERE id = ? AND suspended_at IS NOT NULL",
+            )
+            .bind(&owner)
+            .execute(pool)
+            .await?
+            .rows_affected()
+        }
+
+        _ => 0,
+    };
+
+    if affected == 0 {
+        return Err(AppError::Conflict(format!(
+            "Ação {} não altera o estado atual de {} {}",
+            action.action, action.target_type, action.target_id
+        )));
+    }
+
+    record_action(pool, &action).await?;
+
+    // Denúncias abertas do alvo ficam resolvidas por esta ação
+    if action.target_type.is_reportable() {
+        sqlx::query(
+            "UPDATE reports SET status = 'RESOLVED', action_id = ?, updated_at = ?
+             WHERE target_type = ? AND target_id = ? AND status = 'OPEN'",
+        )
+        .bind(&action.id)
+        .bind(&now)
+        .bind(action.target_type)
+        .bind(&action.target_id)
+        .execute(pool)
+        .await?;
+    }
+
+    let (product_id, title) = match (action.action, action.target_type) {
+        (Hide, Product)    => (Some(action.target_id.clone()), "Seu anúncio foi ocultado pela moderação"),
+        (Remove, Product)  => (Some(action.target_id.clone()), "Seu anúncio foi removido pela moderação"),
+        (Restore, Product) => (Some(action.target_id.clone()), "Seu anúncio foi restaurado"),
+        (Hide, Review)     => (None, "Sua avaliação foi ocultada pela moderação"),
+        (Remove, Review)   => (None, "Sua avaliação foi removida pela moderação"),
+        (Restore, Review)  => (None, "Sua avaliação foi restaurada"),
+        (Suspend, _)       => (None, "Sua conta de vendedor foi suspensa"),
+        (Restore, User)    => (None, "A suspensão da sua conta foi encerrada"),
+        _                  => (None, "Você recebeu uma advertência da moderação"),
+    };
+
+    let notification = Notification::new(
+        owner,
+        NotificationKind::Moderation,
+        title.into(),
+        action.reason.clone(),
+        product_id,
+        None,
+        None,
+    );
+    notify(pool, hub, notification).await?;
+
+    Ok(action)
+}
@@ -0,0 +1,238 @@
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::contract::Contract;
+use crate::models::notification::{Notification, NotificationKind};
+use crate::models::product::{Product, ProductStatus};
+use crate::models::watchlist::SavedSearch;
+use crate::blockchain::categories::category_filter;
+use crate::blockchain::contracts::escrow_address;
+use crate::ws::hub::Hub;
+
+// ─── Blocos de antecedência do aviso de leilão ───────────────
+pub fn auction_alert_blocks() -> i64 {
+    env::var("AUCTION_ALERT_BLOCKS")
+        .unwrap_or_else(|_| "3".into())
+        .parse()
+        .unwrap_or(3)
+}
+
+// ─── Gravar na caixa e enviar pelo WebSocket ─────────────────
+// Avisos duplicados (mesmo leilão) são ignorados pelo índice único.
+// Retorna se a notificação foi criada.
+pub async fn notify(
+    pool:         &SqlitePool,
+    hub:          &Hub,
+    notification: Notification,
+) -> Result<bool, AppError> {
+    let inserted = sqlx::query(
+        "INSERT OR IGNORE INTO notifications
+         (id, user_id, kind, title, body, product_id, auction_id, search_id, read_at, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, NULL, ?)",
+    )
+    .bind(&notification.id)
+    .bind(&notification.user_id)
+    .bind(notification.kind)
+    .bind(&notification.title)
+    .bind(&notification.body)
+    .bind(&notification.product_id)
+    .bind(&notification.auction_id)
+    .bind(&notification.search_id)
+    .bind(&notification.created_at)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Ok(false);
+    }
+
+    let addresses = sqlx::query_scalar::<_, String>("SELECT address FROM wallets WHERE user_id = ?")
+        .bind(&notification.user_id)
+        .fetch_all(pool)
+        .await?;
+
+    let event = serde_json::json!({ "type": "notification", "data": notification });
+    for address in addresses {
+        hub.send(&address, &event);
+    }
+
+    Ok(true)
+}
+
+// ─── Produto novo × buscas salvas ────────────────────────────
+// Mesmos filtros da listagem; um aviso por usuário, nunca ao vendedor.
+pub async fn alert_saved_searches(
+    pool:    &SqlitePool,
+    hub:     &Hub,
+    product: &Product,
+) -> Result<u64, AppError> {
+    if product.status != ProductStatus::Active {
+        return Ok(0);
+    }
+
+    let matches = sqlx::query_as::<_, SavedSearch>(&format!(
+        "SELECT s.* FROM saved_searches s, products p
+         WHERE p.id = ?1
+           AND s.user_id != p.seller_id
+           AND (s.category IS NULL OR {category})
+           AND (s.condition IS NULL OR p.condition = s.condition)
+           AND (s.min_price IS NULL OR p.price_sats >= s.min_price)
+           AND (s.max_price IS NULL OR p.price_sats <= s.max_price)
+           AND (s.state IS NULL OR p.state = s.state)
+           AND (s.fts_query IS NULL OR p.id IN
+                (SELECT product_id FROM products_fts WHERE products_fts MATCH s.fts_query))
+         GROUP BY s.user_id",
+        category = category_filter("s.category"),
+    ))
+    .bind(&product.id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0u64;
+    for search in matches {
+        let notification = Notification::new(
+            search.user_id,
+            NotificationKind::NewMatch,
+            format!("Novo anúncio para \"{}\"", search.name),
+            format!("{} — {} sats", product.title, product.price_sats),
+            Some(product.id.clone()),
+            None,
+            Some(search.id),
+        );
+        if notify(pool, hub, notification).await? {
+            sent += 1;
+        }
+    }
+
+    Ok(sent)
+}
+
+// ─── Queda de preço de produto acompanhado ───────────────────
+// Compara com o último preço avisado a cada usuário e atualiza a base,
+// então subir e baixar de novo volta a gerar aviso.
+pub async fn alert_price_drop(
+    pool:    &SqlitePool,
+    hub:     &Hub,
+    product: &Product,
+) -> Result<u64, AppError> {
+    let watchers = sqlx::query_as::<_, (String, i64)>(
+        "SELECT user_id, last_price_sats FROM watchlist
+         WHERE product_id = ? AND last_price_sats > ?",
+    )
+    .bind(&product.id)
+    .bind(product.price_sats)
+    .fetch_all(pool)
+    .await?;
+
+    sqlx::query("UPDATE watchlist SET last_price_sats = ? WHERE product_id = ?")
+        .bind(product.price_sats)
+        .bind(&product.id)
+        .execute(pool)
+        .await?;
+
+    if product.status != ProductStatus::Active {
+        return Ok(0);
+    }
+
+    let mut sent = 0u64;
+    for (user_id, old_price) in watchers {
+        let notification = Notification::new(
+            user_id,
+            NotificationKind::PriceDrop,
+            format!("{} baixou de preço", product.title),
+            format!("De {} para {} sats", old_price, product.price_sats),
+            Some(product.id.clone()),
+            None,
+            None,
+        );
+        if notify(pool, hub, notification).await? {
+            sent += 1;
+        }
+    }
+
+    Ok(sent)
+}
+
+// ─── Leilões acompanhados perto do fim ───────────────────────
+// Chamado a cada bloco aceito; avisa uma vez por usuário e leilão
+// quando faltam até AUCTION_ALERT_BLOCKS blocos.
+pub async fn process_auction_alerts(
+    pool:   &SqlitePool,
+    hub:    &Hub,
+    height: i64,
+) -> Result<u64, AppError> {
+    let ending = sqlx::query_as::<_, (String, String, String, i64, String)>(
+        "SELECT w.user_id, a.id, p.id, a.end_block, p.title
+         FROM auctions a
+         JOIN products p  ON p.id = a.product_id
+         JOIN watchlist w ON w.product_id = a.product_id
+         WHERE a.status = 'OPEN'
+           AND a.end_block > ?1
+           AND a.end_block <= ?1 + ?2
+           AND w.user_id != a.seller_id
+           AND NOT EXISTS (
+               SELECT 1 FROM notifications n
+               WHERE n.user_id = w.user_id AND n.auction_id = a.id AND n.kind = 'AUCTION_ENDING'
+           )",
+    )
+    .bind(height)
+    .bind(auction_alert_blocks())
+    .fetch_all(pool)
+    .await?;
+
+    let mut sent = 0u64;
+    for (user_id, auction_id, product_id, end_block, title) in ending {
+        let notification = Notification::new(
+            user_id,
+            NotificationKind::AuctionEnding,
+            format!("Leilão de {} está acabando", title),
+            format!("Encerra no bloco {} ({} restantes)", end_block, end_block - height),
+            Some(product_id),
+            Some(auction_id),
+            None,
+        );
+        if notify(pool, hub, notification).await? {
+            sent += 1;
+        }
+    }
+
+    Ok(sent)
+}
+
+// ─── Escrow aberto para o comprador travar ───────────────────
+// Leilão e oferta abrem o escrow sem ordem: o aviso diz ao comprador
+// quanto enviar ao endereço do escrow e até que bloco, para travar o
+// contrato em POST /api/contracts/:id/fund.
+pub async fn alert_escrow_to_fund(
+    pool:       &SqlitePool,
+    hub:        &Hub,
+    contract:   &Contract,
+    kind:       NotificationKind,
+    title:      String,
+    auction_id: Option<String>,
+) -> Result<bool, AppError> {
+    let buyer_id = sqlx::query_scalar::<_, String>("SELECT user_id FROM wallets WHERE pubkey = ?")
+        .bind(&contract.buyer_pubkey)
+        .fetch_one(pool)
+        .await?;
+
+    let notification = Notification::new(
+        buyer_id,
+        kind,
+        title,
+        format!(
+            "Envie {} sats a {} e trave o contrato {} até o bloco {}",
+            contract.amount_sats,
+            escrow_address(),
+            contract.id,
+            contract.expires_at_block
+        ),
+        Some(contract.product_id.clone()),
+        auction_id,
+        None,
+    );
+
+    notify(pool, hub, notification).await
+}
@@ -0,0 +1,146 @@
+use sqlx::SqlitePool;
+use chrono::Utc;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::offer::{Offer, OfferStatus};
+use crate::models::contract::Contract;
+use crate::models::notification::NotificationKind;
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::open_escrow;
+use crate::blockchain::notifications::alert_escrow_to_fund;
+use crate::ws::hub::Hub;
+
+/// Validade de uma oferta, em blocos (`OFFER_EXPIRY_BLOCKS`, padrão 20)
+pub fn offer_expiry_blocks() -> i64 {
+    env::var("OFFER_EXPIRY_BLOCKS")
+        .unwrap_or_else(|_| "20".into())
+        .parse()
+        .unwrap_or(20)
+}
+
+// ─── Buscar oferta por id ────────────────────────────────────
+pub async fn find_offer(pool: &SqlitePool, id: &str) -> Result<Offer, AppError> {
+    sqlx::query_as::<_, Offer>("SELECT * FROM offers WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Oferta não encontrada".into()))
+}
+
+// ─── Aplicar transição de status da oferta ───────────────────
+// Guarda otimista: só grava se a oferta ainda está no status lido.
+pub async fn set_offer_status(
+    pool:  &SqlitePool,
+    offer: &Offer,
+    next:  OfferStatus,
+) -> Result<(), AppError> {
+    if !offer.status.can_transition_to(next) {
+        return Err(AppError::Validation(format!(
+            "Oferta já está {}",
+            offer.status
+        )));
+    }
+
+    let affected = sqlx::query(
+        "UPDATE offers SET status = ?, updated_at = ? WHERE id = ? AND status = ?",
+    )
+    .bind(next)
+    .bind(Utc::now().to_rfc3339())
+    .bind(&offer.id)
+    .bind(offer.status)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Conflict(format!(
+            "Oferta {} não está mais {}",
+            offer.id, offer.status
+        )));
+    }
+
+    Ok(())
+}
+
+// ─── Aceitar oferta e abrir o escrow pelo valor negociado ────
+// O escrow reserva 1 unidade (devolvida quando o contrato expira ou é
+// reembolsado). Sem estoque livre, a oferta volta a PENDING. O
+// comprador é avisado para travar os fundos do contrato.
+pub async fn accept_offer(
+    pool:  &SqlitePool,
+    hub:   &Hub,
+    offer: &Offer,
+) -> Result<Contract, AppError> {
+    // Reivindicar a oferta antes de abrir o escrow (evita escrow duplicado)
+    set_offer_status(pool, offer, OfferStatus::Accepted).await?;
+
+    let buyer_address = sqlx::query_scalar::<_, String>(
+        "SELECT address FROM wallets WHERE user_id = ?",
+    )
+    .bind(&offer.buyer_id)
+    .fetch_one(pool)
+    .await?;
+
+    let contract = match open_escrow(pool, &buyer_address, &offer.product_id, offer.amount_sats, 1).await {
+        Ok(contract) => contract,
+        Err(e) => {
+            // Produto indisponível ou sem estoque: a oferta volta a ficar pendente
+            sqlx::query(
+                "UPDATE offers SET status = 'PENDING', updated_at = ?
+                 WHERE id = ? AND status = 'ACCEPTED' AND contract_id IS NULL",
+            )
+            .bind(Utc::now().to_rfc3339())
+            .bind(&offer.id)
+            .execute(pool)
+            .await?;
+            return Err(e);
+        }
+    };
+
+    sqlx::query("UPDATE offers SET contract_id = ?, updated_at = ? WHERE id = ?")
+        .bind(&contract.id)
+        .bind(Utc::now().to_rfc3339())
+        .bind(&offer.id)
+        .execute(pool)
+        .await?;
+
+    tracing::info!(
+        "Oferta {} aceita: {} sats (contrato {})",
+        offer.id, offer.amount_sats, contract.id
+    );
+
+    // A oferta já está aceita: falha no aviso só é registrada
+    let alert = alert_escrow_to_fund(
+        pool,
+        hub,
+        &contract,
+        NotificationKind::OfferAccepted,
+        "Oferta aceita".into(),
+        None,
+    )
+    .await;
+    if let Err(e) = alert {
+        tracing::warn!("Falha ao avisar comprador da oferta {}: {}", offer.id, e);
+    }
+
+    Ok(contract)
+}
+
+// ─── Expirar ofertas pendentes ───────────────────────────────
+// Chamado a cada bloco aceito, como `process_expired_contracts`.
+pub async fn process_expired_offers(pool: &SqlitePool) -> Result<u64, AppError> {
+    let (_, current_height, _) = get_latest_block(pool).await?;
+
+    let expired = sqlx::query(
+        "UPDATE offers SET status = 'EXPIRED', updated_at = ?
+         WHERE status = 'PENDING' AND expires_at_block <= ?",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(current_height)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(expired)
+}
@@ -0,0 +1,286 @@
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::reputation::{
+    MerkleSide, MerkleStep, ReputationEntry, ReputationLeaf, ReputationProof,
+    ReputationSnapshot,
+};
+use crate::blockchain::pow::sha256_hex;
+
+/// Intervalo entre fotografias de reputação (`REPUTATION_INTERVAL`, padrão 10 blocos).
+/// Nunca menor que 1 — zero dividiria por zero no meio da mineração.
+pub fn reputation_interval() -> i64 {
+    env::var("REPUTATION_INTERVAL")
+        .unwrap_or_else(|_| "10".into())
+        .parse::<i64>()
+        .unwrap_or(10)
+        .max(1)
+}
+
+// ─── Merkle tree ─────────────────────────────────────────────
+// Mesmo esquema da merkle root das TXs: pares concatenados em hex,
+// último elemento duplicado quando o nível é ímpar.
+pub fn merkle_root(leaves: &[String]) -> String {
+    if leaves.is_empty() {
+        return sha256_hex("empty");
+    }
+
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+
+    level.remove(0)
+}
+
+/// Caminho da folha `index` até a root
+pub fn merkle_proof(leaves: &[String], mut index: usize) -> Vec<MerkleStep> {
+    let mut path = Vec::new();
+    let mut level = leaves.to_vec();
+
+    while level.len() > 1 {
+        let sibling = if index.is_multiple_of(2) {
+            MerkleStep {
+                hash: level.get(index + 1).unwrap_or(&level[index]).clone(),
+                side: MerkleSide::Right,
+            }
+        } else {
+            MerkleStep { hash: level[index - 1].clone(), side: MerkleSide::Left }
+        };
+
+        path.push(sibling);
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    path
+}
+
+/// Refaz a root a partir da folha e do caminho
+pub fn verify_merkle_proof(leaf: &str, path: &[MerkleStep], root: &str) -> bool {
+    let computed = path.iter().fold(leaf.to_string(), |acc, step| match step.side {
+        MerkleSide::Left  => sha256_hex(&format!("{}{}", step.hash, acc)),
+        MerkleSide::Right => sha256_hex(&format!("{}{}", acc, step.hash)),
+    });
+
+    computed == root
+}
+
+fn next_level(level: &[String]) -> Vec<String> {
+    level
+        .chunks(2)
+        .map(|pair| {
+            let right = pair.get(1).unwrap_or(&pair[0]);
+            sha256_hex(&format!("{}{}", pair[0], right))
+        })
+        .collect()
+}
+
+// ─── Componentes a partir dos escrows e avaliações ───────────
+// `address = None` calcula todos os vendedores com algum escrow.
+pub async fn reputation_entries(
+    pool:    &SqlitePool,
+    address: Option<&str>,
+) -> Result<Vec<ReputationEntry>, AppError> {
+    let disputed = "EXISTS (SELECT 1 FROM contract_events e
+                            WHERE e.contract_id = c.id AND e.event_type = 'DISPUTED')";
+
+    let query = format!(
+        "SELECT w.address,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.state = 'RELEASED' AND NOT {d}) AS completed,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.state = 'REFUNDED'
+               AND c.lock_tx_id IS NOT NULL AND NOT {d}) AS refunds,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.state = 'RELEASED' AND {d}) AS disputes_won,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.state = 'REFUNDED' AND {d}) AS disputes_lost,
+            (SELECT COUNT(*) FROM reviews r JOIN products p ON p.id = r.product_id
+             WHERE p.seller_id = w.user_id AND r.verified = 1 AND r.hidden_at IS NULL) AS verified_reviews,
+            (SELECT AVG(r.rating) FROM reviews r JOIN products p ON p.id = r.product_id
+             WHERE p.seller_id = w.user_id AND r.verified = 1 AND r.hidden_at IS NULL) AS avg_rating
+         FROM wallets w
+         WHERE (?1 IS NULL AND EXISTS (SELECT 1 FROM contracts c WHERE c.seller_pubkey = w.pubkey))
+            OR w.address = ?1
+         ORDER BY w.address ASC",
+        d = disputed
+    );
+
+    let entries = sqlx::query_as::<_, ReputationEntry>(&query)
+        .bind(address)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(entries)
+}
+
+// ─── Fotografar reputações ───────────────────────────────────
+// Chamado a cada bloco aceito; só grava nas alturas múltiplas do
+// intervalo. A root fica pendente até o próximo bloco ancorá-la.
+pub async fn take_snapshot(
+    pool:   &SqlitePool,
+    height: i64,
+) -> Result<Option<ReputationSnapshot>, AppError> {
+    if height % reputation_interval() != 0 {
+        return Ok(None);
+    }
+
+    let entries = reputation_entries(pool, None).await?;
+    let leaves: Vec<String> = entries.iter().map(|e| e.leaf_hash()).collect();
+
+    let snapshot = ReputationSnapshot::new(height, merkle_root(&leaves), leaves.len() as i64);
+
+    sqlx::query(
+        "INSERT INTO reputation_snapshots (id, height, root, leaf_count, block_hash, block_height, created_at)
+         VALUES (?, ?, ?, ?, NULL, NULL, ?)",
+    )
+    .bind(&snapshot.id)
+    .bind(snapshot.height)
+    .bind(&snapshot.root)
+    .bind(snapshot.leaf_count)
+    .bind(&snapshot.created_at)
+    .execute(pool)
+    .await?;
+
+    for (position, (entry, leaf_hash)) in entries.iter().zip(&leaves).enumerate() {
+        sqlx::query(
+            "INSERT INTO reputation_leaves (snapshot_id, position, address, score, completed, refunds,
+             disputes_won, disputes_lost, verified_reviews, avg_rating, leaf_hash)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&snapshot.id)
+        .bind(position as i64)
+        .bind(&entry.address)
+        .bind(entry.score())
+        .bind(entry.completed)
+        .bind(entry.refunds)
+        .bind(entry.disputes_won)
+        .bind(entry.disputes_lost)
+        .bind(entry.verified_reviews)
+        .bind(entry.avg_rating)
+        .bind(leaf_hash)
+        .execute(pool)
+        .await?;
+    }
+
+    tracing::info!(
+        "Reputação fotografada no bloco {}: {} vendedor(es), root {}",
+        height, snapshot.leaf_count, snapshot.root
+    );
+
+    Ok(Some(snapshot))
+}
+
+// ─── Fotografia aguardando âncora ────────────────────────────
+pub async fn pending_snapshot(pool: &SqlitePool) -> Result<Option<ReputationSnapshot>, AppError> {
+    let snapshot = sqlx::query_as::<_, ReputationSnapshot>(
+        "SELECT * FROM reputation_snapshots WHERE block_hash IS NULL ORDER BY height DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(snapshot)
+}
+
+// ─── Marcar fotografia como ancorada no bloco ────────────────
+pub async fn anchor_snapshot(
+    pool:         &SqlitePool,
+    snapshot_id:  &str,
+    block_hash:   &str,
+    block_height: i64,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE reputation_snapshots SET block_hash = ?, block_height = ? WHERE id = ? AND block_hash IS NULL",
+    )
+    .bind(block_hash)
+    .bind(block_height)
+    .bind(snapshot_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Prova de inclusão na última fotografia ancorada ─────────
+pub async fn reputation_proof(
+    pool:    &SqlitePool,
+    address: &str,
+) -> Result<Option<ReputationProof>, AppError> {
+    let Some(snapshot) = sqlx::query_as::<_, ReputationSnapshot>(
+        "SELECT s.* FROM reputation_snapshots s
+         JOIN reputation_leaves l ON l.snapshot_id = s.id
+         WHERE l.address = ? AND s.block_hash IS NOT NULL
+         ORDER BY s.height DESC
+         LIMIT 1",
+    )
+    .bind(address)
+    .fetch_optional(pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let leaves = sqlx::query_scalar::<_, String>(
+        "SELECT leaf_hash FROM reputation_leaves WHERE snapshot_id = ? ORDER BY position ASC",
+    )
+    .bind(&snapshot.id)
+    .fetch_all(pool)
+    .await?;
+
+    let leaf = sqlx::query_as::<_, ReputationLeaf>(
+        "SELECT * FROM reputation_leaves WHERE snapshot_id = ? AND address = ?",
+    )
+    .bind(&snapshot.id)
+    .bind(address)
+    .fetch_one(pool)
+    .await?;
+
+    let path  = merkle_proof(&leaves, leaf.position as usize);
+    let valid = verify_merkle_proof(&leaf.leaf_hash, &path, &snapshot.root);
+
+    Ok(Some(ReputationProof {
+        score:        leaf.score,
+        path,
+        leaf_hash:    leaf.leaf_hash,
+        position:     leaf.position,
+        root:         snapshot.root,
+        block_hash:   snapshot.block_hash.unwrap_or_default(),
+        block_height: snapshot.block_height.unwrap_or_default(),
+        entry:        leaf.entry,
+        valid,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<String> {
+        (0..n).map(|i| sha256_hex(&i.to_string())).collect()
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf() {
+        for n in [1, 2, 3, 5, 8] {
+            let leaves = leaves(n);
+            let root = merkle_root(&leaves);
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let path = merkle_proof(&leaves, i);
+                assert!(verify_merkle_proof(leaf, &path, &root), "n={} i={}", n, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_leaf() {
+        let leaves = leaves(4);
+        let root = merkle_root(&leaves);
+        let path = merkle_proof(&leaves, 1);
+
+        assert!(!verify_merkle_proof(&leaves[2], &path, &root));
+    }
+}
@@ -0,0 +1,139 @@
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::contract::Contract;
+use crate::models::product::{Product, ProductRevision};
+
+// ─── Imagens que entram no item_hash ─────────────────────────
+// Ordem de exibição: trocar ou reordenar fotos muda o hash dos
+// próximos escrows, nunca o dos já abertos.
+pub async fn product_image_hashes(
+    pool:       &SqlitePool,
+    product_id: &str,
+) -> Result<Vec<String>, AppError> {
+    let hashes = sqlx::query_scalar::<_, String>(
+        "SELECT sha256 FROM product_media
+         WHERE product_id = ? AND kind = 'IMAGE'
+         ORDER BY position ASC, created_at ASC",
+    )
+    .bind(product_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(hashes)
+}
+
+// ─── Revisão do estado atual do produto ──────────────────────
+// Chamado após criar/editar o produto ou suas imagens e ao abrir um
+// escrow. Idempotente: só grava quando o conteúdo mudou.
+pub async fn snapshot_revision(
+    pool:       &SqlitePool,
+    product_id: &str,
+) -> Result<ProductRevision, AppError> {
+    let product = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
+        .bind(product_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    let image_hashes = product_image_hashes(pool, product_id).await?;
+    let current = ProductRevision::from_product(&product, &image_hashes);
+
+    let latest = sqlx::query_as::<_, ProductRevision>(
+        "SELECT * FROM product_revisions WHERE product_id = ? ORDER BY revision DESC LIMIT 1",
+    )
+    .bind(product_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(latest) = latest {
+        if latest.same_content(&current) {
+            return Ok(latest);
+        }
+    }
+
+    // Número atribuído no próprio INSERT (escritas são serializadas no SQLite)
+    sqlx::query(
+        "INSERT INTO product_revisions (id, product_id, revision, title, description, price_sats,
+         category, condition, location, image_hashes, item_hash, created_at)
+         SELECT ?1, ?2, COALESCE(MAX(revision), 0) + 1, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11
+         FROM product_revisions WHERE product_id = ?2",
+    )
+    .bind(&current.id)
+    .bind(&current.product_id)
+    .bind(&current.title)
+    .bind(&current.description)
+    .bind(current.price_sats)
+    .bind(&current.category)
+    .bind(&current.condition)
+    .bind(&current.location)
+    .bind(&current.image_hashes)
+    .bind(&current.item_hash)
+    .bind(&current.created_at)
+    .execute(pool)
+    .await?;
+
+    let revision = sqlx::query_as::<_, ProductRevision>("SELECT * FROM product_revisions WHERE id = ?")
+        .bind(&current.id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(revision)
+}
+
+// ─── Revisão comprometida por um contrato ────────────────────
+// Contratos anteriores ao histórico não têm revision_id: busca a
+// revisão com o mesmo item_hash (ou fotografa o produto, se ainda
+// não foi editado desde a venda).
+pub async fn contract_revision(
+    pool:     &SqlitePool,
+    contract: &Contract,
+) -> Result<ProductRevision, AppError> {
+    if let Some(ref revision_id) = contract.revision_id {
+        let revision = sqlx::query_as::<_, ProductRevision>(
+            "SELECT * FROM product_revisions WHERE id = ?",
+        )
+        .bind(revision_id)
+        .fetch_one(pool)
+        .await?;
+
+        return Ok(revision);
+    }
+
+    let by_hash = sqlx::query_as::<_, ProductRevision>(
+        "SELECT * FROM product_revisions WHERE product_id = ? AND item_hash = ?
+         ORDER BY revision ASC LIMIT 1",
+    )
+    .bind(&contract.product_id)
+    .bind(&contract.item_hash)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(revision) = by_hash {
+        return Ok(revision);
+    }
+
+    let current = snapshot_revision(pool, &contract.product_id).await?;
+    if current.item_hash == contract.item_hash {
+        return Ok(current);
+    }
+
+    Err(AppError::NotFound(
+        "Item do contrato não recuperável: produto editado antes do histórico de revisões".into(),
+    ))
+}
+
+// ─── Histórico de revisões ───────────────────────────────────
+pub async fn list_revisions(
+    pool:       &SqlitePool,
+    product_id: &str,
+) -> Result<Vec<ProductRevision>, AppError> {
+    let revisions = sqlx::query_as::<_, ProductRevision>(
+        "SELECT * FROM product_revisions WHERE product_id = ? ORDER BY revision DESC",
+    )
+    .bind(product_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(revisions)
+}
@@ -0,0 +1,179 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::role::{satisfies, Role, RoleAction, RoleAudit};
+use crate::models::user::Claims;
+
+// ─── Papéis atuais de um usuário ─────────────────────────────
+pub async fn load_roles(pool: &SqlitePool, user_id: &str) -> Result<Vec<Role>, AppError> {
+    let roles = sqlx::query_scalar::<_, Role>(
+        "SELECT role FROM user_roles WHERE user_id = ? ORDER BY granted_at ASC",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(roles)
+}
+
+// ─── Exigir papel ────────────────────────────────────────────
+// Decide só pelo banco: os papéis do token são os da emissão, então
+// concessões e revogações valem sem esperar novo login.
+pub async fn require_role(pool: &SqlitePool, claims: &Claims, role: Role) -> Result<(), AppError> {
+    let current = load_roles(pool, &claims.sub).await?;
+    if satisfies(&current, role) {
+        Ok(())
+    } else {
+        Err(AppError::Unauthorized)
+    }
+}
+
+// ─── Conceder papel (com auditoria) ──────────────────────────
+// Retorna false se o usuário já tinha o papel.
+pub async fn grant_role(
+    pool:          &SqlitePool,
+    user_id:       &str,
+    role:          Role,
+    actor_address: Option<&str>,
+    reason:        &str,
+) -> Result<bool, AppError> {
+    let inserted = sqlx::query(
+        "INSERT OR IGNORE INTO user_roles (user_id, role, granted_by, granted_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(user_id)
+    .bind(role)
+    .bind(actor_address)
+    .bind(Utc::now().to_rfc3339())
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if inserted == 0 {
+        return Ok(false);
+    }
+
+    record_role_audit(pool, &RoleAudit::new(
+        user_id.to_string(),
+        role,
+        RoleAction::Grant,
+        actor_address.map(String::from),
+        reason.to_string(),
+    ))
+    .await?;
+
+    Ok(true)
+}
+
+// ─── Revogar papel (com auditoria) ───────────────────────────
+// Retorna false se o usuário não tinha o papel.
+pub async fn revoke_role(
+    pool:          &SqlitePool,
+    user_id:       &str,
+    role:          Role,
+    actor_address: &str,
+    reason:        &str,
+) -> Result<bool, AppError> {
+    let removed = sqlx::query("DELETE FROM user_roles WHERE user_id = ? AND role = ?")
+        .bind(user_id)
+        .bind(role)
+        .execute(pool)
+        .await?
+        .rows_affected();
+
+    if removed == 0 {
+        return Ok(false);
+    }
+
+    record_role_audit(pool, &RoleAudit::new(
+        user_id.to_string(),
+        role,
+        RoleAction::Revoke,
+        Some(actor_address.to_string()),
+        reason.to_string(),
+    ))
+    .await?;
+
+    Ok(true)
+}
+
+async fn record_role_audit(pool: &SqlitePool, entry: &RoleAudit) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO role_audit (id, user_id, role, action, actor_address, reason, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&entry.id)
+    .bind(&entry.user_id)
+    .bind(entry.role)
+    .bind(entry.action)
+    .bind(&entry.actor_address)
+    .bind(&entry.reason)
+    .bind(&entry.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Primeiro admin ──────────────────────────────────────────
+// Carteiras em `ADMIN_ADDRESSES` recebem o papel admin ao subir o
+// servidor, só enquanto não houver nenhum admin; depois disso, papéis
+// são geridos pela API e uma revogação não é desfeita no restart.
+pub async fn bootstrap_admins(pool: &SqlitePool) -> Result<u64, AppError> {
+    let existing = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM user_roles WHERE role = 'admin'",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if existing > 0 {
+        return Ok(0);
+    }
+
+    let admins = env::var("ADMIN_ADDRESSES").unwrap_or_default();
+    let mut granted = 0u64;
+
+    for address in admins.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+        let user_id = sqlx::query_scalar::<_, String>(
+            "SELECT user_id FROM wallets WHERE address = ?",
+        )
+        .bind(address)
+        .fetch_optional(pool)
+        .await?;
+
+        match user_id {
+            Some(user_id) => {
+                if grant_role(pool, &user_id, Role::Admin, None, "ADMIN_ADDRESSES").await? {
+                    granted += 1;
+                }
+            }
+            None => tracing::warn!("ADMIN_ADDRESSES: carteira {} não encontrada", address),
+        }
+    }
+
+    Ok(granted)
+}
+
+// ─── Árbitro para um novo escrow ─────────────────────────────
+// Usuário com papel arbiter e menos contratos em aberto; sem
+// nenhum cadastrado, vale o `ARBITER_PUBKEY` da plataforma.
+pub async fn pick_arbiter(pool: &SqlitePool) -> Result<String, AppError> {
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT w.pubkey
+         FROM user_roles r
+         JOIN wallets w ON w.user_id = r.user_id
+         LEFT JOIN contracts c
+                ON c.arbiter_pubkey = w.pubkey AND c.state IN ('PENDING', 'LOCKED', 'DISPUTED')
+         WHERE r.role = 'arbiter'
+         GROUP BY w.pubkey
+         ORDER BY COUNT(c.id) ASC, MIN(r.granted_at) ASC
+         LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(pubkey.unwrap_or_else(|| {
+        env::var("ARBITER_PUBKEY").unwrap_or_else(|_| "PAPERMARKET_ARB_MASTER_01".into())
+    }))
+}
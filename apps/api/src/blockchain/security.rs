@@ -0,0 +1,311 @@
+use chrono::{DateTime, Duration, Utc};
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::security::{lockout_seconds, SecurityEvent, SecurityEventKind};
+
+/// Tentativas de recuperação por hora, por carteira e por IP
+/// (`RECOVERY_MAX_ATTEMPTS`, padrão 5)
+pub fn recovery_max_attempts() -> i64 {
+    env::var("RECOVERY_MAX_ATTEMPTS")
+        .unwrap_or_else(|_| "5".into())
+        .parse()
+        .unwrap_or(5)
+}
+
+/// Falhas seguidas de login até o primeiro bloqueio (`LOGIN_LOCKOUT_THRESHOLD`, padrão 5)
+pub fn login_lockout_threshold() -> i64 {
+    env::var("LOGIN_LOCKOUT_THRESHOLD")
+        .unwrap_or_else(|_| "5".into())
+        .parse()
+        .unwrap_or(5)
+}
+
+/// Primeiro bloqueio em segundos, dobra a cada nova falha (`LOGIN_LOCKOUT_BASE_SECS`, padrão 30)
+pub fn login_lockout_base_secs() -> i64 {
+    env::var("LOGIN_LOCKOUT_BASE_SECS")
+        .unwrap_or_else(|_| "30".into())
+        .parse()
+        .unwrap_or(30)
+}
+
+/// Teto do bloqueio em segundos (`LOGIN_LOCKOUT_MAX_SECS`, padrão 3600)
+pub fn login_lockout_max_secs() -> i64 {
+    env::var("LOGIN_LOCKOUT_MAX_SECS")
+        .unwrap_or_else(|_| "3600".into())
+        .parse()
+        .unwrap_or(3600)
+}
+
+/// Códigos TOTP errados seguidos até o primeiro bloqueio (`TOTP_LOCKOUT_THRESHOLD`, padrão 5)
+pub fn totp_lockout_threshold() -> i64 {
+    env::var("TOTP_LOCKOUT_THRESHOLD")
+        .unwrap_or_else(|_| "5".into())
+        .parse()
+        .unwrap_or(5)
+}
+
+/// Primeiro bloqueio do 2FA em segundos, dobra a cada novo erro (`TOTP_LOCKOUT_BASE_SECS`, padrão 60)
+pub fn totp_lockout_base_secs() -> i64 {
+    env::var("TOTP_LOCKOUT_BASE_SECS")
+        .unwrap_or_else(|_| "60".into())
+        .parse()
+        .unwrap_or(60)
+}
+
+/// Teto do bloqueio do 2FA em segundos (`TOTP_LOCKOUT_MAX_SECS`, padrão 3600)
+pub fn totp_lockout_max_secs() -> i64 {
+    env::var("TOTP_LOCKOUT_MAX_SECS")
+        .unwrap_or_else(|_| "3600".into())
+        .parse()
+        .unwrap_or(3600)
+}
+
+// ─── Registrar evento ────────────────────────────────────────
+pub async fn record_security_event(pool: &SqlitePool, event: &SecurityEvent) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO security_events (id, user_id, address, event_type, ip_address, detail, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&event.id)
+    .bind(&event.user_id)
+    .bind(&event.address)
+    .bind(event.event_type)
+    .bind(&event.ip_address)
+    .bind(&event.detail)
+    .bind(&event.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Limite de tentativas de recuperação ─────────────────────
+// Janela deslizante de 1 hora; conta sucessos e falhas. Estourado
+// o limite → 429 com os segundos até a tentativa mais antiga sair.
+pub async fn check_recovery_limit(
+    pool:       &SqlitePool,
+    address:    Option<&str>,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let since = Utc::now() - Duration::hours(1);
+
+    let (attempts, oldest) = sqlx::query_as::<_, (i64, Option<String>)>(
+        "SELECT COUNT(*), MIN(created_at) FROM security_events
+         WHERE event_type IN ('RECOVERY_SUCCEEDED', 'RECOVERY_FAILED')
+           AND created_at > ?1
+           AND ((?2 IS NOT NULL AND address = ?2) OR (?3 IS NOT NULL AND ip_address = ?3))",
+    )
+    .bind(since.to_rfc3339())
+    .bind(address)
+    .bind(ip_address)
+    .fetch_one(pool)
+    .await?;
+
+    if attempts < recovery_max_attempts() {
+        return Ok(());
+    }
+
+    let retry_after = oldest
+        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+        .map(|t| (t.with_timezone(&Utc) + Duration::hours(1) - Utc::now()).num_seconds().max(1))
+        .unwrap_or(3600);
+
+    Err(AppError::TooManyRequests(retry_after))
+}
+
+// ─── Bloqueio de login ───────────────────────────────────────
+// Por username + IP: quem erra a senha tranca só a própria origem,
+// nunca o dono da conta em outro IP. Conferido antes do Argon2.
+pub async fn check_login_lockout(
+    pool:       &SqlitePool,
+    username:   &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let locked_until = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT locked_until FROM login_failures WHERE username = ? AND ip_address = ?",
+    )
+    .bind(username)
+    .bind(ip_address.unwrap_or_default())
+    .fetch_optional(pool)
+    .await?
+    .flatten()
+    .and_then(|t| DateTime::parse_from_rfc3339(&t).ok());
+
+    match locked_until {
+        Some(until) if until > Utc::now() => Err(AppError::TooManyRequests(
+            (until.with_timezone(&Utc) - Utc::now()).num_seconds().max(1),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ─── Falha de login ──────────────────────────────────────────
+// Falhas com mais de 24 h não contam. Ao atingir o limite, cada
+// nova falha dobra o bloqueio daquele IP (até o teto).
+pub async fn record_login_failure(
+    pool:       &SqlitePool,
+    username:   &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let now = Utc::now();
+    let ip_key = ip_address.unwrap_or_default();
+
+    let previous = sqlx::query_as::<_, (i64, String)>(
+        "SELECT failed_count, last_failed_at FROM login_failures WHERE username = ? AND ip_address = ?",
+    )
+    .bind(username)
+    .bind(ip_key)
+    .fetch_optional(pool)
+    .await?;
+
+    let failed_count = previous
+        .filter(|(_, last)| {
+            DateTime::parse_from_rfc3339(last)
+                .map(|t| t.with_timezone(&Utc) > now - Duration::hours(24))
+                .unwrap_or(false)
+        })
+        .map(|(count, _)| count)
+        .unwrap_or(0)
+        + 1;
+
+    let lock_secs = lockout_seconds(
+        failed_count,
+        login_lockout_threshold(),
+        login_lockout_base_secs(),
+        login_lockout_max_secs(),
+    );
+    let locked_until = (lock_secs > 0).then(|| (now + Duration::seconds(lock_secs)).to_rfc3339());
+
+    sqlx::query(
+        "INSERT INTO login_failures (username, ip_address, failed_count, locked_until, last_failed_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(username, ip_address) DO UPDATE SET failed_count = excluded.failed_count,
+             locked_until = excluded.locked_until, last_failed_at = excluded.last_failed_at",
+    )
+    .bind(username)
+    .bind(ip_key)
+    .bind(failed_count)
+    .bind(&locked_until)
+    .bind(now.to_rfc3339())
+    .execute(pool)
+    .await?;
+
+    if lock_secs > 0 {
+        record_security_event(pool, &SecurityEvent::new(
+            None,
+            None,
+            SecurityEventKind::LoginLocked,
+            ip_address.map(String::from),
+            Some(format!("{}: {} falhas seguidas, IP bloqueado por {}s", username, failed_count, lock_secs)),
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+// ─── Login bem-sucedido zera o contador daquele IP ───────────
+pub async fn clear_login_failures(
+    pool:       &SqlitePool,
+    username:   &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM login_failures WHERE username = ? AND ip_address = ?")
+        .bind(username)
+        .bind(ip_address.unwrap_or_default())
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// ─── Bloqueio do segundo fator ───────────────────────────────
+// Por usuário: o código só vale para aquela conta, então a origem
+// não importa. Conferido antes de olhar o código.
+pub async fn check_totp_lockout(pool: &SqlitePool, user_id: &str) -> Result<(), AppError> {
+    let locked_until = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT locked_until FROM totp_failures WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .flatten()
+    .and_then(|t| DateTime::parse_from_rfc3339(&t).ok());
+
+    match locked_until {
+        Some(until) if until > Utc::now() => Err(AppError::TooManyRequests(
+            (until.with_timezone(&Utc) - Utc::now()).num_seconds().max(1),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ─── Código TOTP errado ──────────────────────────────────────
+// Mesma regra do login: erros com mais de 24 h não contam e, passado
+// o limite, cada novo erro dobra o bloqueio (até o teto).
+pub async fn record_totp_failure(pool: &SqlitePool, user_id: &str) -> Result<(), AppError> {
+    let now = Utc::now();
+
+    let previous = sqlx::query_as::<_, (i64, String)>(
+        "SELECT failed_count, last_failed_at FROM totp_failures WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let failed_count = previous
+        .filter(|(_, last)| {
+            DateTime::parse_from_rfc3339(last)
+                .map(|t| t.with_timezone(&Utc) > now - Duration::hours(24))
+                .unwrap_or(false)
+        })
+        .map(|(count, _)| count)
+        .unwrap_or(0)
+        + 1;
+
+    let lock_secs = lockout_seconds(
+        failed_count,
+        totp_lockout_threshold(),
+        totp_lockout_base_secs(),
+        totp_lockout_max_secs(),
+    );
+    let locked_until = (lock_secs > 0).then(|| (now + Duration::seconds(lock_secs)).to_rfc3339());
+
+    sqlx::query(
+        "INSERT INTO totp_failures (user_id, failed_count, locked_until, last_failed_at)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT(user_id) DO UPDATE SET failed_count = excluded.failed_count,
+             locked_until = excluded.locked_until, last_failed_at = excluded.last_failed_at",
+    )
+    .bind(user_id)
+    .bind(failed_count)
+    .bind(&locked_until)
+    .bind(now.to_rfc3339())
+    .execute(pool)
+    .await?;
+
+    if lock_secs > 0 {
+        record_security_event(pool, &SecurityEvent::new(
+            Some(user_id.to_string()),
+            None,
+            SecurityEventKind::TotpLocked,
+            None,
+            Some(format!("{} códigos errados seguidos, 2FA bloqueado por {}s", failed_count, lock_secs)),
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+// ─── Código aceito zera o contador ───────────────────────────
+pub async fn clear_totp_failures(pool: &SqlitePool, user_id: &str) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM totp_failures WHERE user_id = ?")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
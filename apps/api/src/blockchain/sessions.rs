@@ -0,0 +1,207 @@
+use chrono::{Duration, Utc};
+use rand::Rng;
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::session::Session;
+use crate::blockchain::pow::sha256_hex;
+
+/// Validade do access token em minutos (`JWT_ACCESS_MINUTES`, padrão 15)
+pub fn access_token_minutes() -> i64 {
+    env::var("JWT_ACCESS_MINUTES")
+        .unwrap_or_else(|_| "15".into())
+        .parse()
+        .unwrap_or(15)
+}
+
+/// Validade da sessão sem uso em dias (`REFRESH_TOKEN_DAYS`, padrão 30)
+pub fn refresh_token_days() -> i64 {
+    env::var("REFRESH_TOKEN_DAYS")
+        .unwrap_or_else(|_| "30".into())
+        .parse()
+        .unwrap_or(30)
+}
+
+/// Validade do desafio de login em segundos (`AUTH_CHALLENGE_SECONDS`, padrão 300)
+pub fn auth_challenge_seconds() -> i64 {
+    env::var("AUTH_CHALLENGE_SECONDS")
+        .unwrap_or_else(|_| "300".into())
+        .parse()
+        .unwrap_or(300)
+}
+
+// ─── Refresh token ───────────────────────────────────────────
+// 32 bytes aleatórios em hex; o banco guarda apenas o SHA-256.
+pub fn generate_refresh_token() -> String {
+    hex::encode(rand::thread_rng().gen::<[u8; 32]>())
+}
+
+pub fn hash_refresh_token(token: &str) -> String {
+    sha256_hex(token)
+}
+
+// ─── Criar sessão ────────────────────────────────────────────
+pub async fn create_session(pool: &SqlitePool, session: &Session) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO sessions (id, user_id, refresh_hash, previous_hash, access_jti, user_agent,
+         ip_address, created_at, last_used_at, expires_at, revoked_at)
+         VALUES (?, ?, ?, NULL, ?, ?, ?, ?, ?, ?, NULL)",
+    )
+    .bind(&session.id)
+    .bind(&session.user_id)
+    .bind(&session.refresh_hash)
+    .bind(&session.access_jti)
+    .bind(&session.user_agent)
+    .bind(&session.ip_address)
+    .bind(&session.created_at)
+    .bind(&session.last_used_at)
+    .bind(&session.expires_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Sessão dona de um refresh token ─────────────────────────
+// Um token já girado que volta a aparecer indica vazamento: a
+// sessão inteira é revogada e o cliente precisa logar de novo.
+pub async fn session_for_refresh(pool: &SqlitePool, refresh_token: &str) -> Result<Session, AppError> {
+    let hash = hash_refresh_token(refresh_token);
+
+    let session = sqlx::query_as::<_, Session>("SELECT * FROM sessions WHERE refresh_hash = ?")
+        .bind(&hash)
+        .fetch_optional(pool)
+        .await?;
+
+    if let Some(session) = session {
+        if !session.is_active(Utc::now()) {
+            return Err(AppError::InvalidToken);
+        }
+        return Ok(session);
+    }
+
+    let reused = sqlx::query_as::<_, Session>(
+        "SELECT * FROM sessions WHERE previous_hash = ? AND revoked_at IS NULL",
+    )
+    .bind(&hash)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some(session) = reused {
+        tracing::warn!("Refresh token reutilizado — revogando sessão {}", session.id);
+        revoke_session(pool, &session.id, &session.user_id).await?;
+    }
+
+    Err(AppError::InvalidToken)
+}
+
+// ─── Girar refresh token ─────────────────────────────────────
+// Novo refresh + novo access; o access anterior entra na lista
+// de revogação. Guardado pelo hash atual contra uso concorrente.
+pub async fn rotate_session(
+    pool:        &SqlitePool,
+    session:     &Session,
+    new_refresh: &str,
+    new_jti:     &str,
+) -> Result<(), AppError> {
+    let now = Utc::now();
+
+    let updated = sqlx::query(
+        "UPDATE sessions
+         SET previous_hash = refresh_hash, refresh_hash = ?, access_jti = ?,
+             last_used_at = ?, expires_at = ?
+         WHERE id = ? AND refresh_hash = ? AND revoked_at IS NULL",
+    )
+    .bind(hash_refresh_token(new_refresh))
+    .bind(new_jti)
+    .bind(now.to_rfc3339())
+    .bind((now + Duration::days(refresh_token_days())).to_rfc3339())
+    .bind(&session.id)
+    .bind(&session.refresh_hash)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(AppError::InvalidToken);
+    }
+
+    revoke_jti(pool, &session.access_jti, &session.id).await
+}
+
+// ─── Revogar uma sessão ──────────────────────────────────────
+// Retorna false se a sessão não existe, é de outro usuário ou
+// já estava revogada.
+pub async fn revoke_session(pool: &SqlitePool, session_id: &str, user_id: &str) -> Result<bool, AppError> {
+    let access_jti = sqlx::query_scalar::<_, String>(
+        "UPDATE sessions SET revoked_at = ?
+         WHERE id = ? AND user_id = ? AND revoked_at IS NULL
+         RETURNING access_jti",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(session_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match access_jti {
+        Some(jti) => {
+            revoke_jti(pool, &jti, session_id).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// ─── Revogar todas as sessões do usuário ─────────────────────
+pub async fn revoke_all_sessions(pool: &SqlitePool, user_id: &str) -> Result<u64, AppError> {
+    let sessions = sqlx::query_scalar::<_, String>(
+        "SELECT id FROM sessions WHERE user_id = ? AND revoked_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut revoked = 0u64;
+    for session_id in sessions {
+        if revoke_session(pool, &session_id, user_id).await? {
+            revoked += 1;
+        }
+    }
+
+    Ok(revoked)
+}
+
+// ─── Lista de revogação de access tokens ─────────────────────
+// A entrada vale até o token expirar sozinho; depois é podada.
+async fn revoke_jti(pool: &SqlitePool, jti: &str, session_id: &str) -> Result<(), AppError> {
+    let now = Utc::now();
+
+    sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < ?")
+        .bind(now.to_rfc3339())
+        .execute(pool)
+        .await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO revoked_tokens (jti, session_id, expires_at, revoked_at)
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(jti)
+    .bind(session_id)
+    .bind((now + Duration::minutes(access_token_minutes())).to_rfc3339())
+    .bind(now.to_rfc3339())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn is_token_revoked(pool: &SqlitePool, jti: &str) -> Result<bool, AppError> {
+    let revoked = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM revoked_tokens WHERE jti = ?")
+        .bind(jti)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(revoked > 0)
+}
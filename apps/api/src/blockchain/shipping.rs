@@ -0,0 +1,92 @@
+use sqlx::SqlitePool;
+use std::env;
+
+use crate::errors::AppError;
+use crate::models::contract::{Contract, ContractState};
+use crate::models::shipment::{Shipment, ShipmentEvent};
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::transition_contract;
+
+/// Blocos após a entrega estimada em que o comprador ainda pode
+/// disputar (`SHIPPING_DISPUTE_WINDOW_BLOCKS`, padrão 20)
+pub fn shipping_dispute_window_blocks() -> i64 {
+    env::var("SHIPPING_DISPUTE_WINDOW_BLOCKS")
+        .unwrap_or_else(|_| "20".into())
+        .parse()
+        .unwrap_or(20)
+}
+
+/// Maior prazo de entrega aceito (`SHIPPING_MAX_DELIVERY_BLOCKS`, padrão 500)
+pub fn shipping_max_delivery_blocks() -> i64 {
+    env::var("SHIPPING_MAX_DELIVERY_BLOCKS")
+        .unwrap_or_else(|_| "500".into())
+        .parse()
+        .unwrap_or(500)
+}
+
+// ─── Buscar envio de um contrato ─────────────────────────────
+pub async fn find_shipment(pool: &SqlitePool, contract_id: &str) -> Result<Shipment, AppError> {
+    sqlx::query_as::<_, Shipment>("SELECT * FROM shipments WHERE contract_id = ?")
+        .bind(contract_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Envio não encontrado".into()))
+}
+
+// ─── Gravar evento na linha do tempo ─────────────────────────
+pub async fn record_shipment_event(pool: &SqlitePool, event: &ShipmentEvent) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO shipment_events (id, shipment_id, event_type, reported_by, description,
+         block_height, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&event.id)
+    .bind(&event.shipment_id)
+    .bind(event.event_type)
+    .bind(&event.reported_by)
+    .bind(&event.description)
+    .bind(event.block_height)
+    .bind(&event.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// ─── Liberar escrows enviados sem disputa ────────────────────
+// Chamado a cada bloco aceito, como `process_expired_contracts`.
+// Disputa aberta tira o contrato de LOCKED e suspende a liberação.
+pub async fn process_auto_releases(pool: &SqlitePool) -> Result<u64, AppError> {
+    let (_, current_height, _) = get_latest_block(pool).await?;
+    let mut released = 0u64;
+
+    let due = sqlx::query_as::<_, Contract>(
+        "SELECT c.* FROM contracts c
+         JOIN shipments s ON s.contract_id = c.id
+         WHERE c.state = 'LOCKED' AND s.auto_release_block <= ?",
+    )
+    .bind(current_height)
+    .fetch_all(pool)
+    .await?;
+
+    for contract in due {
+        let result = transition_contract(
+            pool,
+            &contract,
+            ContractState::Released,
+            None,
+            format!(
+                "Liberação automática no bloco {}: enviado e sem disputa no prazo.",
+                current_height
+            ),
+        )
+        .await;
+
+        match result {
+            Ok(_)  => released += 1,
+            Err(e) => tracing::warn!("Falha ao liberar contrato {}: {}", contract.id, e),
+        }
+    }
+
+    Ok(released)
+}
@@ -0,0 +1,154 @@
+use chrono::Utc;
+use sqlx::SqlitePool;
+use std::env;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::crypto::totp::verify_totp;
+use crate::models::two_factor::{is_totp_format, normalize_backup_code, UserTotp};
+use crate::blockchain::pow::sha256_hex;
+use crate::blockchain::security::{check_totp_lockout, clear_totp_failures, record_totp_failure};
+
+/// Nome exibido no app autenticador (`TOTP_ISSUER`, padrão PaperMarket)
+pub fn totp_issuer() -> String {
+    env::var("TOTP_ISSUER").unwrap_or_else(|_| "PaperMarket".into())
+}
+
+/// Como o segundo fator foi atendido
+#[derive(Debug, PartialEq, Eq)]
+pub enum SecondFactor {
+    NotEnabled,   // usuário sem 2FA — nada a conferir
+    Totp,
+    BackupCode,
+}
+
+// ─── Buscar TOTP do usuário ──────────────────────────────────
+pub async fn find_totp(pool: &SqlitePool, user_id: &str) -> Result<Option<UserTotp>, AppError> {
+    let totp = sqlx::query_as::<_, UserTotp>("SELECT * FROM user_totp WHERE user_id = ?")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(totp)
+}
+
+// ─── Conferir código TOTP ────────────────────────────────────
+// O passo aceito é gravado com UPDATE guardado: o mesmo código
+// (ou um mais antigo) não passa duas vezes, nem em paralelo.
+pub async fn check_totp_code(pool: &SqlitePool, totp: &UserTotp, code: &str) -> Result<bool, AppError> {
+    let Some(step) = verify_totp(&totp.secret, code, Utc::now().timestamp()) else {
+        return Ok(false);
+    };
+
+    let accepted = sqlx::query(
+        "UPDATE user_totp SET last_used_step = ? WHERE user_id = ? AND last_used_step < ?",
+    )
+    .bind(step)
+    .bind(&totp.user_id)
+    .bind(step)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(accepted > 0)
+}
+
+// ─── Trocar códigos de recuperação ───────────────────────────
+// Os anteriores deixam de valer; só o hash fica no banco.
+pub async fn replace_backup_codes(pool: &SqlitePool, user_id: &str, codes: &[String]) -> Result<(), AppError> {
+    sqlx::query("DELETE FROM totp_backup_codes WHERE user_id = ?")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    let now = Utc::now().to_rfc3339();
+    for code in codes {
+        sqlx::query(
+            "INSERT INTO totp_backup_codes (id, user_id, code_hash, used_at, created_at)
+             VALUES (?, ?, ?, NULL, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(sha256_hex(&normalize_backup_code(code)))
+        .bind(&now)
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+// ─── Consumir código de recuperação ──────────────────────────
+async fn use_backup_code(pool: &SqlitePool, user_id: &str, code: &str) -> Result<bool, AppError> {
+    let used = sqlx::query(
+        "UPDATE totp_backup_codes SET used_at = ?
+         WHERE id = (SELECT id FROM totp_backup_codes
+                     WHERE user_id = ? AND code_hash = ? AND used_at IS NULL LIMIT 1)",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(user_id)
+    .bind(sha256_hex(&normalize_backup_code(code)))
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(used > 0)
+}
+
+pub async fn remaining_backup_codes(pool: &SqlitePool, user_id: &str) -> Result<i64, AppError> {
+    let remaining = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM totp_backup_codes WHERE user_id = ? AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(remaining)
+}
+
+// ─── Segundo fator ───────────────────────────────────────────
+// Sem 2FA ativo passa direto. Com 2FA, exige um código TOTP atual
+// ou, se `allow_backup`, um código de recuperação ainda não usado.
+// Códigos errados contam para o bloqueio do usuário (429 enquanto
+// durar); o código ausente não conta — é só o pedido do segundo fator.
+pub async fn check_second_factor(
+    pool:         &SqlitePool,
+    user_id:      &str,
+    code:         Option<&str>,
+    allow_backup: bool,
+) -> Result<SecondFactor, AppError> {
+    let Some(totp) = find_totp(pool, user_id).await?.filter(UserTotp::is_enabled) else {
+        return Ok(SecondFactor::NotEnabled);
+    };
+
+    let code = code.map(str::trim).filter(|c| !c.is_empty()).ok_or(AppError::TwoFactorRequired)?;
+
+    check_totp_lockout(pool, user_id).await?;
+
+    let factor = if is_totp_format(code) {
+        check_totp_code(pool, &totp, code).await?.then_some(SecondFactor::Totp)
+    } else if allow_backup {
+        use_backup_code(pool, user_id, code).await?.then_some(SecondFactor::BackupCode)
+    } else {
+        None
+    };
+
+    match factor {
+        Some(factor) => {
+            clear_totp_failures(pool, user_id).await?;
+            Ok(factor)
+        }
+        None => {
+            record_totp_failure(pool, user_id).await?;
+            Err(AppError::TwoFactorRequired)
+        }
+    }
+}
+
+// ─── Exigir TOTP recente (ações sensíveis) ───────────────────
+// Transferências e resolução de contratos pedem um código do app
+// no cabeçalho `X-TOTP-Code`, mesmo com sessão válida. Códigos de
+// recuperação não servem aqui — só para entrar na conta.
+pub async fn require_fresh_totp(pool: &SqlitePool, user_id: &str, code: Option<&str>) -> Result<(), AppError> {
+    check_second_factor(pool, user_id, code, false).await.map(|_| ())
+}
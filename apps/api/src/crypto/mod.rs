@@ -1,3 +1,4 @@
 pub mod bip39;
 pub mod keys;
-pub mod signing;
\ No newline at end of file
+pub mod signing;
+pub mod totp;
@@ -0,0 +1,148 @@
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha1::Sha1;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Passo de 30 s e 6 dígitos — padrão dos apps autenticadores
+pub const TOTP_STEP_SECS: i64 = 30;
+const TOTP_DIGITS: u32 = 6;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// ─── Segredo aleatório (160 bits, como no RFC 4226) ──────────
+pub fn generate_secret() -> String {
+    base32_encode(&rand::thread_rng().gen::<[u8; 20]>())
+}
+
+// ─── Base32 (RFC 4648, sem padding) ──────────────────────────
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            out.push(BASE32_ALPHABET[((buffer >> (bits - 5)) & 31) as usize] as char);
+            bits -= 5;
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+
+    out
+}
+
+pub fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in text.chars().filter(|c| *c != '=' && !c.is_whitespace()) {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            out.push(((buffer >> (bits - 8)) & 0xff) as u8);
+            bits -= 8;
+        }
+    }
+
+    Some(out)
+}
+
+// ─── HOTP (RFC 4226) para um contador ────────────────────────
+pub fn hotp(secret: &[u8], counter: u64) -> u32 {
+    let mut mac = HmacSha1::new_from_slice(secret).expect("HMAC aceita qualquer tamanho de chave");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Truncamento dinâmico
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([hash[offset], hash[offset + 1], hash[offset + 2], hash[offset + 3]])
+        & 0x7fff_ffff;
+
+    binary % 10u32.pow(TOTP_DIGITS)
+}
+
+// ─── Verificar TOTP (RFC 6238) ───────────────────────────────
+// Aceita um passo de tolerância para cada lado (relógio do celular).
+// Devolve o passo que conferiu, para o chamador recusar reuso.
+pub fn verify_totp(secret_b32: &str, code: &str, unix_time: i64) -> Option<i64> {
+    let secret = base32_decode(secret_b32)?;
+    let code = code.trim();
+    if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let current = unix_time / TOTP_STEP_SECS;
+
+    (current - 1..=current + 1)
+        .filter(|step| *step >= 0)
+        .find(|step| hotp(&secret, *step as u64) == code)
+}
+
+// ─── URI para QR code (Google Authenticator, Authy...) ───────
+pub fn otpauth_uri(issuer: &str, account: &str, secret_b32: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+        issuer  = uri_encode(issuer),
+        account = uri_encode(account),
+        secret  = secret_b32,
+        digits  = TOTP_DIGITS,
+        period  = TOTP_STEP_SECS,
+    )
+}
+
+fn uri_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vetores do RFC 6238 (SHA-1, segredo "12345678901234567890"), 6 dígitos
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        assert_eq!(hotp(RFC_SECRET, 59 / 30), 287082);
+        assert_eq!(hotp(RFC_SECRET, 1111111109 / 30), 81804);
+        assert_eq!(hotp(RFC_SECRET, 1234567890 / 30), 5924);
+        assert_eq!(hotp(RFC_SECRET, 2000000000 / 30), 279037);
+    }
+
+    #[test]
+    fn test_base32_roundtrip() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_decode("MZXW6YTBOI").unwrap(), b"foobar");
+        assert_eq!(base32_decode(&base32_encode(RFC_SECRET)).unwrap(), RFC_SECRET);
+        assert!(base32_decode("1!").is_none());
+    }
+
+    #[test]
+    fn test_verify_with_skew() {
+        let secret = base32_encode(RFC_SECRET);
+        assert_eq!(verify_totp(&secret, "287082", 59), Some(1));
+        assert_eq!(verify_totp(&secret, "287082", 59 + 30), Some(1));   // um passo atrasado
+        assert_eq!(verify_totp(&secret, "287082", 59 + 90), None);
+        assert_eq!(verify_totp(&secret, "28708", 59), None);
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let uri = otpauth_uri("PaperMarket", "ana silva", "ABC");
+        assert!(uri.starts_with("otpauth://totp/PaperMarket:ana%20silva?secret=ABC"));
+    }
+}
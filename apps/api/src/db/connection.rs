@@ -1,5 +1,7 @@
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{ConnectOptions, Connection, SqlitePool};
 use std::env;
+use std::str::FromStr;
 use tracing::info;
 
 pub async fn init_db() -> Result<SqlitePool, sqlx::Error> {
@@ -8,19 +10,38 @@ pub async fn init_db() -> Result<SqlitePool, sqlx::Error> {
 
     info!("Conectando ao banco de dados: {}", database_url);
 
+    // ─── Rodar migrations automaticamente ───────────────────
+    // Conexão própria com foreign_keys desligado: recriar tabela (o
+    // jeito do SQLite de mudar constraints) não pode disparar CASCADE
+    // nos filhos. No fim, nenhuma referência pode ter ficado órfã.
+    info!("Rodando migrations...");
+    let mut conn = SqliteConnectOptions::from_str(&database_url)?
+        .foreign_keys(false)
+        .connect()
+        .await?;
+
+    sqlx::migrate!("src/db/migrations")
+        .run(&mut conn)
+        .await?;
+
+    let violations = sqlx::query("PRAGMA foreign_key_check")
+        .fetch_all(&mut conn)
+        .await?;
+    if !violations.is_empty() {
+        return Err(sqlx::Error::Protocol(format!(
+            "Migrations deixaram {} referência(s) quebrada(s) (PRAGMA foreign_key_check)",
+            violations.len()
+        )));
+    }
+    conn.close().await?;
+
     // ─── Criar pool de conexões ──────────────────────────────
     let pool = SqlitePoolOptions::new()
         .max_connections(10)
         .connect(&database_url)
         .await?;
 
-    // ─── Rodar migrations automaticamente ───────────────────
-    info!("Rodando migrations...");
-    sqlx::migrate!("src/db/migrations")
-        .run(&pool)
-        .await?;
-
     info!("✅ Banco de dados pronto");
 
     Ok(pool)
-}
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+-- ============================================================
+-- MIGRATION 006 — Ordens de compra
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS orders (
+    id                    TEXT PRIMARY KEY,   -- UUID v4
+    product_id            TEXT NOT NULL,      -- FK → products.id
+    buyer_id              TEXT NOT NULL,      -- FK → users.id
+    seller_id             TEXT NOT NULL,      -- FK → users.id
+    contract_id           TEXT NOT NULL UNIQUE, -- FK → contracts.id (escrow da ordem)
+    quantity              INTEGER NOT NULL CHECK (quantity > 0),
+    unit_price_sats       INTEGER NOT NULL,   -- preço do produto no momento da compra
+    total_sats            INTEGER NOT NULL,   -- quantity × unit_price_sats
+    shipping_address_hash TEXT NOT NULL,      -- SHA-256 do endereço de entrega (hex)
+    status                TEXT NOT NULL DEFAULT 'PENDING'
+                          CHECK (status IN ('PENDING', 'FUNDED', 'SHIPPED', 'DELIVERED',
+                                            'COMPLETED', 'CANCELLED', 'REFUNDED')),
+    created_at            TEXT NOT NULL,      -- ISO 8601
+    updated_at            TEXT NOT NULL,      -- ISO 8601
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE RESTRICT,
+    FOREIGN KEY (buyer_id)    REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (seller_id)   REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE RESTRICT
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_orders_buyer_id    ON orders(buyer_id);
+CREATE INDEX IF NOT EXISTS idx_orders_seller_id   ON orders(seller_id);
+CREATE INDEX IF NOT EXISTS idx_orders_product_id  ON orders(product_id);
+CREATE INDEX IF NOT EXISTS idx_orders_status      ON orders(status);
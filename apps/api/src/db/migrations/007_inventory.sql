@@ -0,0 +1,30 @@
+-- ============================================================
+-- MIGRATION 007 — Estoque e reservas
+-- ============================================================
+
+-- Estoque do produto. `reserved` conta unidades presas em escrows
+-- PENDING/LOCKED; `version` é incrementado a cada alteração de estoque
+-- (controle de concorrência otimista).
+ALTER TABLE products ADD COLUMN stock    INTEGER NOT NULL DEFAULT 1 CHECK (stock >= 0);
+ALTER TABLE products ADD COLUMN reserved INTEGER NOT NULL DEFAULT 0 CHECK (reserved >= 0);
+ALTER TABLE products ADD COLUMN version  INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS reservations (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    product_id  TEXT NOT NULL,              -- FK → products.id
+    order_id    TEXT NOT NULL UNIQUE,       -- FK → orders.id
+    contract_id TEXT NOT NULL UNIQUE,       -- FK → contracts.id
+    quantity    INTEGER NOT NULL CHECK (quantity > 0),
+    status      TEXT NOT NULL DEFAULT 'HELD'
+                CHECK (status IN ('HELD', 'CONSUMED', 'RELEASED')),
+    created_at  TEXT NOT NULL,              -- ISO 8601
+    updated_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE CASCADE,
+    FOREIGN KEY (order_id)    REFERENCES orders(id)    ON DELETE CASCADE,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE CASCADE
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_reservations_product_id ON reservations(product_id);
+CREATE INDEX IF NOT EXISTS idx_reservations_status     ON reservations(status);
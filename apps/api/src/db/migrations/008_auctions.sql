@@ -0,0 +1,49 @@
+-- ============================================================
+-- MIGRATION 008 — Leilões
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS auctions (
+    id                   TEXT PRIMARY KEY,  -- UUID v4
+    product_id           TEXT NOT NULL,     -- FK → products.id
+    seller_id            TEXT NOT NULL,     -- FK → users.id
+    kind                 TEXT NOT NULL CHECK (kind IN ('ENGLISH', 'DUTCH')),
+    start_price_sats     INTEGER NOT NULL,  -- preço inicial
+    reserve_price_sats   INTEGER NOT NULL DEFAULT 0, -- mínimo aceito / piso do holandês
+    min_increment_sats   INTEGER NOT NULL DEFAULT 1, -- inglês: incremento mínimo
+    decay_per_block_sats INTEGER NOT NULL DEFAULT 0, -- holandês: queda por bloco
+    start_block          INTEGER NOT NULL,  -- altura de abertura
+    end_block            INTEGER NOT NULL,  -- altura de encerramento (pode ser estendida)
+    extension_blocks     INTEGER NOT NULL DEFAULT 0, -- anti-sniping
+    highest_bid_sats     INTEGER,           -- maior lance (NULL se nenhum)
+    status               TEXT NOT NULL DEFAULT 'OPEN'
+                         CHECK (status IN ('OPEN', 'SETTLED', 'UNSOLD', 'CANCELLED')),
+    winner_id            TEXT,              -- FK → users.id
+    contract_id          TEXT,              -- FK → contracts.id (escrow do vencedor)
+    created_at           TEXT NOT NULL,     -- ISO 8601
+    updated_at           TEXT NOT NULL,     -- ISO 8601
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE CASCADE,
+    FOREIGN KEY (seller_id)   REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (winner_id)   REFERENCES users(id)     ON DELETE SET NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE SET NULL
+);
+
+CREATE TABLE IF NOT EXISTS bids (
+    id              TEXT PRIMARY KEY,       -- UUID v4
+    auction_id      TEXT NOT NULL,          -- FK → auctions.id
+    bidder_id       TEXT NOT NULL,          -- FK → users.id
+    bidder_pubkey   TEXT NOT NULL,          -- chave pública que assinou o lance
+    amount_sats     INTEGER NOT NULL,       -- valor do lance
+    signature       TEXT NOT NULL,          -- assinatura secp256k1 (hex)
+    block_height    INTEGER NOT NULL,       -- altura da chain no lance
+    created_at      TEXT NOT NULL,          -- ISO 8601
+
+    FOREIGN KEY (auction_id) REFERENCES auctions(id) ON DELETE CASCADE,
+    FOREIGN KEY (bidder_id)  REFERENCES users(id)    ON DELETE CASCADE
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_auctions_product_id ON auctions(product_id);
+CREATE INDEX IF NOT EXISTS idx_auctions_status     ON auctions(status);
+CREATE INDEX IF NOT EXISTS idx_auctions_end_block  ON auctions(end_block);
+CREATE INDEX IF NOT EXISTS idx_bids_auction_id     ON bids(auction_id);
@@ -0,0 +1,33 @@
+-- ============================================================
+-- MIGRATION 009 — Ofertas e contrapropostas
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS offers (
+    id               TEXT PRIMARY KEY,      -- UUID v4
+    product_id       TEXT NOT NULL,         -- FK → products.id
+    buyer_id         TEXT NOT NULL,         -- FK → users.id
+    seller_id        TEXT NOT NULL,         -- FK → users.id
+    parent_id        TEXT,                  -- oferta respondida por esta contraproposta
+    proposer         TEXT NOT NULL CHECK (proposer IN ('BUYER', 'SELLER')),
+    amount_sats      INTEGER NOT NULL CHECK (amount_sats > 0),
+    signature        TEXT NOT NULL,         -- assinatura secp256k1 de quem propôs (hex)
+    status           TEXT NOT NULL DEFAULT 'PENDING'
+                     CHECK (status IN ('PENDING', 'ACCEPTED', 'REJECTED', 'COUNTERED', 'EXPIRED')),
+    expires_at_block INTEGER NOT NULL,      -- altura em que a oferta caduca
+    contract_id      TEXT,                  -- FK → contracts.id (escrow da oferta aceita)
+    created_at       TEXT NOT NULL,         -- ISO 8601
+    updated_at       TEXT NOT NULL,         -- ISO 8601
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE CASCADE,
+    FOREIGN KEY (buyer_id)    REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (seller_id)   REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (parent_id)   REFERENCES offers(id)    ON DELETE SET NULL,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE SET NULL
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_offers_product_id ON offers(product_id);
+CREATE INDEX IF NOT EXISTS idx_offers_buyer_id   ON offers(buyer_id);
+CREATE INDEX IF NOT EXISTS idx_offers_seller_id  ON offers(seller_id);
+CREATE INDEX IF NOT EXISTS idx_offers_status     ON offers(status);
+CREATE INDEX IF NOT EXISTS idx_offers_parent_id  ON offers(parent_id);
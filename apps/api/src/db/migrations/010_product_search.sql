@@ -0,0 +1,36 @@
+-- ============================================================
+-- MIGRATION 010 — Busca textual de produtos (FTS5)
+-- ============================================================
+
+-- Índice invertido de título e descrição. `remove_diacritics 2`
+-- faz "cafe" encontrar "café" e "acao" encontrar "ação".
+CREATE VIRTUAL TABLE IF NOT EXISTS products_fts USING fts5(
+    product_id UNINDEXED,                   -- FK → products.id
+    title,
+    description,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Sincronização com products
+CREATE TRIGGER IF NOT EXISTS products_fts_insert AFTER INSERT ON products BEGIN
+    INSERT INTO products_fts (product_id, title, description)
+    VALUES (new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS products_fts_delete AFTER DELETE ON products BEGIN
+    DELETE FROM products_fts WHERE product_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS products_fts_update
+AFTER UPDATE OF title, description ON products BEGIN
+    UPDATE products_fts SET title = new.title, description = new.description
+    WHERE product_id = new.id;
+END;
+
+-- Indexar produtos já cadastrados
+INSERT INTO products_fts (product_id, title, description)
+SELECT id, title, description FROM products;
+
+-- Índices de ordenação
+CREATE INDEX IF NOT EXISTS idx_products_price_sats ON products(price_sats);
+CREATE INDEX IF NOT EXISTS idx_products_created_at ON products(created_at);
@@ -0,0 +1,8 @@
+-- ============================================================
+-- MIGRATION 011 — Paginação por cursor da listagem de produtos
+-- ============================================================
+
+-- Cobre `WHERE status = 'active' ORDER BY created_at DESC, id DESC`
+-- e o salto do cursor `(created_at, id) < (?, ?)`.
+CREATE INDEX IF NOT EXISTS idx_products_status_created_id
+    ON products(status, created_at DESC, id DESC);
@@ -0,0 +1,14 @@
+-- ============================================================
+-- MIGRATION 012 — Avaliações de compras verificadas
+-- ============================================================
+
+-- Cada avaliação nova nasce de um escrow RELEASED do avaliador.
+-- Avaliações antigas ficam com contract_id NULL e verified = 0.
+ALTER TABLE reviews ADD COLUMN contract_id  TEXT REFERENCES contracts(id) ON DELETE SET NULL;
+ALTER TABLE reviews ADD COLUMN verified     INTEGER NOT NULL DEFAULT 0;  -- compra confirmada
+ALTER TABLE reviews ADD COLUMN seller_reply TEXT;                        -- resposta do vendedor
+ALTER TABLE reviews ADD COLUMN replied_at   TEXT;                        -- ISO 8601
+
+-- Uma avaliação por contrato
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reviews_contract_id
+    ON reviews(contract_id) WHERE contract_id IS NOT NULL;
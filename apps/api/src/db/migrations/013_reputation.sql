@@ -0,0 +1,41 @@
+-- ============================================================
+-- MIGRATION 013 — Reputação on-chain
+-- ============================================================
+
+-- Merkle root da reputação ancorada no header do bloco
+ALTER TABLE blocks ADD COLUMN reputation_root TEXT;
+
+-- Fotografia periódica das reputações (a cada REPUTATION_INTERVAL blocos)
+CREATE TABLE IF NOT EXISTS reputation_snapshots (
+    id              TEXT PRIMARY KEY,       -- UUID v4
+    height          INTEGER NOT NULL UNIQUE,-- altura em que foi calculada
+    root            TEXT NOT NULL,          -- merkle root das folhas
+    leaf_count      INTEGER NOT NULL,
+    block_hash      TEXT,                   -- bloco que ancorou a root (NULL = pendente)
+    block_height    INTEGER,
+    created_at      TEXT NOT NULL,          -- ISO 8601
+
+    FOREIGN KEY (block_hash) REFERENCES blocks(id) ON DELETE SET NULL
+);
+
+-- Uma folha por vendedor, na ordem do endereço
+CREATE TABLE IF NOT EXISTS reputation_leaves (
+    snapshot_id      TEXT NOT NULL,         -- FK → reputation_snapshots.id
+    position         INTEGER NOT NULL,      -- índice da folha na árvore
+    address          TEXT NOT NULL,         -- endereço BPC do vendedor
+    score            INTEGER NOT NULL,      -- 0 a 1000
+    completed        INTEGER NOT NULL,      -- escrows liberados sem disputa
+    refunds          INTEGER NOT NULL,      -- escrows pagos e reembolsados sem disputa
+    disputes_won     INTEGER NOT NULL,
+    disputes_lost    INTEGER NOT NULL,
+    verified_reviews INTEGER NOT NULL,
+    avg_rating       REAL,                  -- média das avaliações verificadas
+    leaf_hash        TEXT NOT NULL,
+
+    PRIMARY KEY (snapshot_id, position),
+    FOREIGN KEY (snapshot_id) REFERENCES reputation_snapshots(id) ON DELETE CASCADE
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_reputation_leaves_address ON reputation_leaves(address);
+CREATE INDEX IF NOT EXISTS idx_reputation_snapshots_block ON reputation_snapshots(block_height);
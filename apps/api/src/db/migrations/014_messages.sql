@@ -0,0 +1,50 @@
+-- ============================================================
+-- MIGRATION 014 — Mensagens cifradas entre comprador e vendedor
+-- ============================================================
+
+-- Conversa ligada a um produto (pré-venda) ou a um contrato (pós-venda)
+CREATE TABLE IF NOT EXISTS message_threads (
+    id           TEXT PRIMARY KEY,          -- UUID v4
+    product_id   TEXT NOT NULL,             -- FK → products.id
+    contract_id  TEXT,                      -- FK → contracts.id (NULL = conversa do anúncio)
+    buyer_id     TEXT NOT NULL,             -- FK → users.id
+    seller_id    TEXT NOT NULL,             -- FK → users.id
+    created_at   TEXT NOT NULL,             -- ISO 8601
+    updated_at   TEXT NOT NULL,             -- ISO 8601 (última mensagem)
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE CASCADE,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE CASCADE,
+    FOREIGN KEY (buyer_id)    REFERENCES users(id)     ON DELETE CASCADE,
+    FOREIGN KEY (seller_id)   REFERENCES users(id)     ON DELETE CASCADE
+);
+
+-- Só o texto cifrado é guardado: a chave vem de ECDH entre as
+-- pubkeys secp256k1 das carteiras e nunca passa pelo servidor.
+CREATE TABLE IF NOT EXISTS messages (
+    id            TEXT PRIMARY KEY,         -- UUID v4
+    thread_id     TEXT NOT NULL,            -- FK → message_threads.id
+    sender_id     TEXT NOT NULL,            -- FK → users.id
+    recipient_id  TEXT NOT NULL,            -- FK → users.id
+    sender_pubkey TEXT NOT NULL,            -- pubkey usada no ECDH pelo remetente
+    ciphertext    TEXT NOT NULL,            -- AEAD (hex)
+    nonce         TEXT NOT NULL,            -- 12 bytes (hex)
+    read_at       TEXT,                     -- ISO 8601 (NULL = não lida)
+    created_at    TEXT NOT NULL,            -- ISO 8601
+
+    FOREIGN KEY (thread_id)    REFERENCES message_threads(id) ON DELETE CASCADE,
+    FOREIGN KEY (sender_id)    REFERENCES users(id)           ON DELETE CASCADE,
+    FOREIGN KEY (recipient_id) REFERENCES users(id)           ON DELETE CASCADE
+);
+
+-- Uma conversa por anúncio e comprador, uma por contrato
+CREATE UNIQUE INDEX IF NOT EXISTS idx_threads_product_buyer
+    ON message_threads(product_id, buyer_id) WHERE contract_id IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_threads_contract
+    ON message_threads(contract_id) WHERE contract_id IS NOT NULL;
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_threads_buyer_id       ON message_threads(buyer_id);
+CREATE INDEX IF NOT EXISTS idx_threads_seller_id      ON message_threads(seller_id);
+CREATE INDEX IF NOT EXISTS idx_messages_thread_id     ON messages(thread_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_messages_recipient_unread
+    ON messages(recipient_id) WHERE read_at IS NULL;
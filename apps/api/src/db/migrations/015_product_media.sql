@@ -0,0 +1,29 @@
+-- ============================================================
+-- MIGRATION 015 — Imagens e anexos dos produtos
+-- ============================================================
+
+-- Os bytes ficam no armazenamento de mídia (MEDIA_DIR), endereçados
+-- pelo SHA-256 do conteúdo; aqui só os metadados. Os hashes das
+-- imagens entram no item_hash do contrato de escrow.
+CREATE TABLE IF NOT EXISTS product_media (
+    id            TEXT PRIMARY KEY,         -- UUID v4
+    product_id    TEXT NOT NULL,            -- FK → products.id
+    kind          TEXT NOT NULL,            -- IMAGE | ATTACHMENT
+    sha256        TEXT NOT NULL,            -- chave do conteúdo no armazenamento
+    mime_type     TEXT NOT NULL,            -- detectado pelos bytes, não pelo cliente
+    size_bytes    INTEGER NOT NULL,
+    filename      TEXT,                     -- nome original (informativo)
+    width         INTEGER,                  -- só imagens
+    height        INTEGER,                  -- só imagens
+    thumb_sha256  TEXT,                     -- miniatura PNG (só imagens)
+    position      INTEGER NOT NULL,         -- ordem de exibição (0 = capa)
+    created_at    TEXT NOT NULL,            -- ISO 8601
+
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    UNIQUE (product_id, sha256)
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_product_media_product ON product_media(product_id, kind, position);
+CREATE INDEX IF NOT EXISTS idx_product_media_sha256  ON product_media(sha256);
+CREATE INDEX IF NOT EXISTS idx_product_media_thumb   ON product_media(thumb_sha256);
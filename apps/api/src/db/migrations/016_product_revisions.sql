@@ -0,0 +1,30 @@
+-- ============================================================
+-- MIGRATION 016 — Revisões imutáveis dos produtos
+-- ============================================================
+
+-- Cada edição do anúncio (texto, preço, imagens) gera uma revisão
+-- nova; as anteriores nunca mudam. O escrow guarda a revisão em que
+-- foi aberto, e o item_hash dela é o item_hash do contrato.
+CREATE TABLE IF NOT EXISTS product_revisions (
+    id            TEXT PRIMARY KEY,         -- UUID v4
+    product_id    TEXT NOT NULL,            -- FK → products.id
+    revision      INTEGER NOT NULL,         -- 1, 2, 3... por produto
+    title         TEXT NOT NULL,
+    description   TEXT NOT NULL,
+    price_sats    INTEGER NOT NULL,
+    category      TEXT NOT NULL,
+    condition     TEXT NOT NULL,
+    location      TEXT NOT NULL,
+    image_hashes  TEXT NOT NULL DEFAULT '', -- SHA-256 das imagens, em ordem, separados por vírgula
+    item_hash     TEXT NOT NULL,            -- sha256(título + descrição [+ imagens])
+    created_at    TEXT NOT NULL,            -- ISO 8601
+
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    UNIQUE (product_id, revision)
+);
+
+-- Contratos antigos ficam com revision_id NULL (resolvido pelo item_hash)
+ALTER TABLE contracts ADD COLUMN revision_id TEXT REFERENCES product_revisions(id);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_revisions_item_hash ON product_revisions(product_id, item_hash);
@@ -0,0 +1,49 @@
+-- ============================================================
+-- MIGRATION 017 — Taxonomia de categorias e localização estruturada
+-- ============================================================
+
+-- Árvore de categorias gerenciada pelos administradores
+CREATE TABLE IF NOT EXISTS categories (
+    id          TEXT PRIMARY KEY,           -- UUID v4 (seeds: cat-<slug>)
+    parent_id   TEXT,                       -- FK → categories.id (NULL = raiz)
+    slug        TEXT NOT NULL UNIQUE,       -- usado em ?category= e em products.category
+    name        TEXT NOT NULL,
+    position    INTEGER NOT NULL DEFAULT 0, -- ordem entre irmãs
+    active      INTEGER NOT NULL DEFAULT 1, -- inativa = não aceita novos produtos
+    created_at  TEXT NOT NULL,              -- ISO 8601
+    updated_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (parent_id) REFERENCES categories(id) ON DELETE RESTRICT
+);
+
+INSERT OR IGNORE INTO categories (id, parent_id, slug, name, position, active, created_at, updated_at) VALUES
+    ('cat-tech',   NULL, 'tech',   'Tecnologia', 0, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('cat-livros', NULL, 'livros', 'Livros',     1, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('cat-foto',   NULL, 'foto',   'Fotografia', 2, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('cat-casa',   NULL, 'casa',   'Casa',       3, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('cat-moda',   NULL, 'moda',   'Moda',       4, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    ('cat-outros', NULL, 'outros', 'Outros',     5, 1, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+-- Produto aponta para a categoria; `category` passa a guardar o slug
+ALTER TABLE products ADD COLUMN category_id TEXT REFERENCES categories(id);
+ALTER TABLE products ADD COLUMN state       TEXT;   -- UF (SP, RJ...)
+ALTER TABLE products ADD COLUMN city        TEXT;
+
+-- Produtos existentes: casar texto livre com slug ou nome da categoria
+UPDATE products SET category_id = (
+    SELECT c.id FROM categories c
+    WHERE c.slug = lower(products.category) OR lower(c.name) = lower(products.category)
+);
+UPDATE products SET category = (SELECT slug FROM categories WHERE id = products.category_id)
+WHERE category_id IS NOT NULL;
+
+-- "Cidade/UF" → campos separados (demais formatos ficam NULL)
+UPDATE products
+SET city  = trim(substr(location, 1, length(location) - 3)),
+    state = upper(substr(location, -2))
+WHERE location LIKE '%_/__';
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_categories_parent_id ON categories(parent_id, position);
+CREATE INDEX IF NOT EXISTS idx_products_category_id ON products(category_id);
+CREATE INDEX IF NOT EXISTS idx_products_state_city  ON products(state, city);
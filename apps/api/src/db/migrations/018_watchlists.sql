@@ -0,0 +1,62 @@
+-- ============================================================
+-- MIGRATION 018 — Favoritos, buscas salvas e notificações
+-- ============================================================
+
+-- Produtos acompanhados pelo usuário
+CREATE TABLE IF NOT EXISTS watchlist (
+    user_id          TEXT NOT NULL,         -- FK → users.id
+    product_id       TEXT NOT NULL,         -- FK → products.id
+    last_price_sats  INTEGER NOT NULL,      -- último preço avisado (base da queda)
+    created_at       TEXT NOT NULL,         -- ISO 8601
+
+    PRIMARY KEY (user_id, product_id),
+    FOREIGN KEY (user_id)    REFERENCES users(id)    ON DELETE CASCADE,
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+-- Filtros de listagem salvos; cada produto novo que casa gera alerta
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    user_id     TEXT NOT NULL,              -- FK → users.id
+    name        TEXT NOT NULL,
+    query       TEXT,                       -- texto livre, como digitado
+    fts_query   TEXT,                       -- expressão MATCH derivada de `query`
+    category    TEXT,                       -- slug (inclui subcategorias)
+    condition   TEXT,
+    state       TEXT,                       -- UF
+    min_price   INTEGER,
+    max_price   INTEGER,
+    created_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Caixa de notificações (também enviadas pelo WebSocket)
+CREATE TABLE IF NOT EXISTS notifications (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    user_id     TEXT NOT NULL,              -- FK → users.id
+    kind        TEXT NOT NULL,              -- NEW_MATCH | PRICE_DROP | AUCTION_ENDING
+    title       TEXT NOT NULL,
+    body        TEXT NOT NULL,
+    product_id  TEXT,                       -- FK → products.id
+    auction_id  TEXT,                       -- FK → auctions.id
+    search_id   TEXT,                       -- FK → saved_searches.id
+    read_at     TEXT,                       -- ISO 8601 (NULL = não lida)
+    created_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (user_id)    REFERENCES users(id)          ON DELETE CASCADE,
+    FOREIGN KEY (product_id) REFERENCES products(id)       ON DELETE CASCADE,
+    FOREIGN KEY (auction_id) REFERENCES auctions(id)       ON DELETE CASCADE,
+    FOREIGN KEY (search_id)  REFERENCES saved_searches(id) ON DELETE SET NULL
+);
+
+-- Um aviso de "leilão acabando" por usuário e leilão
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notifications_auction_ending
+    ON notifications(user_id, auction_id) WHERE kind = 'AUCTION_ENDING';
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_watchlist_product_id       ON watchlist(product_id);
+CREATE INDEX IF NOT EXISTS idx_saved_searches_user_id     ON saved_searches(user_id);
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created ON notifications(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread
+    ON notifications(user_id) WHERE read_at IS NULL;
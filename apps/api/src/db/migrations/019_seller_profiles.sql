@@ -0,0 +1,13 @@
+-- ============================================================
+-- MIGRATION 019 — Perfil público do vendedor (vitrine)
+-- ============================================================
+
+-- Campos editáveis pelo próprio usuário; métricas da vitrine são
+-- calculadas a partir de products, reviews e contracts.
+ALTER TABLE users ADD COLUMN bio                TEXT;   -- apresentação (texto livre)
+ALTER TABLE users ADD COLUMN avatar_sha256      TEXT;   -- imagem já enviada em um produto do vendedor
+ALTER TABLE users ADD COLUMN policies           TEXT;   -- envio, trocas e devoluções
+ALTER TABLE users ADD COLUMN profile_updated_at TEXT;   -- ISO 8601
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_products_seller_status ON products(seller_id, status, created_at);
@@ -0,0 +1,58 @@
+-- ============================================================
+-- MIGRATION 020 — Moderação: denúncias, ações e termos proibidos
+-- ============================================================
+
+-- Denúncias de usuários sobre anúncios e avaliações
+CREATE TABLE IF NOT EXISTS reports (
+    id           TEXT PRIMARY KEY,          -- UUID v4
+    reporter_id  TEXT NOT NULL,             -- FK → users.id
+    target_type  TEXT NOT NULL CHECK (target_type IN ('PRODUCT', 'REVIEW')),
+    target_id    TEXT NOT NULL,             -- products.id | reviews.id
+    reason       TEXT NOT NULL,             -- SPAM | FRAUD | PROHIBITED | OFFENSIVE | OTHER
+    details      TEXT,
+    status       TEXT NOT NULL DEFAULT 'OPEN'
+                 CHECK (status IN ('OPEN', 'RESOLVED', 'DISMISSED')),
+    action_id    TEXT,                      -- FK → moderation_actions.id (ação que resolveu)
+    created_at   TEXT NOT NULL,             -- ISO 8601
+    updated_at   TEXT NOT NULL,             -- ISO 8601
+
+    FOREIGN KEY (reporter_id) REFERENCES users(id)              ON DELETE CASCADE,
+    FOREIGN KEY (action_id)   REFERENCES moderation_actions(id) ON DELETE SET NULL
+);
+
+-- Trilha de auditoria: toda ação de moderação, nunca apagada
+CREATE TABLE IF NOT EXISTS moderation_actions (
+    id                TEXT PRIMARY KEY,     -- UUID v4
+    moderator_address TEXT NOT NULL,        -- carteira de quem agiu
+    action            TEXT NOT NULL,        -- HIDE | REMOVE | WARN | SUSPEND | RESTORE
+                                            -- | BAN_TERM | UNBAN_TERM | DISMISS
+    target_type       TEXT NOT NULL,        -- PRODUCT | REVIEW | USER | TERM | REPORT
+    target_id         TEXT NOT NULL,
+    target_user_id    TEXT,                 -- dono do conteúdo (ou o próprio usuário)
+    reason            TEXT NOT NULL,
+    created_at        TEXT NOT NULL         -- ISO 8601
+);
+
+-- Termos que bloqueiam título/descrição na criação e edição
+CREATE TABLE IF NOT EXISTS banned_terms (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    term        TEXT NOT NULL UNIQUE,       -- minúsculas; palavra ou expressão
+    created_by  TEXT NOT NULL,              -- carteira do moderador
+    created_at  TEXT NOT NULL               -- ISO 8601
+);
+
+-- Estado de moderação no conteúdo
+ALTER TABLE products ADD COLUMN hidden_by       TEXT;   -- FK → moderation_actions.id (status 'hidden')
+ALTER TABLE reviews  ADD COLUMN hidden_at       TEXT;   -- ISO 8601 (NULL = visível)
+ALTER TABLE users    ADD COLUMN suspended_at    TEXT;   -- ISO 8601 (NULL = ativo)
+ALTER TABLE users    ADD COLUMN suspended_by    TEXT;   -- FK → moderation_actions.id
+
+-- Uma denúncia aberta por usuário e alvo
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reports_open_unique
+    ON reports(reporter_id, target_type, target_id) WHERE status = 'OPEN';
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_reports_status_target      ON reports(status, target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_moderation_actions_target  ON moderation_actions(target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_moderation_actions_user    ON moderation_actions(target_user_id);
+CREATE INDEX IF NOT EXISTS idx_products_hidden_by         ON products(hidden_by);
@@ -0,0 +1,37 @@
+-- ============================================================
+-- MIGRATION 021 — Envio, rastreio e liberação automática do escrow
+-- ============================================================
+
+-- Um envio por contrato de escrow
+CREATE TABLE IF NOT EXISTS shipments (
+    id                         TEXT PRIMARY KEY,        -- UUID v4
+    contract_id                TEXT NOT NULL UNIQUE,    -- FK → contracts.id
+    carrier                    TEXT NOT NULL,           -- transportadora (Correios, Jadlog...)
+    tracking_code              TEXT NOT NULL,
+    shipped_at_block           INTEGER NOT NULL,        -- altura em que o vendedor despachou
+    estimated_delivery_blocks  INTEGER NOT NULL,        -- prazo estimado informado pelo vendedor
+    auto_release_block         INTEGER NOT NULL,        -- envio + prazo + janela de disputa
+    delivered_at_block         INTEGER,                 -- confirmação do comprador (NULL = pendente)
+    created_at                 TEXT NOT NULL,           -- ISO 8601
+    updated_at                 TEXT NOT NULL,           -- ISO 8601
+
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE CASCADE
+);
+
+-- Linha do tempo do envio
+CREATE TABLE IF NOT EXISTS shipment_events (
+    id            TEXT PRIMARY KEY,         -- UUID v4
+    shipment_id   TEXT NOT NULL,            -- FK → shipments.id
+    event_type    TEXT NOT NULL,            -- SHIPPED | IN_TRANSIT | OUT_FOR_DELIVERY | DELIVERED
+                                            -- | EXCEPTION | DELIVERY_CONFIRMED
+    reported_by   TEXT NOT NULL,            -- buyer | seller
+    description   TEXT,
+    block_height  INTEGER NOT NULL,         -- altura da chain no registro
+    created_at    TEXT NOT NULL,            -- ISO 8601
+
+    FOREIGN KEY (shipment_id) REFERENCES shipments(id) ON DELETE CASCADE
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_shipments_auto_release     ON shipments(auto_release_block);
+CREATE INDEX IF NOT EXISTS idx_shipment_events_shipment   ON shipment_events(shipment_id, created_at);
@@ -0,0 +1,35 @@
+-- ============================================================
+-- MIGRATION 022 — Papéis de acesso (RBAC) e auditoria
+-- ============================================================
+
+-- Papéis atribuídos a cada usuário (um usuário pode ter vários)
+CREATE TABLE IF NOT EXISTS user_roles (
+    user_id     TEXT NOT NULL,              -- FK → users.id
+    role        TEXT NOT NULL CHECK (role IN ('user', 'seller', 'arbiter', 'moderator', 'admin')),
+    granted_by  TEXT,                       -- carteira do admin (NULL = cadastro/bootstrap)
+    granted_at  TEXT NOT NULL,              -- ISO 8601
+
+    PRIMARY KEY (user_id, role),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Trilha de auditoria: toda concessão e revogação, nunca apagada
+CREATE TABLE IF NOT EXISTS role_audit (
+    id              TEXT PRIMARY KEY,       -- UUID v4
+    user_id         TEXT NOT NULL,          -- usuário afetado
+    role            TEXT NOT NULL,
+    action          TEXT NOT NULL CHECK (action IN ('GRANT', 'REVOKE')),
+    actor_address   TEXT,                   -- carteira do admin (NULL = cadastro/bootstrap)
+    reason          TEXT NOT NULL,
+    created_at      TEXT NOT NULL           -- ISO 8601
+);
+
+-- Usuários existentes compram e vendem
+INSERT OR IGNORE INTO user_roles (user_id, role, granted_by, granted_at)
+    SELECT id, 'user', NULL, created_at FROM users;
+INSERT OR IGNORE INTO user_roles (user_id, role, granted_by, granted_at)
+    SELECT id, 'seller', NULL, created_at FROM users;
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_user_roles_role     ON user_roles(role);
+CREATE INDEX IF NOT EXISTS idx_role_audit_user     ON role_audit(user_id, created_at);
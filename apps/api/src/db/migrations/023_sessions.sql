@@ -0,0 +1,33 @@
+-- ============================================================
+-- MIGRATION 023 — Sessões, refresh tokens e revogação de JWT
+-- ============================================================
+
+-- Uma sessão por login/dispositivo; o refresh token gira a cada uso
+CREATE TABLE IF NOT EXISTS sessions (
+    id              TEXT PRIMARY KEY,       -- UUID v4 (claim `sid`)
+    user_id         TEXT NOT NULL,          -- FK → users.id
+    refresh_hash    TEXT NOT NULL UNIQUE,   -- SHA-256 do refresh token atual
+    previous_hash   TEXT,                   -- SHA-256 do anterior (detecta reuso)
+    access_jti      TEXT NOT NULL,          -- jti do access token mais recente
+    user_agent      TEXT,
+    ip_address      TEXT,
+    created_at      TEXT NOT NULL,          -- ISO 8601
+    last_used_at    TEXT NOT NULL,          -- ISO 8601 (último refresh)
+    expires_at      TEXT NOT NULL,          -- ISO 8601 (renovado a cada refresh)
+    revoked_at      TEXT,                   -- ISO 8601 (NULL = ativa)
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Access tokens revogados antes de expirar (logout, rotação)
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    jti         TEXT PRIMARY KEY,           -- claim `jti`
+    session_id  TEXT,                       -- FK → sessions.id
+    expires_at  TEXT NOT NULL,              -- ISO 8601 — depois disso o JWT já é inválido
+    revoked_at  TEXT NOT NULL               -- ISO 8601
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_sessions_user           ON sessions(user_id, revoked_at);
+CREATE INDEX IF NOT EXISTS idx_sessions_previous_hash  ON sessions(previous_hash);
+CREATE INDEX IF NOT EXISTS idx_revoked_tokens_expires  ON revoked_tokens(expires_at);
@@ -0,0 +1,17 @@
+-- ============================================================
+-- MIGRATION 024 — Login por desafio assinado (challenge–response)
+-- ============================================================
+
+-- Nonce de uso único que o cliente assina com a chave da carteira
+CREATE TABLE IF NOT EXISTS auth_challenges (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    address     TEXT NOT NULL,              -- carteira que pretende logar
+    nonce       TEXT NOT NULL UNIQUE,       -- 16 bytes aleatórios (hex)
+    message     TEXT NOT NULL,              -- texto exato a ser assinado
+    expires_at  TEXT NOT NULL,              -- ISO 8601
+    used_at     TEXT,                       -- ISO 8601 (NULL = disponível)
+    created_at  TEXT NOT NULL               -- ISO 8601
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_auth_challenges_expires ON auth_challenges(expires_at);
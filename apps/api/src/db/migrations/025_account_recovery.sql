@@ -0,0 +1,23 @@
+-- ============================================================
+-- MIGRATION 025 — Recuperação de conta e eventos de segurança
+-- ============================================================
+
+-- Finalidade do desafio: a assinatura de login não serve para recuperar
+ALTER TABLE auth_challenges ADD COLUMN purpose TEXT NOT NULL DEFAULT 'LOGIN'
+    CHECK (purpose IN ('LOGIN', 'RECOVER'));
+
+-- Trilha de eventos sensíveis da conta (recuperação, 2FA...), nunca apagada
+CREATE TABLE IF NOT EXISTS security_events (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    user_id     TEXT,                       -- FK → users.id (NULL = carteira desconhecida)
+    address     TEXT,                       -- carteira envolvida
+    event_type  TEXT NOT NULL,              -- RECOVERY_SUCCEEDED | RECOVERY_FAILED
+    ip_address  TEXT,
+    detail      TEXT,
+    created_at  TEXT NOT NULL               -- ISO 8601
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_security_events_user     ON security_events(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_security_events_address  ON security_events(address, event_type, created_at);
+CREATE INDEX IF NOT EXISTS idx_security_events_ip       ON security_events(ip_address, event_type, created_at);
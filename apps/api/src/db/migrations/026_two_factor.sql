@@ -0,0 +1,28 @@
+-- ============================================================
+-- MIGRATION 026 — Autenticação em duas etapas (TOTP)
+-- ============================================================
+
+-- Segredo TOTP por usuário; só vale depois de confirmado com um código
+CREATE TABLE IF NOT EXISTS user_totp (
+    user_id         TEXT PRIMARY KEY,       -- FK → users.id
+    secret          TEXT NOT NULL,          -- base32 (RFC 4648), mostrado uma vez no setup
+    enabled_at      TEXT,                   -- ISO 8601 (NULL = setup pendente)
+    last_used_step  INTEGER NOT NULL DEFAULT -1,  -- último passo aceito (anti-replay)
+    created_at      TEXT NOT NULL,          -- ISO 8601
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Códigos de recuperação de uso único (celular perdido)
+CREATE TABLE IF NOT EXISTS totp_backup_codes (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    user_id     TEXT NOT NULL,              -- FK → users.id
+    code_hash   TEXT NOT NULL,              -- SHA-256 do código normalizado
+    used_at     TEXT,                       -- ISO 8601 (NULL = disponível)
+    created_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Índices
+CREATE INDEX IF NOT EXISTS idx_totp_backup_codes_user  ON totp_backup_codes(user_id, code_hash);
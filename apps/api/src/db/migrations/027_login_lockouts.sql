@@ -0,0 +1,11 @@
+-- ============================================================
+-- MIGRATION 027 — Bloqueio progressivo de login
+-- ============================================================
+
+-- Falhas seguidas por username (existente ou não — não revela cadastros)
+CREATE TABLE IF NOT EXISTS login_failures (
+    username        TEXT PRIMARY KEY,
+    failed_count    INTEGER NOT NULL DEFAULT 0,   -- zera no login bem-sucedido
+    locked_until    TEXT,                   -- ISO 8601 (NULL = liberado)
+    last_failed_at  TEXT NOT NULL           -- ISO 8601
+);
@@ -0,0 +1,17 @@
+-- ============================================================
+-- MIGRATION 028 — Bloqueio de login por username + IP
+-- ============================================================
+
+-- Bloquear só pelo username deixava qualquer um trancar a conta
+-- alheia errando a senha. Os contadores são efêmeros: recria a tabela.
+DROP TABLE IF EXISTS login_failures;
+
+CREATE TABLE IF NOT EXISTS login_failures (
+    username        TEXT NOT NULL,
+    ip_address      TEXT NOT NULL,          -- IP de origem das falhas ('' se desconhecido)
+    failed_count    INTEGER NOT NULL DEFAULT 0,   -- zera no login bem-sucedido desse IP
+    locked_until    TEXT,                   -- ISO 8601 (NULL = liberado)
+    last_failed_at  TEXT NOT NULL,          -- ISO 8601
+
+    PRIMARY KEY (username, ip_address)
+);
@@ -0,0 +1,53 @@
+-- ============================================================
+-- MIGRATION 029 — CHECK no estado dos contratos
+-- ============================================================
+
+-- SQLite não adiciona constraint com ALTER TABLE: recria a tabela
+-- (cópia → troca de nome). Roda com foreign_keys desligado (ver
+-- db/connection.rs), senão o DROP apagaria em cascata os filhos.
+
+CREATE TABLE contracts_new (
+    id                  TEXT PRIMARY KEY,   -- UUID v4
+    version             TEXT NOT NULL DEFAULT 'CONTRACT_ESCROW_v1',
+    product_id          TEXT NOT NULL,      -- FK → products.id
+    buyer_pubkey        TEXT NOT NULL,      -- chave pública do comprador
+    seller_pubkey       TEXT NOT NULL,      -- chave pública do vendedor
+    arbiter_pubkey      TEXT NOT NULL,      -- chave pública do árbitro
+    amount_sats         INTEGER NOT NULL,   -- valor bloqueado em satoshis
+    fee_sats            INTEGER NOT NULL,   -- taxa do escrow em satoshis
+    item_hash           TEXT NOT NULL,      -- SHA-256 da descrição do produto
+    state               TEXT NOT NULL DEFAULT 'PENDING'
+                        CHECK (state IN ('PENDING', 'LOCKED', 'RELEASED', 'DISPUTED', 'REFUNDED')),
+    created_at_block    INTEGER NOT NULL,   -- altura do bloco de criação
+    expires_at_block    INTEGER NOT NULL,   -- altura do bloco de expiração
+    lock_tx_id          TEXT,               -- FK → transactions.id (TX de bloqueio)
+    release_tx_id       TEXT,               -- FK → transactions.id (TX de liberação)
+    created_at          TEXT NOT NULL,      -- ISO 8601
+    updated_at          TEXT NOT NULL,      -- ISO 8601
+    revision_id         TEXT,               -- FK → product_revisions.id (016)
+
+    FOREIGN KEY (product_id)    REFERENCES products(id)          ON DELETE RESTRICT,
+    FOREIGN KEY (lock_tx_id)    REFERENCES transactions(id)      ON DELETE SET NULL,
+    FOREIGN KEY (release_tx_id) REFERENCES transactions(id)      ON DELETE SET NULL,
+    FOREIGN KEY (revision_id)   REFERENCES product_revisions(id)
+);
+
+INSERT INTO contracts_new (
+    id, version, product_id, buyer_pubkey, seller_pubkey, arbiter_pubkey, amount_sats,
+    fee_sats, item_hash, state, created_at_block, expires_at_block, lock_tx_id,
+    release_tx_id, created_at, updated_at, revision_id
+)
+SELECT
+    id, version, product_id, buyer_pubkey, seller_pubkey, arbiter_pubkey, amount_sats,
+    fee_sats, item_hash, state, created_at_block, expires_at_block, lock_tx_id,
+    release_tx_id, created_at, updated_at, revision_id
+FROM contracts;
+
+DROP TABLE contracts;
+ALTER TABLE contracts_new RENAME TO contracts;
+
+-- Índices (caíram com a tabela antiga)
+CREATE INDEX IF NOT EXISTS idx_contracts_state          ON contracts(state);
+CREATE INDEX IF NOT EXISTS idx_contracts_product_id     ON contracts(product_id);
+CREATE INDEX IF NOT EXISTS idx_contracts_buyer_pubkey   ON contracts(buyer_pubkey);
+CREATE INDEX IF NOT EXISTS idx_contracts_seller_pubkey  ON contracts(seller_pubkey);
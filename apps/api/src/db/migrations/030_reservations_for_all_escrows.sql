@@ -0,0 +1,68 @@
+-- ============================================================
+-- MIGRATION 030 — Reserva de estoque para todo escrow
+-- ============================================================
+
+-- Toda abertura de escrow (ordem, escrow direto, leilão, oferta) passa
+-- a reservar estoque, então a reserva nem sempre tem ordem: `order_id`
+-- vira opcional. Recria a tabela (ver 029: roda com foreign_keys
+-- desligado).
+
+CREATE TABLE reservations_new (
+    id          TEXT PRIMARY KEY,           -- UUID v4
+    product_id  TEXT NOT NULL,              -- FK → products.id
+    order_id    TEXT UNIQUE,                -- FK → orders.id (NULL fora de ordens)
+    contract_id TEXT NOT NULL UNIQUE,       -- FK → contracts.id
+    quantity    INTEGER NOT NULL CHECK (quantity > 0),
+    status      TEXT NOT NULL DEFAULT 'HELD'
+                CHECK (status IN ('HELD', 'CONSUMED', 'RELEASED')),
+    created_at  TEXT NOT NULL,              -- ISO 8601
+    updated_at  TEXT NOT NULL,              -- ISO 8601
+
+    FOREIGN KEY (product_id)  REFERENCES products(id)  ON DELETE CASCADE,
+    FOREIGN KEY (order_id)    REFERENCES orders(id)    ON DELETE CASCADE,
+    FOREIGN KEY (contract_id) REFERENCES contracts(id) ON DELETE CASCADE
+);
+
+INSERT INTO reservations_new
+    (id, product_id, order_id, contract_id, quantity, status, created_at, updated_at)
+SELECT id, product_id, order_id, contract_id, quantity, status, created_at, updated_at
+FROM reservations;
+
+DROP TABLE reservations;
+ALTER TABLE reservations_new RENAME TO reservations;
+
+CREATE INDEX IF NOT EXISTS idx_reservations_product_id ON reservations(product_id);
+CREATE INDEX IF NOT EXISTS idx_reservations_status     ON reservations(status);
+
+-- Escrows abertos antes desta migration sem reserva (direto, leilão,
+-- oferta) seguravam 1 unidade implícita: prende a unidade no produto e
+-- grava a reserva, para que a liberação encontre o registro.
+UPDATE products
+SET reserved = reserved + (
+        SELECT COUNT(*) FROM contracts c
+        WHERE c.product_id = products.id
+          AND c.state IN ('PENDING', 'LOCKED', 'DISPUTED')
+          AND NOT EXISTS (SELECT 1 FROM reservations r WHERE r.contract_id = c.id)
+    ),
+    version = version + 1
+WHERE EXISTS (
+    SELECT 1 FROM contracts c
+    WHERE c.product_id = products.id
+      AND c.state IN ('PENDING', 'LOCKED', 'DISPUTED')
+      AND NOT EXISTS (SELECT 1 FROM reservations r WHERE r.contract_id = c.id)
+);
+
+INSERT INTO reservations
+    (id, product_id, order_id, contract_id, quantity, status, created_at, updated_at)
+SELECT lower(
+           hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' ||
+           substr(hex(randomblob(2)), 2) || '-' ||
+           substr('89ab', 1 + (abs(random()) % 4), 1) ||
+           substr(hex(randomblob(2)), 2) || '-' || hex(randomblob(6))
+       ),
+       c.product_id, NULL, c.id, 1, 'HELD',
+       strftime('%Y-%m-%dT%H:%M:%SZ', 'now'),
+       strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+FROM contracts c
+WHERE c.state IN ('PENDING', 'LOCKED', 'DISPUTED')
+  AND NOT EXISTS (SELECT 1 FROM reservations r WHERE r.contract_id = c.id);
@@ -0,0 +1,16 @@
+-- ============================================================
+-- MIGRATION 031 — Bloqueio após códigos TOTP errados
+-- ============================================================
+
+-- Códigos errados seguidos por usuário, em qualquer conferência do
+-- segundo fator (login, transferência, assinatura de contrato,
+-- desligar o 2FA). Sem isso os 10⁶ códigos podiam ser tentados à
+-- vontade por quem já tem a sessão.
+CREATE TABLE IF NOT EXISTS totp_failures (
+    user_id         TEXT PRIMARY KEY,       -- FK → users.id
+    failed_count    INTEGER NOT NULL DEFAULT 0,   -- zera no código aceito
+    locked_until    TEXT,                   -- ISO 8601 (NULL = liberado)
+    last_failed_at  TEXT NOT NULL,          -- ISO 8601
+
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
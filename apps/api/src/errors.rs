@@ -17,6 +17,12 @@ pub enum AppError {
     #[error("Não autorizado")]
     Unauthorized,
 
+    #[error("Código de verificação em duas etapas ausente ou inválido")]
+    TwoFactorRequired,
+
+    #[error("Muitas tentativas — tente novamente em {0}s")]
+    TooManyRequests(i64),   // segundos para o Retry-After
+
     // ─── Recursos ────────────────────────────────────────────
     #[error("Recurso não encontrado: {0}")]
     NotFound(String),
@@ -24,6 +30,9 @@ pub enum AppError {
     #[error("Recurso já existe: {0}")]
     AlreadyExists(String),
 
+    #[error("Conflito: {0}")]
+    Conflict(String),
+
     // ─── Validação ───────────────────────────────────────────
     #[error("Dados inválidos: {0}")]
     Validation(String),
@@ -59,16 +68,21 @@ impl actix_web::ResponseError for AppError {
         use AppError::*;
 
         match self {
-            InvalidCredentials | InvalidToken | InvalidSignature => {
+            InvalidCredentials | InvalidToken | InvalidSignature | TwoFactorRequired => {
                 HttpResponse::Unauthorized().json(error_body(self))
             }
             Unauthorized => {
                 HttpResponse::Forbidden().json(error_body(self))
             }
+            TooManyRequests(retry_after) => {
+                HttpResponse::TooManyRequests()
+                    .insert_header((actix_web::http::header::RETRY_AFTER, retry_after.to_string()))
+                    .json(error_body(self))
+            }
             NotFound(_) => {
                 HttpResponse::NotFound().json(error_body(self))
             }
-            AlreadyExists(_) => {
+            AlreadyExists(_) | Conflict(_) => {
                 HttpResponse::Conflict().json(error_body(self))
             }
             Validation(_) | InvalidTransaction(_) | InvalidBlock(_) | InvalidContractState(_) => {
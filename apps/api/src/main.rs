@@ -1,4 +1,5 @@
-use actix_web::{web, App, HttpServer, middleware};
+use actix_web::{web, App, HttpServer};
+use actix_web::middleware::{from_fn, Logger};
 use dotenvy::dotenv;
 use std::env;
 use tracing::info;
@@ -10,7 +11,9 @@ mod blockchain;
 mod crypto;
 mod db;
 mod ws;
+mod storage;
 mod errors;
+mod middleware;
 
 use db::connection::init_db;
 
@@ -36,6 +39,23 @@ async fn main() -> std::io::Result<()> {
     let pool = init_db().await.expect("Falha ao conectar ao banco de dados");
     let pool = web::Data::new(pool);
 
+    // ─── Primeiro admin (ADMIN_ADDRESSES) ────────────────────
+    let admins = blockchain::roles::bootstrap_admins(pool.as_ref())
+        .await
+        .expect("Falha ao conceder papel admin");
+    if admins > 0 {
+        info!("🔑 {} admin(s) concedido(s) por ADMIN_ADDRESSES", admins);
+    }
+
+    // ─── Hub de sessões WebSocket (eventos por carteira) ─────
+    let hub = web::Data::new(ws::hub::Hub::default());
+
+    // ─── Armazenamento de imagens e anexos ───────────────────
+    let media = web::Data::new(storage::local::MediaStore::from_env());
+
+    // ─── Limites por rota (RATE_LIMIT_*) ─────────────────────
+    let limiter = web::Data::new(middleware::rate_limit::RateLimiter::from_env());
+
     info!("🚀 PaperMarket API rodando em http://{}", addr);
 
     // ─── Iniciar servidor ────────────────────────────────────
@@ -49,13 +69,18 @@ async fn main() -> std::io::Result<()> {
             .allowed_headers(vec![
                 actix_web::http::header::AUTHORIZATION,
                 actix_web::http::header::CONTENT_TYPE,
+                actix_web::http::header::HeaderName::from_static("x-totp-code"),
             ])
             .max_age(3600);
 
         App::new()
             .app_data(pool.clone())
+            .app_data(hub.clone())
+            .app_data(media.clone())
+            .app_data(limiter.clone())
+            .wrap(from_fn(middleware::auth::authenticate))
             .wrap(cors)
-            .wrap(middleware::Logger::default())
+            .wrap(Logger::default())
             // ─── Rotas ──────────────────────────────────────
             .service(
                 web::scope("/api")
@@ -65,9 +90,24 @@ async fn main() -> std::io::Result<()> {
                     .configure(routes::chain::config)
                     .configure(routes::mining::config)
                     .configure(routes::contracts::config)
+                    .configure(routes::orders::config)
+                    .configure(routes::auctions::config)
+                    .configure(routes::offers::config)
+                    .configure(routes::reputation::config)
+                    .configure(routes::messages::config)
+                    .configure(routes::media::config)
+                    .configure(routes::categories::config)
+                    .configure(routes::watchlist::config)
+                    .configure(routes::notifications::config)
+                    .configure(routes::sellers::config)
+                    .configure(routes::moderation::config)
+                    .configure(routes::admin::config)
             )
             // ─── WebSocket ───────────────────────────────────
-            .route("/ws", web::get().to(ws::handler::ws_handler))
+            .service(
+                middleware::rate_limit::limited("/ws", models::rate_limit::LimitedRoute::WebSocket)
+                    .route(web::get().to(ws::handler::ws_handler)),
+            )
     })
     .bind(&addr)?
     .run()
@@ -0,0 +1,81 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpMessage};
+use futures_util::future::LocalBoxFuture;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::role::Role;
+use crate::models::user::Claims;
+use crate::blockchain::roles::require_role;
+use crate::blockchain::sessions::is_token_revoked;
+use crate::routes::auth::decode_token;
+
+// ─── Autenticação (toda a API) ───────────────────────────────
+// Com `Authorization: Bearer <jwt>` válido, insere os Claims nas
+// extensões da requisição; token inválido, expirado ou revogado → 401.
+// Sem cabeçalho segue adiante — cada rota decide se exige login.
+pub async fn authenticate(
+    req:  ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let token = req
+        .headers()
+        .get(actix_web::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::to_string);
+
+    if let Some(token) = token {
+        let claims = decode_token(token.trim())?;
+
+        // Tokens anteriores às sessões não têm jti/sid e escapariam da
+        // revogação — exigem novo login
+        if claims.jti.is_empty() || claims.sid.is_empty() {
+            return Err(AppError::InvalidToken.into());
+        }
+
+        // Logout e rotação revogam o jti antes de o token expirar
+        let pool = req
+            .app_data::<web::Data<SqlitePool>>()
+            .cloned()
+            .ok_or_else(|| AppError::Internal("Pool não configurado".into()))?;
+
+        if is_token_revoked(pool.as_ref(), &claims.jti).await? {
+            return Err(AppError::InvalidToken.into());
+        }
+
+        req.extensions_mut().insert(claims);
+    }
+
+    next.call(req).await
+}
+
+// ─── Guarda de papel (por escopo) ────────────────────────────
+// Uso: `web::scope("/admin").wrap(from_fn(role_guard(Role::Admin)))`
+pub fn role_guard<B>(
+    role: Role,
+) -> impl Fn(ServiceRequest, Next<B>) -> LocalBoxFuture<'static, Result<ServiceResponse<B>, Error>>
+where
+    B: MessageBody + 'static,
+{
+    move |req, next| {
+        Box::pin(async move {
+            let claims = req
+                .extensions()
+                .get::<Claims>()
+                .cloned()
+                .ok_or(AppError::Unauthorized)?;
+
+            let pool = req
+                .app_data::<web::Data<SqlitePool>>()
+                .cloned()
+                .ok_or_else(|| AppError::Internal("Pool não configurado".into()))?;
+
+            require_role(pool.as_ref(), &claims, role).await?;
+
+            next.call(req).await
+        })
+    }
+}
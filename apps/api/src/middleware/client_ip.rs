@@ -0,0 +1,97 @@
+use actix_web::HttpRequest;
+use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+// ─── IP do cliente ───────────────────────────────────────────
+// Por padrão é o IP da conexão (`peer_addr`): cabeçalhos como
+// `X-Forwarded-For` vêm do cliente e podem ser forjados. Só quando
+// a conexão chega de um proxy em `TRUSTED_PROXIES` (IPs separados
+// por vírgula) o cabeçalho é lido — da direita para a esquerda,
+// pulando os proxies confiáveis.
+pub fn client_ip(req: &HttpRequest) -> Option<String> {
+    let forwarded = req
+        .headers()
+        .get("X-Forwarded-For")
+        .and_then(|value| value.to_str().ok());
+
+    resolve_client_ip(req.peer_addr(), forwarded, trusted_proxies())
+}
+
+fn trusted_proxies() -> &'static [IpAddr] {
+    static TRUSTED: OnceLock<Vec<IpAddr>> = OnceLock::new();
+
+    TRUSTED.get_or_init(|| {
+        env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .filter_map(|ip| {
+                ip.parse()
+                    .map_err(|_| tracing::warn!("TRUSTED_PROXIES: IP inválido ignorado ({})", ip))
+                    .ok()
+            })
+            .collect()
+    })
+}
+
+fn resolve_client_ip(
+    peer:      Option<SocketAddr>,
+    forwarded: Option<&str>,
+    trusted:   &[IpAddr],
+) -> Option<String> {
+    let peer = peer?.ip();
+    if !trusted.contains(&peer) {
+        return Some(peer.to_string());
+    }
+
+    // Cada proxy acrescenta à direita quem o chamou; o primeiro IP não
+    // confiável a partir da direita é o cliente. O resto é do cliente.
+    let client = forwarded
+        .into_iter()
+        .flat_map(|header| header.rsplit(','))
+        .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+        .find(|ip| !trusted.contains(ip));
+
+    Some(client.unwrap_or(peer).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn peer(ip: &str) -> Option<SocketAddr> {
+        Some(SocketAddr::new(ip.parse().unwrap(), 40000))
+    }
+
+    #[test]
+    fn test_spoofed_header_ignored_without_trusted_proxy() {
+        let req = TestRequest::default()
+            .peer_addr("203.0.113.7:40000".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "198.51.100.1"))
+            .to_http_request();
+
+        assert_eq!(client_ip(&req).as_deref(), Some("203.0.113.7"));
+    }
+
+    #[test]
+    fn test_trusted_proxy_uses_rightmost_untrusted() {
+        let trusted: Vec<IpAddr> = vec!["10.0.0.1".parse().unwrap()];
+
+        // Cliente forja o primeiro valor; o proxy acrescenta o IP real
+        let ip = resolve_client_ip(peer("10.0.0.1"), Some("198.51.100.1, 203.0.113.7"), &trusted);
+        assert_eq!(ip.as_deref(), Some("203.0.113.7"));
+
+        // Sem cabeçalho (ou só lixo) fica o IP do proxy
+        let ip = resolve_client_ip(peer("10.0.0.1"), Some("lixo"), &trusted);
+        assert_eq!(ip.as_deref(), Some("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_header() {
+        let ip = resolve_client_ip(peer("203.0.113.7"), Some("198.51.100.1"), &[]);
+        assert_eq!(ip.as_deref(), Some("203.0.113.7"));
+    }
+}
@@ -0,0 +1,115 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::middleware::{from_fn, Next};
+use actix_web::{web, Error, HttpMessage, Resource};
+use futures_util::future::LocalBoxFuture;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::errors::AppError;
+use crate::middleware::client_ip::client_ip;
+use crate::models::rate_limit::{LimitedRoute, RateLimitPolicy, TokenBucket};
+use crate::models::user::Claims;
+
+/// Acima disso, baldes já cheios são descartados a cada verificação
+const PRUNE_THRESHOLD: usize = 10_000;
+
+// ─── Estado do limitador (em memória, por processo) ──────────
+// Políticas lidas do env na subida; baldes por rota + IP e por
+// rota + usuário autenticado.
+pub struct RateLimiter {
+    policies: HashMap<LimitedRoute, RateLimitPolicy>,
+    buckets:  Mutex<HashMap<(LimitedRoute, String), TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn from_env() -> Self {
+        let policies = LimitedRoute::ALL
+            .iter()
+            .filter_map(|route| route.policy().map(|policy| (*route, policy)))
+            .collect();
+
+        Self {
+            policies,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gasta uma ficha de cada chave; a primeira sem fichas decide o Retry-After
+    fn check(&self, route: LimitedRoute, keys: &[String]) -> Result<(), i64> {
+        let Some(policy) = self.policies.get(&route) else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|(r, _), bucket| {
+                self.policies.get(r).is_some_and(|p| !bucket.is_full(p, now))
+            });
+        }
+
+        for key in keys {
+            buckets
+                .entry((route, key.clone()))
+                .or_insert_with(|| TokenBucket::new(policy, now))
+                .try_take(policy, now)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ─── Limite por rota ─────────────────────────────────────────
+// Uso: `web::resource("/login").wrap(from_fn(rate_limit(LimitedRoute::Login)))`
+// Estourado → 429 com Retry-After. Roda depois de `authenticate`,
+// então requisições autenticadas também contam por usuário.
+pub fn rate_limit<B>(
+    route: LimitedRoute,
+) -> impl Fn(ServiceRequest, Next<B>) -> LocalBoxFuture<'static, Result<ServiceResponse<B>, Error>>
+where
+    B: MessageBody + 'static,
+{
+    move |req, next| {
+        Box::pin(async move {
+            let limiter = req
+                .app_data::<web::Data<RateLimiter>>()
+                .cloned()
+                .ok_or_else(|| AppError::Internal("Limitador não configurado".into()))?;
+
+            let mut keys = Vec::with_capacity(2);
+            if let Some(ip) = client_ip(req.request()) {
+                keys.push(format!("ip:{}", ip));
+            }
+            if let Some(claims) = req.extensions().get::<Claims>() {
+                keys.push(format!("user:{}", claims.sub));
+            }
+
+            if let Err(retry_after) = limiter.check(route, &keys) {
+                tracing::warn!("Limite de {} estourado ({})", route.as_str(), keys.join(", "));
+                return Err(AppError::TooManyRequests(retry_after).into());
+            }
+
+            next.call(req).await
+        })
+    }
+}
+
+/// Recurso já envolvido pelo limite da rota:
+/// `.service(limited("/login", LimitedRoute::Login).route(web::post().to(login)))`
+pub fn limited(
+    path:  &str,
+    route: LimitedRoute,
+) -> Resource<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config   = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error    = Error,
+        InitError = (),
+    >,
+> {
+    web::resource(path).wrap(from_fn(rate_limit(route)))
+}
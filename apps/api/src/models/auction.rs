@@ -0,0 +1,234 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Tipo de leilão ──────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum AuctionKind {
+    English,    // lances crescentes, maior lance vence no fim
+    Dutch,      // preço cai a cada bloco, primeiro lance vence
+}
+
+// ─── Status do leilão ────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum AuctionStatus {
+    Open,       // recebendo lances
+    Settled,    // vencedor definido, escrow aberto
+    Unsold,     // encerrado sem lance válido
+    Cancelled,  // cancelado pelo vendedor antes de qualquer lance
+}
+
+// ─── Leilão ──────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Auction {
+    pub id:                   String,
+    pub product_id:           String,
+    pub seller_id:            String,
+    pub kind:                 AuctionKind,
+    pub start_price_sats:     i64,
+    pub reserve_price_sats:   i64,            // mínimo aceito (piso no holandês)
+    pub min_increment_sats:   i64,            // inglês: incremento mínimo entre lances
+    pub decay_per_block_sats: i64,            // holandês: queda de preço por bloco
+    pub start_block:          i64,
+    pub end_block:            i64,
+    pub extension_blocks:     i64,            // anti-sniping: janela e extensão
+    pub highest_bid_sats:     Option<i64>,
+    pub status:               AuctionStatus,
+    pub winner_id:            Option<String>,
+    pub contract_id:          Option<String>, // escrow aberto para o vencedor
+    pub created_at:           String,
+    pub updated_at:           String,
+}
+
+// ─── Regras de preço do leilão (já validadas) ────────────────
+#[derive(Debug, Clone, Copy)]
+pub struct AuctionTerms {
+    pub kind:                 AuctionKind,
+    pub start_price_sats:     i64,
+    pub reserve_price_sats:   i64,
+    pub min_increment_sats:   i64,
+    pub decay_per_block_sats: i64,
+    pub extension_blocks:     i64,
+}
+
+impl Auction {
+    pub fn new(
+        product_id:  String,
+        seller_id:   String,
+        terms:       AuctionTerms,
+        start_block: i64,
+        end_block:   i64,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:                   Uuid::new_v4().to_string(),
+            product_id,
+            seller_id,
+            kind:                 terms.kind,
+            start_price_sats:     terms.start_price_sats,
+            reserve_price_sats:   terms.reserve_price_sats,
+            min_increment_sats:   terms.min_increment_sats,
+            decay_per_block_sats: terms.decay_per_block_sats,
+            start_block,
+            end_block,
+            extension_blocks:     terms.extension_blocks,
+            highest_bid_sats:     None,
+            status:               AuctionStatus::Open,
+            winner_id:            None,
+            contract_id:          None,
+            created_at:           now.clone(),
+            updated_at:           now,
+        }
+    }
+
+    /// Preço corrente na altura `height`.
+    /// Inglês: maior lance (ou preço inicial). Holandês: decai até o piso.
+    pub fn price_at(&self, height: i64) -> i64 {
+        match self.kind {
+            AuctionKind::English => self.highest_bid_sats.unwrap_or(self.start_price_sats),
+            AuctionKind::Dutch => {
+                let elapsed = (height - self.start_block).max(0);
+                let decayed = self
+                    .start_price_sats
+                    .saturating_sub(self.decay_per_block_sats.saturating_mul(elapsed));
+                decayed.max(self.reserve_price_sats)
+            }
+        }
+    }
+
+    /// Lance mínimo aceito no leilão inglês
+    pub fn min_next_bid(&self) -> i64 {
+        match self.highest_bid_sats {
+            Some(highest) => highest.saturating_add(self.min_increment_sats),
+            None          => self.start_price_sats,
+        }
+    }
+
+    /// Novo bloco final após um lance em `height` (anti-sniping):
+    /// lances nos últimos `extension_blocks` empurram o fim para frente.
+    pub fn end_block_after_bid(&self, height: i64) -> i64 {
+        if self.end_block - height <= self.extension_blocks {
+            self.end_block.max(height + self.extension_blocks)
+        } else {
+            self.end_block
+        }
+    }
+}
+
+// ─── Lance ───────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Bid {
+    pub id:            String,
+    pub auction_id:    String,
+    pub bidder_id:     String,
+    pub bidder_pubkey: String,
+    pub amount_sats:   i64,
+    pub signature:     String,   // assinatura secp256k1 de `bid_message`
+    pub block_height:  i64,      // altura da chain quando o lance foi aceito
+    pub created_at:    String,
+}
+
+impl Bid {
+    pub fn new(
+        auction_id:    String,
+        bidder_id:     String,
+        bidder_pubkey: String,
+        amount_sats:   i64,
+        signature:     String,
+        block_height:  i64,
+    ) -> Self {
+        Self {
+            id:            Uuid::new_v4().to_string(),
+            auction_id,
+            bidder_id,
+            bidder_pubkey,
+            amount_sats,
+            signature,
+            block_height,
+            created_at:    Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Mensagem que o licitante assina para se comprometer com o lance
+pub fn bid_message(auction_id: &str, amount_sats: i64) -> String {
+    format!("PAPERMARKET_BID:{}:{}", auction_id, amount_sats)
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Criar leilão
+#[derive(Debug, Deserialize)]
+pub struct CreateAuctionRequest {
+    pub product_id:           String,
+    pub kind:                 AuctionKind,
+    pub start_price_sats:     i64,
+    pub reserve_price_sats:   Option<i64>,
+    pub min_increment_sats:   Option<i64>,   // inglês (padrão: 1)
+    pub decay_per_block_sats: Option<i64>,   // holandês (obrigatório)
+    pub duration_blocks:      i64,
+    pub extension_blocks:     Option<i64>,   // inglês (padrão: 5)
+}
+
+/// Dar lance
+#[derive(Debug, Deserialize)]
+pub struct PlaceBidRequest {
+    pub amount_sats: i64,
+    pub signature:   String,   // assinatura de `PAPERMARKET_BID:{auction_id}:{amount_sats}`
+}
+
+/// Leilão com lances e preço corrente
+#[derive(Debug, Serialize)]
+pub struct AuctionResponse {
+    pub auction:       Auction,
+    pub bids:          Vec<Bid>,
+    pub current_price: i64,
+    pub min_next_bid:  Option<i64>,   // apenas leilões ingleses abertos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auction(kind: AuctionKind) -> Auction {
+        let terms = AuctionTerms {
+            kind,
+            start_price_sats:     1_000,
+            reserve_price_sats:   400,
+            min_increment_sats:   50,
+            decay_per_block_sats: 100,
+            extension_blocks:     3,
+        };
+        Auction::new("p".into(), "s".into(), terms, 10, 20)
+    }
+
+    #[test]
+    fn test_dutch_price_decays_to_reserve() {
+        let a = auction(AuctionKind::Dutch);
+        assert_eq!(a.price_at(10), 1_000);
+        assert_eq!(a.price_at(13), 700);
+        assert_eq!(a.price_at(100), 400);
+        assert_eq!(a.price_at(0), 1_000);
+    }
+
+    #[test]
+    fn test_english_min_next_bid() {
+        let mut a = auction(AuctionKind::English);
+        assert_eq!(a.min_next_bid(), 1_000);
+        a.highest_bid_sats = Some(1_200);
+        assert_eq!(a.min_next_bid(), 1_250);
+        assert_eq!(a.price_at(15), 1_200);
+    }
+
+    #[test]
+    fn test_anti_sniping_extension() {
+        let a = auction(AuctionKind::English);
+        assert_eq!(a.end_block_after_bid(12), 20);
+        assert_eq!(a.end_block_after_bid(17), 20);
+        assert_eq!(a.end_block_after_bid(19), 22);
+    }
+}
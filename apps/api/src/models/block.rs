@@ -4,41 +4,40 @@ use serde::{Deserialize, Serialize};
 // ─── Bloco ───────────────────────────────────────────────────
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Block {
-    pub id:             String,    // hash SHA-256 do bloco
-    pub height:         i64,       // altura na chain
-    pub prev_hash:      String,    // hash do bloco anterior
-    pub merkle_root:    String,    // merkle root das TXs
-    pub nonce:          i64,       // nonce encontrado no PoW
-    pub difficulty:     i64,       // dificuldade no momento
-    pub reward_sats:    i64,       // recompensa em satoshis
-    pub miner_address:  String,    // endereço BPC do minerador
-    pub tx_count:       i64,       // quantidade de TXs
-    pub mined_at:       String,    // ISO 8601
+    pub id:              String,    // hash SHA-256 do bloco
+    pub height:          i64,       // altura na chain
+    pub prev_hash:       String,    // hash do bloco anterior
+    pub merkle_root:     String,    // merkle root das TXs
+    pub nonce:           i64,       // nonce encontrado no PoW
+    pub difficulty:      i64,       // dificuldade no momento
+    pub reward_sats:     i64,       // recompensa em satoshis
+    pub miner_address:   String,    // endereço BPC do minerador
+    pub tx_count:        i64,       // quantidade de TXs
+    pub mined_at:        String,    // ISO 8601
+    pub reputation_root: Option<String>, // root de reputação ancorada no header
 }
 
 impl Block {
+    /// Bloco aceito a partir do header já conferido no PoW
     pub fn new(
         id:            String,
-        height:        i64,
-        prev_hash:     String,
-        merkle_root:   String,
-        nonce:         i64,
-        difficulty:    i64,
+        header:        BlockHeader,
         reward_sats:   i64,
         miner_address: String,
         tx_count:      i64,
     ) -> Self {
         Self {
             id,
-            height,
-            prev_hash,
-            merkle_root,
-            nonce,
-            difficulty,
+            height:          header.height,
+            prev_hash:       header.prev_hash,
+            merkle_root:     header.merkle_root,
+            nonce:           header.nonce,
+            difficulty:      header.difficulty,
             reward_sats,
             miner_address,
             tx_count,
-            mined_at: Utc::now().to_rfc3339(),
+            mined_at:        Utc::now().to_rfc3339(),
+            reputation_root: header.reputation_root,
         }
     }
 }
@@ -46,12 +45,13 @@ impl Block {
 // ─── Header do bloco (usado no PoW) ──────────────────────────
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlockHeader {
-    pub height:      i64,
-    pub prev_hash:   String,
-    pub merkle_root: String,
-    pub nonce:       i64,
-    pub difficulty:  i64,
-    pub timestamp:   String,
+    pub height:          i64,
+    pub prev_hash:       String,
+    pub merkle_root:     String,
+    pub nonce:           i64,
+    pub difficulty:      i64,
+    pub timestamp:       String,
+    pub reputation_root: Option<String>, // fotografia pendente fecha o header
 }
 
 impl BlockHeader {
@@ -65,22 +65,24 @@ impl BlockHeader {
             height,
             prev_hash,
             merkle_root,
-            nonce:      0,
+            nonce:           0,
             difficulty,
-            timestamp:  Utc::now().to_rfc3339(),
+            timestamp:       Utc::now().to_rfc3339(),
+            reputation_root: None,
         }
     }
 
     /// Serializa o header para hashing
     pub fn to_bytes(&self) -> String {
         format!(
-            "{}{}{}{}{}{}",
+            "{}{}{}{}{}{}{}",
             self.height,
             self.prev_hash,
             self.merkle_root,
             self.nonce,
             self.difficulty,
             self.timestamp,
+            self.reputation_root.as_deref().unwrap_or_default(),
         )
     }
 }
@@ -90,12 +92,13 @@ impl BlockHeader {
 /// Job de mineração enviado ao minerador
 #[derive(Debug, Serialize)]
 pub struct MiningJob {
-    pub block_height:  i64,
-    pub prev_hash:     String,
-    pub merkle_root:   String,
-    pub difficulty:    i64,
-    pub target:        String,    // string de zeros ex: "0000..."
-    pub reward_sats:   i64,
+    pub block_height:    i64,
+    pub prev_hash:       String,
+    pub merkle_root:     String,
+    pub difficulty:      i64,
+    pub target:          String,    // string de zeros ex: "0000..."
+    pub reward_sats:     i64,
+    pub reputation_root: Option<String>, // se presente, vai no fim do header
 }
 
 /// Submissão de bloco minerado
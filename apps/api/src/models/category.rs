@@ -0,0 +1,150 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// ─── Categoria ───────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct Category {
+    pub id:         String,
+    pub parent_id:  Option<String>,   // NULL = raiz
+    pub slug:       String,
+    pub name:       String,
+    pub position:   i64,
+    pub active:     bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Category {
+    pub fn new(parent_id: Option<String>, slug: String, name: String, position: i64) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            parent_id,
+            slug,
+            name,
+            position,
+            active:     true,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+/// Slug: minúsculas, dígitos e hífen, até 40 caracteres
+pub fn validate_slug(slug: &str) -> Result<(), String> {
+    let valid = !slug.is_empty()
+        && slug.len() <= 40
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err("Slug deve ter até 40 caracteres: a-z, 0-9 e hífen".into())
+    }
+}
+
+// ─── Árvore ──────────────────────────────────────────────────
+#[derive(Debug, Serialize)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: Category,
+    pub children: Vec<CategoryNode>,
+}
+
+/// Monta a árvore a partir da lista plana, respeitando `position`.
+/// Categorias cujo pai não está na lista viram raízes.
+pub fn build_tree(categories: Vec<Category>) -> Vec<CategoryNode> {
+    let ids: Vec<String> = categories.iter().map(|c| c.id.clone()).collect();
+    let mut by_parent: HashMap<Option<String>, Vec<Category>> = HashMap::new();
+
+    for category in categories {
+        let parent = category.parent_id.clone().filter(|p| ids.contains(p));
+        by_parent.entry(parent).or_default().push(category);
+    }
+
+    fn attach(
+        parent:    Option<String>,
+        by_parent: &mut HashMap<Option<String>, Vec<Category>>,
+    ) -> Vec<CategoryNode> {
+        let mut level = by_parent.remove(&parent).unwrap_or_default();
+        level.sort_by(|a, b| a.position.cmp(&b.position).then_with(|| a.name.cmp(&b.name)));
+
+        level
+            .into_iter()
+            .map(|category| {
+                let children = attach(Some(category.id.clone()), by_parent);
+                CategoryNode { category, children }
+            })
+            .collect()
+    }
+
+    attach(None, &mut by_parent)
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Criar categoria (admin)
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryRequest {
+    pub slug:      String,
+    pub name:      String,
+    pub parent_id: Option<String>,
+    pub position:  Option<i64>,
+}
+
+/// Editar categoria (admin). `parent_id: ""` move para a raiz.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCategoryRequest {
+    pub slug:      Option<String>,
+    pub name:      Option<String>,
+    pub parent_id: Option<String>,
+    pub position:  Option<i64>,
+    pub active:    Option<bool>,
+}
+
+/// Parâmetros de GET /api/categories
+#[derive(Debug, Deserialize)]
+pub struct CategoryFilters {
+    pub all: Option<bool>,   // inclui inativas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cat(id: &str, parent: Option<&str>, position: i64) -> Category {
+        let mut c = Category::new(parent.map(String::from), id.into(), id.into(), position);
+        c.id = id.into();
+        c
+    }
+
+    #[test]
+    fn test_build_tree() {
+        let tree = build_tree(vec![
+            cat("celulares", Some("tech"), 1),
+            cat("moda", None, 1),
+            cat("tech", None, 0),
+            cat("notebooks", Some("tech"), 0),
+            cat("orfa", Some("inexistente"), 2),
+        ]);
+
+        let roots: Vec<&str> = tree.iter().map(|n| n.category.id.as_str()).collect();
+        assert_eq!(roots, vec!["tech", "moda", "orfa"]);
+
+        let children: Vec<&str> = tree[0].children.iter().map(|n| n.category.id.as_str()).collect();
+        assert_eq!(children, vec!["notebooks", "celulares"]);
+    }
+
+    #[test]
+    fn test_validate_slug() {
+        assert!(validate_slug("foto-analogica").is_ok());
+        assert!(validate_slug("Foto").is_err());
+        assert!(validate_slug("-foto").is_err());
+        assert!(validate_slug("foto/analogica").is_err());
+        assert!(validate_slug("").is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Finalidade do desafio ───────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum ChallengePurpose {
+    #[default]
+    Login,     // POST /api/auth/verify
+    Recover,   // POST /api/auth/recover
+}
+
+impl ChallengePurpose {
+    /// Primeira linha do texto assinado
+    fn title(&self) -> &'static str {
+        match self {
+            ChallengePurpose::Login   => "PaperMarket login",
+            ChallengePurpose::Recover => "PaperMarket recuperação de conta",
+        }
+    }
+}
+
+// ─── Desafio de login / recuperação ──────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuthChallenge {
+    pub id:         String,
+    pub address:    String,
+    pub purpose:    ChallengePurpose,
+    pub nonce:      String,
+    pub message:    String,
+    pub expires_at: String,
+    pub used_at:    Option<String>,
+    pub created_at: String,
+}
+
+impl AuthChallenge {
+    pub fn new(address: String, purpose: ChallengePurpose, ttl_seconds: i64) -> Self {
+        let now = Utc::now();
+        let nonce = hex::encode(rand::thread_rng().gen::<[u8; 16]>());
+        let expires_at = (now + Duration::seconds(ttl_seconds)).to_rfc3339();
+
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            message:    challenge_message(purpose, &address, &nonce, &expires_at),
+            address,
+            purpose,
+            nonce,
+            expires_at,
+            used_at:    None,
+            created_at: now.to_rfc3339(),
+        }
+    }
+
+    /// Ainda não usado e dentro da validade
+    pub fn is_valid(&self, now: DateTime<Utc>) -> bool {
+        self.used_at.is_none()
+            && DateTime::parse_from_rfc3339(&self.expires_at)
+                .map(|exp| exp > now)
+                .unwrap_or(false)
+    }
+}
+
+/// Texto assinado pelo cliente — finalidade, carteira, nonce e validade
+/// ficam explícitos para a assinatura não servir em outro contexto.
+pub fn challenge_message(
+    purpose:    ChallengePurpose,
+    address:    &str,
+    nonce:      &str,
+    expires_at: &str,
+) -> String {
+    format!(
+        "{}\nendereço: {}\nnonce: {}\nexpira: {}",
+        purpose.title(), address, nonce, expires_at
+    )
+}
+
+/// Prova de posse no cadastro: o cliente assina username + pubkey
+/// com a chave privada que acabou de gerar.
+pub fn register_message(username: &str, pubkey: &str) -> String {
+    format!("PaperMarket cadastro\nusuário: {}\npubkey: {}", username, pubkey)
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Pedido de desafio
+#[derive(Debug, Deserialize)]
+pub struct ChallengeRequest {
+    pub address: String,
+    #[serde(default)]
+    pub purpose: ChallengePurpose,   // LOGIN (padrão) | RECOVER
+}
+
+/// Desafio a ser assinado
+#[derive(Debug, Serialize)]
+pub struct ChallengeResponse {
+    pub challenge_id: String,
+    pub message:      String,   // assinar exatamente este texto (SHA-256 + secp256k1)
+    pub expires_at:   String,
+}
+
+/// Assinatura do desafio
+#[derive(Debug, Deserialize)]
+pub struct VerifyChallengeRequest {
+    pub challenge_id: String,
+    pub signature:    String,   // ECDSA compacta em hex
+    pub totp_code:    Option<String>,   // exigido com 2FA ativo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::signing::{sign_message, verify_signature};
+    use secp256k1::Secp256k1;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_challenge_message() {
+        let challenge = AuthChallenge::new("1BPCABC".into(), ChallengePurpose::Login, 300);
+        assert!(challenge.message.contains("1BPCABC"));
+        assert!(challenge.message.contains(&challenge.nonce));
+        assert_eq!(challenge.nonce.len(), 32);
+    }
+
+    #[test]
+    fn test_purpose_changes_message() {
+        let login = challenge_message(ChallengePurpose::Login, "1BPCABC", "n1", "t");
+        let recover = challenge_message(ChallengePurpose::Recover, "1BPCABC", "n1", "t");
+        assert_ne!(login, recover);
+        assert!(recover.starts_with("PaperMarket recuperação"));
+    }
+
+    #[test]
+    fn test_challenge_expiry_and_single_use() {
+        let mut challenge = AuthChallenge::new("1BPCABC".into(), ChallengePurpose::Login, 300);
+        assert!(challenge.is_valid(Utc::now()));
+        assert!(!challenge.is_valid(Utc::now() + Duration::seconds(301)));
+
+        challenge.used_at = Some(Utc::now().to_rfc3339());
+        assert!(!challenge.is_valid(Utc::now()));
+    }
+
+    #[test]
+    fn test_signed_challenge_verifies() {
+        let secp = Secp256k1::new();
+        let (sk, pk) = secp.generate_keypair(&mut OsRng);
+        let (sk, pk) = (hex::encode(sk.secret_bytes()), hex::encode(pk.serialize()));
+
+        let challenge = AuthChallenge::new("1BPCABC".into(), ChallengePurpose::Login, 300);
+        let signature = sign_message(&challenge.message, &sk).unwrap();
+
+        assert!(verify_signature(&challenge.message, &signature, &pk).is_ok());
+        assert!(verify_signature(&AuthChallenge::new("1BPCABC".into(), ChallengePurpose::Login, 300).message, &signature, &pk).is_err());
+    }
+
+    #[test]
+    fn test_register_proof_of_possession() {
+        let secp = Secp256k1::new();
+        let (sk, pk) = secp.generate_keypair(&mut OsRng);
+        let (sk, pk) = (hex::encode(sk.secret_bytes()), hex::encode(pk.serialize()));
+
+        let signature = sign_message(&register_message("alice", &pk), &sk).unwrap();
+
+        assert!(verify_signature(&register_message("alice", &pk), &signature, &pk).is_ok());
+        assert!(verify_signature(&register_message("mallory", &pk), &signature, &pk).is_err());
+    }
+}
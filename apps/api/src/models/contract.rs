@@ -2,8 +2,13 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::product::ProductRevision;
+
 // ─── Estado do contrato ──────────────────────────────────────
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+// Persistido como TEXT em maiúsculas (PENDING, LOCKED, ...).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
 pub enum ContractState {
     Pending,    // criado, aguardando bloqueio de fundos
     Locked,     // fundos bloqueados na chain
@@ -13,7 +18,7 @@ pub enum ContractState {
 }
 
 impl ContractState {
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             ContractState::Pending  => "PENDING",
             ContractState::Locked   => "LOCKED",
@@ -23,13 +28,52 @@ impl ContractState {
         }
     }
 
-    pub fn from_str(s: &str) -> Self {
+    /// Tabela de transições válidas do CONTRACT_ESCROW_v1:
+    ///
+    /// ```text
+    /// PENDING  → LOCKED | REFUNDED
+    /// LOCKED   → RELEASED | DISPUTED | REFUNDED
+    /// DISPUTED → RELEASED | REFUNDED
+    /// RELEASED, REFUNDED → (final)
+    /// ```
+    pub fn can_transition_to(&self, next: ContractState) -> bool {
+        use ContractState::*;
+
+        matches!(
+            (self, next),
+            (Pending,  Locked)
+                | (Pending,  Refunded)
+                | (Locked,   Released)
+                | (Locked,   Disputed)
+                | (Locked,   Refunded)
+                | (Disputed, Released)
+                | (Disputed, Refunded)
+        )
+    }
+
+    /// Estado final — nenhuma transição é possível
+    pub fn is_final(&self) -> bool {
+        matches!(self, ContractState::Released | ContractState::Refunded)
+    }
+}
+
+impl std::fmt::Display for ContractState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ContractState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "LOCKED"   => ContractState::Locked,
-            "RELEASED" => ContractState::Released,
-            "DISPUTED" => ContractState::Disputed,
-            "REFUNDED" => ContractState::Refunded,
-            _          => ContractState::Pending,
+            "PENDING"  => Ok(ContractState::Pending),
+            "LOCKED"   => Ok(ContractState::Locked),
+            "RELEASED" => Ok(ContractState::Released),
+            "DISPUTED" => Ok(ContractState::Disputed),
+            "REFUNDED" => Ok(ContractState::Refunded),
+            other      => Err(format!("Estado de contrato desconhecido: {}", other)),
         }
     }
 }
@@ -45,8 +89,9 @@ pub struct Contract {
     pub arbiter_pubkey:   String,
     pub amount_sats:      i64,
     pub fee_sats:         i64,
-    pub item_hash:        String,   // SHA-256 da descrição do produto
-    pub state:            String,   // ContractState serializado
+    pub item_hash:        String,   // SHA-256 da descrição + imagens
+    pub revision_id:      Option<String>,   // revisão do produto comprometida (NULL = legado)
+    pub state:            ContractState,
     pub created_at_block: i64,
     pub expires_at_block: i64,
     pub lock_tx_id:       Option<String>,
@@ -55,17 +100,24 @@ pub struct Contract {
     pub updated_at:       String,
 }
 
+// ─── Termos do escrow (já calculados) ────────────────────────
+#[derive(Debug, Clone)]
+pub struct EscrowTerms {
+    pub amount_sats:      i64,
+    pub fee_sats:         i64,
+    pub item_hash:        String,           // da revisão comprometida
+    pub revision_id:      Option<String>,
+    pub created_at_block: i64,
+    pub expires_at_block: i64,
+}
+
 impl Contract {
     pub fn new(
-        product_id:       String,
-        buyer_pubkey:     String,
-        seller_pubkey:    String,
-        arbiter_pubkey:   String,
-        amount_sats:      i64,
-        fee_sats:         i64,
-        item_hash:        String,
-        created_at_block: i64,
-        expires_at_block: i64,
+        product_id:     String,
+        buyer_pubkey:   String,
+        seller_pubkey:  String,
+        arbiter_pubkey: String,
+        terms:          EscrowTerms,
     ) -> Self {
         let now = Utc::now().to_rfc3339();
         Self {
@@ -75,12 +127,13 @@ impl Contract {
             buyer_pubkey,
             seller_pubkey,
             arbiter_pubkey,
-            amount_sats,
-            fee_sats,
-            item_hash,
-            state:            ContractState::Pending.as_str().into(),
-            created_at_block,
-            expires_at_block,
+            amount_sats:      terms.amount_sats,
+            fee_sats:         terms.fee_sats,
+            item_hash:        terms.item_hash,
+            revision_id:      terms.revision_id,
+            state:            ContractState::Pending,
+            created_at_block: terms.created_at_block,
+            expires_at_block: terms.expires_at_block,
             lock_tx_id:       None,
             release_tx_id:    None,
             created_at:       now.clone(),
@@ -154,6 +207,12 @@ pub struct CreateEscrowRequest {
     pub amount_sats:     i64,
 }
 
+/// Travar fundos do escrow
+#[derive(Debug, Deserialize)]
+pub struct FundContractRequest {
+    pub lock_tx_id:      String,   // TX confirmada do comprador para o endereço do escrow
+}
+
 /// Assinar contrato
 #[derive(Debug, Deserialize)]
 pub struct SignContractRequest {
@@ -166,6 +225,61 @@ pub struct DisputeRequest {
     pub reason:          String,
 }
 
+/// Papel do usuário em um contrato
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractRole {
+    Buyer,
+    Seller,
+    Arbiter,
+}
+
+impl ContractRole {
+    /// Coluna de `contracts` que guarda a pubkey deste papel
+    pub fn pubkey_column(&self) -> &'static str {
+        match self {
+            ContractRole::Buyer   => "buyer_pubkey",
+            ContractRole::Seller  => "seller_pubkey",
+            ContractRole::Arbiter => "arbiter_pubkey",
+        }
+    }
+}
+
+/// Filtros da listagem de contratos do usuário
+#[derive(Debug, Deserialize)]
+pub struct ContractFilters {
+    pub role:   Option<ContractRole>,
+    pub state:  Option<ContractState>,
+    pub page:   Option<i64>,
+    pub limit:  Option<i64>,
+}
+
+/// Parâmetros do resumo de contratos do usuário
+#[derive(Debug, Deserialize)]
+pub struct ContractSummaryQuery {
+    pub within_blocks: Option<i64>,   // janela de expiração (padrão: 10 blocos)
+}
+
+/// Listagem paginada de contratos
+#[derive(Debug, Serialize)]
+pub struct ContractListResponse {
+    pub contracts: Vec<Contract>,
+    pub total:     i64,
+    pub page:      i64,
+}
+
+/// Resumo dos contratos de um usuário (dashboard)
+#[derive(Debug, Serialize)]
+pub struct ContractSummary {
+    pub as_buyer:           i64,
+    pub as_seller:          i64,
+    pub as_arbiter:         i64,
+    pub locked_sats:        i64,            // valor travado em LOCKED/DISPUTED
+    pub pending_signatures: i64,            // contratos aguardando a assinatura do usuário
+    pub within_blocks:      i64,
+    pub expiring:           Vec<Contract>,  // PENDING/LOCKED que expiram na janela
+}
+
 /// Resposta completa do contrato
 #[derive(Debug, Serialize)]
 pub struct ContractResponse {
@@ -174,4 +288,53 @@ pub struct ContractResponse {
     pub events:      Vec<ContractEvent>,
     pub signed_by:   Vec<String>,   // roles que já assinaram
     pub can_release: bool,          // true se tiver 2/3 assinaturas
-}
\ No newline at end of file
+}
+
+/// Item exatamente como estava quando o escrow foi aberto
+#[derive(Debug, Serialize)]
+pub struct ContractItemResponse {
+    pub contract_id:     String,
+    pub item_hash:       String,            // comprometido no contrato
+    pub revision:        ProductRevision,
+    pub images:          Vec<String>,       // SHA-256 (GET /api/media/:sha256)
+    pub hash_matches:    bool,              // conteúdo da revisão refaz o item_hash
+    pub latest_revision: i64,               // revisão atual do anúncio
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ContractState::*;
+
+    const ALL: [ContractState; 5] = [Pending, Locked, Released, Disputed, Refunded];
+
+    #[test]
+    fn test_pending_cannot_be_released() {
+        assert!(!Pending.can_transition_to(Released));
+        assert!(!Pending.can_transition_to(Disputed));
+        assert!(Pending.can_transition_to(Locked));
+    }
+
+    #[test]
+    fn test_final_states_have_no_transitions() {
+        for next in ALL {
+            assert!(!Released.can_transition_to(next));
+            assert!(!Refunded.can_transition_to(next));
+        }
+    }
+
+    #[test]
+    fn test_dispute_only_from_locked() {
+        for from in ALL {
+            assert_eq!(from.can_transition_to(Disputed), from == Locked);
+        }
+    }
+
+    #[test]
+    fn test_from_str_rejects_unknown() {
+        for state in ALL {
+            assert_eq!(state.as_str().parse::<ContractState>(), Ok(state));
+        }
+        assert!("pending".parse::<ContractState>().is_err());
+        assert!("".parse::<ContractState>().is_err());
+    }
+}
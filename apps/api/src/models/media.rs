@@ -0,0 +1,151 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::blockchain::pow::sha256_hex;
+
+// ─── Tipo de mídia ───────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum MediaKind {
+    Image,        // PNG, JPEG, WebP, GIF — entra no item_hash
+    Attachment,   // PDF ou texto (manual, nota fiscal...)
+}
+
+impl MediaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaKind::Image      => "IMAGE",
+            MediaKind::Attachment => "ATTACHMENT",
+        }
+    }
+}
+
+impl std::fmt::Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Identifica o tipo pelos primeiros bytes (o Content-Type do
+/// cliente é ignorado). `None` = formato não aceito.
+pub fn detect_media(bytes: &[u8]) -> Option<(MediaKind, &'static str)> {
+    let starts = |magic: &[u8]| bytes.starts_with(magic);
+
+    if starts(b"\x89PNG\r\n\x1a\n") {
+        Some((MediaKind::Image, "image/png"))
+    } else if starts(b"\xff\xd8\xff") {
+        Some((MediaKind::Image, "image/jpeg"))
+    } else if starts(b"GIF87a") || starts(b"GIF89a") {
+        Some((MediaKind::Image, "image/gif"))
+    } else if bytes.len() >= 12 && starts(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some((MediaKind::Image, "image/webp"))
+    } else if starts(b"%PDF-") {
+        Some((MediaKind::Attachment, "application/pdf"))
+    } else if is_plain_text(bytes) {
+        Some((MediaKind::Attachment, "text/plain; charset=utf-8"))
+    } else {
+        None
+    }
+}
+
+fn is_plain_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => !text.is_empty()
+            && text.chars().all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t')),
+        Err(_) => false,
+    }
+}
+
+/// Hash do item comprometido no escrow: título + descrição e, se
+/// houver, os SHA-256 das imagens na ordem de exibição. Produtos
+/// sem imagem mantêm o hash antigo (só texto).
+pub fn item_hash(title: &str, description: &str, image_hashes: &[String]) -> String {
+    if image_hashes.is_empty() {
+        return sha256_hex(&format!("{}{}", title, description));
+    }
+
+    sha256_hex(&format!("{}{}:{}", title, description, image_hashes.join(",")))
+}
+
+// ─── Imagem ou anexo de um produto ───────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ProductMedia {
+    pub id:           String,
+    pub product_id:   String,
+    pub kind:         MediaKind,
+    pub sha256:       String,           // chave no armazenamento (GET /api/media/:sha256)
+    pub mime_type:    String,
+    pub size_bytes:   i64,
+    pub filename:     Option<String>,
+    pub width:        Option<i64>,
+    pub height:       Option<i64>,
+    pub thumb_sha256: Option<String>,   // miniatura PNG
+    pub position:     i64,              // 0 = capa
+    pub created_at:   String,
+}
+
+impl ProductMedia {
+    pub fn new(
+        product_id: String,
+        kind:       MediaKind,
+        sha256:     String,
+        mime_type:  String,
+        size_bytes: i64,
+        filename:   Option<String>,
+        position:   i64,
+    ) -> Self {
+        Self {
+            id:           Uuid::new_v4().to_string(),
+            product_id,
+            kind,
+            sha256,
+            mime_type,
+            size_bytes,
+            filename,
+            width:        None,
+            height:       None,
+            thumb_sha256: None,
+            position,
+            created_at:   Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Mídia de um produto, separada por tipo
+#[derive(Debug, Serialize)]
+pub struct ProductMediaResponse {
+    pub images:      Vec<ProductMedia>,
+    pub attachments: Vec<ProductMedia>,
+    pub item_hash:   String,   // hash que um escrow aberto agora comprometeria
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_media() {
+        assert_eq!(detect_media(b"\x89PNG\r\n\x1a\n...."), Some((MediaKind::Image, "image/png")));
+        assert_eq!(detect_media(b"\xff\xd8\xff\xe0"), Some((MediaKind::Image, "image/jpeg")));
+        assert_eq!(detect_media(b"RIFF\0\0\0\0WEBPVP8 "), Some((MediaKind::Image, "image/webp")));
+        assert_eq!(detect_media(b"%PDF-1.7"), Some((MediaKind::Attachment, "application/pdf")));
+        assert_eq!(detect_media(b"manual\n"), Some((MediaKind::Attachment, "text/plain; charset=utf-8")));
+        assert_eq!(detect_media(b"MZ\x90\0\x03\0"), None);
+        assert_eq!(detect_media(b""), None);
+    }
+
+    #[test]
+    fn test_item_hash_commits_to_images() {
+        let text_only = item_hash("Câmera", "Analógica", &[]);
+        assert_eq!(text_only, sha256_hex("CâmeraAnalógica"));
+
+        let a = item_hash("Câmera", "Analógica", &["aa".into(), "bb".into()]);
+        let b = item_hash("Câmera", "Analógica", &["bb".into(), "aa".into()]);
+        assert_ne!(a, text_only);
+        assert_ne!(a, b);
+    }
+}
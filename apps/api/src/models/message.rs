@@ -0,0 +1,177 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Limite do texto cifrado: 16 KiB de payload (32 KiB em hex)
+const MAX_CIPHERTEXT_HEX: usize = 32 * 1024;
+// Nonce de 12 bytes (AES-256-GCM / ChaCha20-Poly1305)
+const NONCE_HEX_LEN: usize = 24;
+
+// ─── Conversa ────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct MessageThread {
+    pub id:          String,
+    pub product_id:  String,
+    pub contract_id: Option<String>,   // NULL = conversa do anúncio
+    pub buyer_id:    String,
+    pub seller_id:   String,
+    pub created_at:  String,
+    pub updated_at:  String,
+}
+
+impl MessageThread {
+    pub fn new(
+        product_id:  String,
+        contract_id: Option<String>,
+        buyer_id:    String,
+        seller_id:   String,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:          Uuid::new_v4().to_string(),
+            product_id,
+            contract_id,
+            buyer_id,
+            seller_id,
+            created_at:  now.clone(),
+            updated_at:  now,
+        }
+    }
+
+    /// Outra parte da conversa, ou `None` se `user_id` não participa
+    pub fn counterparty(&self, user_id: &str) -> Option<&str> {
+        if user_id == self.buyer_id {
+            Some(&self.seller_id)
+        } else if user_id == self.seller_id {
+            Some(&self.buyer_id)
+        } else {
+            None
+        }
+    }
+}
+
+// ─── Mensagem cifrada ────────────────────────────────────────
+// O cliente deriva a chave com ECDH(secp256k1) entre a própria chave
+// privada e a pubkey da carteira da outra parte (SHA-256 do ponto
+// comprimido) e cifra com AEAD. O servidor só vê o texto cifrado.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Message {
+    pub id:            String,
+    pub thread_id:     String,
+    pub sender_id:     String,
+    pub recipient_id:  String,
+    pub sender_pubkey: String,           // pubkey que o destinatário usa no ECDH
+    pub ciphertext:    String,           // hex
+    pub nonce:         String,           // hex, 12 bytes
+    pub read_at:       Option<String>,
+    pub created_at:    String,
+}
+
+impl Message {
+    pub fn new(
+        thread_id:     String,
+        sender_id:     String,
+        recipient_id:  String,
+        sender_pubkey: String,
+        ciphertext:    String,
+        nonce:         String,
+    ) -> Self {
+        Self {
+            id:            Uuid::new_v4().to_string(),
+            thread_id,
+            sender_id,
+            recipient_id,
+            sender_pubkey,
+            ciphertext,
+            nonce,
+            read_at:       None,
+            created_at:    Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Valida o formato do payload cifrado (o conteúdo é opaco ao servidor)
+pub fn validate_payload(ciphertext: &str, nonce: &str) -> Result<(), String> {
+    let is_hex = |s: &str| s.chars().all(|c| c.is_ascii_hexdigit());
+
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(2) || !is_hex(ciphertext) {
+        return Err("ciphertext deve ser hex".into());
+    }
+    if ciphertext.len() > MAX_CIPHERTEXT_HEX {
+        return Err("Mensagem excede 16 KiB".into());
+    }
+    if nonce.len() != NONCE_HEX_LEN || !is_hex(nonce) {
+        return Err("nonce deve ter 12 bytes em hex".into());
+    }
+
+    Ok(())
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Abrir (ou reabrir) conversa — informar produto OU contrato
+#[derive(Debug, Deserialize)]
+pub struct OpenThreadRequest {
+    pub product_id:  Option<String>,
+    pub contract_id: Option<String>,
+}
+
+/// Enviar mensagem cifrada
+#[derive(Debug, Deserialize)]
+pub struct SendMessageRequest {
+    pub ciphertext: String,
+    pub nonce:      String,
+}
+
+/// Paginação das mensagens de uma conversa
+#[derive(Debug, Deserialize)]
+pub struct MessageFilters {
+    pub page:  Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Conversa na caixa de entrada
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ThreadSummary {
+    #[sqlx(flatten)]
+    pub thread:               MessageThread,
+    pub counterparty_address: String,
+    pub counterparty_pubkey:  String,   // chave para o ECDH
+    pub unread:               i64,
+}
+
+/// Conversa com mensagens
+#[derive(Debug, Serialize)]
+pub struct ThreadResponse {
+    pub thread:              MessageThread,
+    pub counterparty_pubkey: String,
+    pub messages:            Vec<Message>,
+    pub total:               i64,
+    pub page:                i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_payload() {
+        let nonce = "00".repeat(12);
+
+        assert!(validate_payload("deadbeef", &nonce).is_ok());
+        assert!(validate_payload("", &nonce).is_err());
+        assert!(validate_payload("abc", &nonce).is_err());
+        assert!(validate_payload("zz", &nonce).is_err());
+        assert!(validate_payload("deadbeef", "00").is_err());
+        assert!(validate_payload(&"a".repeat(MAX_CIPHERTEXT_HEX + 2), &nonce).is_err());
+    }
+
+    #[test]
+    fn test_counterparty() {
+        let thread = MessageThread::new("p".into(), None, "buyer".into(), "seller".into());
+
+        assert_eq!(thread.counterparty("buyer"), Some("seller"));
+        assert_eq!(thread.counterparty("seller"), Some("buyer"));
+        assert_eq!(thread.counterparty("other"), None);
+    }
+}
@@ -2,4 +2,22 @@ pub mod user;
 pub mod product;
 pub mod transaction;
 pub mod block;
-pub mod contract;
\ No newline at end of file
+pub mod contract;
+pub mod order;
+pub mod auction;
+pub mod offer;
+pub mod reputation;
+pub mod message;
+pub mod media;
+pub mod category;
+pub mod watchlist;
+pub mod notification;
+pub mod seller;
+pub mod moderation;
+pub mod shipment;
+pub mod role;
+pub mod session;
+pub mod challenge;
+pub mod security;
+pub mod two_factor;
+pub mod rate_limit;
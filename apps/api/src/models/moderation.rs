@@ -0,0 +1,337 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Alvo de denúncia / ação ─────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum ModerationTarget {
+    Product,   // anúncio
+    Review,    // avaliação
+    User,      // conta (advertência e suspensão)
+    Term,      // termo proibido
+    Report,    // denúncia arquivada
+}
+
+impl ModerationTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationTarget::Product => "PRODUCT",
+            ModerationTarget::Review  => "REVIEW",
+            ModerationTarget::User    => "USER",
+            ModerationTarget::Term    => "TERM",
+            ModerationTarget::Report  => "REPORT",
+        }
+    }
+
+    /// Usuários só denunciam conteúdo
+    pub fn is_reportable(&self) -> bool {
+        matches!(self, ModerationTarget::Product | ModerationTarget::Review)
+    }
+}
+
+impl std::fmt::Display for ModerationTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// ─── Motivo da denúncia ──────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum ReportReason {
+    Spam,
+    Fraud,        // golpe, item falso
+    Prohibited,   // item proibido
+    Offensive,
+    Other,
+}
+
+// ─── Status da denúncia ──────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum ReportStatus {
+    Open,        // na fila
+    Resolved,    // uma ação foi aplicada ao alvo
+    Dismissed,   // arquivada sem ação
+}
+
+// ─── Ação de moderação ───────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ModerationActionKind {
+    Hide,        // anúncio → 'hidden' / avaliação oculta
+    Remove,      // anúncio → 'removed' / avaliação apagada
+    Warn,        // advertência ao dono do conteúdo
+    Suspend,     // suspende o vendedor e oculta seus anúncios ativos
+    Restore,     // desfaz HIDE ou SUSPEND
+    BanTerm,     // registrado pelas rotas de termos
+    UnbanTerm,
+    Dismiss,     // denúncia arquivada
+}
+
+impl ModerationActionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationActionKind::Hide      => "HIDE",
+            ModerationActionKind::Remove    => "REMOVE",
+            ModerationActionKind::Warn      => "WARN",
+            ModerationActionKind::Suspend   => "SUSPEND",
+            ModerationActionKind::Restore   => "RESTORE",
+            ModerationActionKind::BanTerm   => "BAN_TERM",
+            ModerationActionKind::UnbanTerm => "UNBAN_TERM",
+            ModerationActionKind::Dismiss   => "DISMISS",
+        }
+    }
+
+    /// Combinações aceitas em POST /moderation/actions.
+    /// WARN e SUSPEND sobre conteúdo atingem o dono dele.
+    pub fn applies_to(&self, target: ModerationTarget) -> bool {
+        use ModerationActionKind::*;
+        use ModerationTarget::*;
+        matches!(
+            (self, target),
+            (Hide, Product) | (Hide, Review)
+                | (Remove, Product) | (Remove, Review)
+                | (Warn, Product) | (Warn, Review) | (Warn, User)
+                | (Suspend, Product) | (Suspend, Review) | (Suspend, User)
+                | (Restore, Product) | (Restore, Review) | (Restore, User)
+        )
+    }
+}
+
+impl std::fmt::Display for ModerationActionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// ─── Denúncia ────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Report {
+    pub id:          String,
+    pub reporter_id: String,
+    pub target_type: ModerationTarget,
+    pub target_id:   String,
+    pub reason:      ReportReason,
+    pub details:     Option<String>,
+    pub status:      ReportStatus,
+    pub action_id:   Option<String>,   // ação que resolveu
+    pub created_at:  String,
+    pub updated_at:  String,
+}
+
+impl Report {
+    pub fn new(
+        reporter_id: String,
+        target_type: ModerationTarget,
+        target_id:   String,
+        reason:      ReportReason,
+        details:     Option<String>,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:          Uuid::new_v4().to_string(),
+            reporter_id,
+            target_type,
+            target_id,
+            reason,
+            details,
+            status:      ReportStatus::Open,
+            action_id:   None,
+            created_at:  now.clone(),
+            updated_at:  now,
+        }
+    }
+}
+
+// ─── Registro de auditoria ───────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ModerationAction {
+    pub id:                String,
+    pub moderator_address: String,
+    pub action:            ModerationActionKind,
+    pub target_type:       ModerationTarget,
+    pub target_id:         String,
+    pub target_user_id:    Option<String>,
+    pub reason:            String,
+    pub created_at:        String,
+}
+
+impl ModerationAction {
+    pub fn new(
+        moderator_address: String,
+        action:            ModerationActionKind,
+        target_type:       ModerationTarget,
+        target_id:         String,
+        target_user_id:    Option<String>,
+        reason:            String,
+    ) -> Self {
+        Self {
+            id:                Uuid::new_v4().to_string(),
+            moderator_address,
+            action,
+            target_type,
+            target_id,
+            target_user_id,
+            reason,
+            created_at:        Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── Termo proibido ──────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BannedTerm {
+    pub id:         String,
+    pub term:       String,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+impl BannedTerm {
+    pub fn new(term: String, created_by: String) -> Self {
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            term,
+            created_by,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Normaliza o termo: palavras em minúsculas separadas por um espaço
+pub fn normalize_term(term: &str) -> String {
+    words(term).join(" ")
+}
+
+/// Primeiro termo proibido presente no texto. Casa palavras inteiras
+/// (e expressões como sequência de palavras), sem diferenciar maiúsculas.
+pub fn find_banned_term<'a>(text: &str, terms: &'a [String]) -> Option<&'a str> {
+    let haystack = words(text);
+
+    terms.iter().map(String::as_str).find(|term| {
+        let needle = words(term);
+        !needle.is_empty() && haystack.windows(needle.len()).any(|w| w == needle.as_slice())
+    })
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Denunciar anúncio ou avaliação
+#[derive(Debug, Deserialize)]
+pub struct CreateReportRequest {
+    pub target_type: ModerationTarget,
+    pub target_id:   String,
+    pub reason:      ReportReason,
+    pub details:     Option<String>,
+}
+
+/// Aplicar ação (moderador). Resolve as denúncias abertas do alvo.
+#[derive(Debug, Deserialize)]
+pub struct ModerationActionRequest {
+    pub action:      ModerationActionKind,
+    pub target_type: ModerationTarget,
+    pub target_id:   String,
+    pub reason:      String,
+}
+
+/// Arquivar denúncia sem ação
+#[derive(Debug, Deserialize)]
+pub struct DismissReportRequest {
+    pub reason: String,
+}
+
+/// Adicionar termo proibido
+#[derive(Debug, Deserialize)]
+pub struct BanTermRequest {
+    pub term: String,
+}
+
+/// Parâmetros da fila de moderação
+#[derive(Debug, Deserialize)]
+pub struct QueueFilters {
+    pub target_type: Option<ModerationTarget>,
+    pub page:        Option<i64>,
+    pub limit:       Option<i64>,
+}
+
+/// Parâmetros da trilha de auditoria
+#[derive(Debug, Deserialize)]
+pub struct AuditFilters {
+    pub target_type: Option<ModerationTarget>,
+    pub target_id:   Option<String>,
+    pub user_id:     Option<String>,
+    pub page:        Option<i64>,
+    pub limit:       Option<i64>,
+}
+
+/// Item da fila: denúncias abertas agrupadas por alvo
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct QueueItem {
+    pub target_type:       ModerationTarget,
+    pub target_id:         String,
+    pub open_reports:      i64,
+    pub reasons:           String,   // motivos distintos, separados por vírgula
+    pub first_reported_at: String,
+    pub last_reported_at:  String,
+}
+
+/// Página da fila de moderação
+#[derive(Debug, Serialize)]
+pub struct QueueResponse {
+    pub items: Vec<QueueItem>,
+    pub total: i64,
+    pub page:  i64,
+}
+
+/// Página da trilha de auditoria
+#[derive(Debug, Serialize)]
+pub struct AuditResponse {
+    pub actions: Vec<ModerationAction>,
+    pub total:   i64,
+    pub page:    i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_banned_term() {
+        let terms = vec!["réplica".to_string(), "arma de fogo".to_string()];
+
+        assert_eq!(find_banned_term("Tênis RÉPLICA primeira linha", &terms), Some("réplica"));
+        assert_eq!(find_banned_term("Vendo arma  de-fogo antiga", &terms), Some("arma de fogo"));
+        assert_eq!(find_banned_term("Replicante de brinquedo", &terms), None);
+        assert_eq!(find_banned_term("Arma de pressão", &terms), None);
+    }
+
+    #[test]
+    fn test_normalize_term() {
+        assert_eq!(normalize_term("  Arma   de FOGO "), "arma de fogo");
+        assert_eq!(normalize_term("?!"), "");
+    }
+
+    #[test]
+    fn test_action_targets() {
+        use ModerationActionKind::*;
+
+        assert!(Hide.applies_to(ModerationTarget::Review));
+        assert!(Suspend.applies_to(ModerationTarget::Product));
+        assert!(!Hide.applies_to(ModerationTarget::User));
+        assert!(!BanTerm.applies_to(ModerationTarget::Product));
+        assert!(!ModerationTarget::User.is_reportable());
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Tipo de notificação ─────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationKind {
+    NewMatch,        // produto novo casa com uma busca salva
+    PriceDrop,       // produto da watchlist ficou mais barato
+    AuctionEnding,   // leilão da watchlist perto do bloco final
+    Moderation,      // ação de moderação sobre conteúdo ou conta do usuário
+    AuctionWon,      // leilão vencido: escrow aberto, falta travar os fundos
+    OfferAccepted,   // oferta aceita: escrow aberto, falta travar os fundos
+}
+
+// ─── Notificação ─────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Notification {
+    pub id:         String,
+    pub user_id:    String,
+    pub kind:       NotificationKind,
+    pub title:      String,
+    pub body:       String,
+    pub product_id: Option<String>,
+    pub auction_id: Option<String>,
+    pub search_id:  Option<String>,
+    pub read_at:    Option<String>,   // NULL = não lida
+    pub created_at: String,
+}
+
+impl Notification {
+    pub fn new(
+        user_id:    String,
+        kind:       NotificationKind,
+        title:      String,
+        body:       String,
+        product_id: Option<String>,
+        auction_id: Option<String>,
+        search_id:  Option<String>,
+    ) -> Self {
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            user_id,
+            kind,
+            title,
+            body,
+            product_id,
+            auction_id,
+            search_id,
+            read_at:    None,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Parâmetros de GET /api/notifications
+#[derive(Debug, Deserialize)]
+pub struct NotificationFilters {
+    pub unread: Option<bool>,   // só não lidas
+    pub page:   Option<i64>,
+    pub limit:  Option<i64>,
+}
+
+/// Página da caixa de notificações
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<Notification>,
+    pub total:         i64,
+    pub unread:        i64,   // não lidas na caixa inteira
+    pub page:          i64,
+}
@@ -0,0 +1,217 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::contract::Contract;
+
+// ─── Quem fez a proposta ─────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum OfferParty {
+    Buyer,
+    Seller,
+}
+
+impl OfferParty {
+    /// Parte que responde a uma proposta feita por `self`
+    pub fn counterparty(&self) -> OfferParty {
+        match self {
+            OfferParty::Buyer  => OfferParty::Seller,
+            OfferParty::Seller => OfferParty::Buyer,
+        }
+    }
+
+    /// Coluna de `offers` que guarda o usuário desta parte
+    pub fn user_column(&self) -> &'static str {
+        match self {
+            OfferParty::Buyer  => "buyer_id",
+            OfferParty::Seller => "seller_id",
+        }
+    }
+}
+
+// ─── Status da oferta ────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum OfferStatus {
+    Pending,    // aguardando resposta da outra parte
+    Accepted,   // aceita — escrow aberto com o valor da oferta
+    Rejected,   // recusada
+    Countered,  // respondida com contraproposta
+    Expired,    // caducou sem resposta
+}
+
+impl OfferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OfferStatus::Pending   => "PENDING",
+            OfferStatus::Accepted  => "ACCEPTED",
+            OfferStatus::Rejected  => "REJECTED",
+            OfferStatus::Countered => "COUNTERED",
+            OfferStatus::Expired   => "EXPIRED",
+        }
+    }
+
+    /// Só ofertas PENDING mudam de status; todos os outros são finais.
+    pub fn can_transition_to(&self, next: OfferStatus) -> bool {
+        *self == OfferStatus::Pending && next != OfferStatus::Pending
+    }
+}
+
+impl std::fmt::Display for OfferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ─── Oferta ──────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Offer {
+    pub id:               String,
+    pub product_id:       String,
+    pub buyer_id:         String,
+    pub seller_id:        String,
+    pub parent_id:        Option<String>,   // oferta que esta contraproposta responde
+    pub proposer:         OfferParty,
+    pub amount_sats:      i64,
+    pub signature:        String,           // assinatura de `offer_message` por quem propôs
+    pub status:           OfferStatus,
+    pub expires_at_block: i64,
+    pub contract_id:      Option<String>,   // escrow aberto quando aceita
+    pub created_at:       String,
+    pub updated_at:       String,
+}
+
+// ─── Proposta assinada (quem propõe, valor e compromisso) ────
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub proposer:    OfferParty,
+    pub amount_sats: i64,
+    pub signature:   String,   // assinatura de `offer_message`
+}
+
+impl Offer {
+    pub fn new(
+        product_id:       String,
+        buyer_id:         String,
+        seller_id:        String,
+        parent_id:        Option<String>,
+        proposal:         Proposal,
+        expires_at_block: i64,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:               Uuid::new_v4().to_string(),
+            product_id,
+            buyer_id,
+            seller_id,
+            parent_id,
+            proposer:         proposal.proposer,
+            amount_sats:      proposal.amount_sats,
+            signature:        proposal.signature,
+            status:           OfferStatus::Pending,
+            expires_at_block,
+            contract_id:      None,
+            created_at:       now.clone(),
+            updated_at:       now,
+        }
+    }
+
+    /// Usuário que deve responder a esta oferta
+    pub fn responder_id(&self) -> &str {
+        match self.proposer.counterparty() {
+            OfferParty::Buyer  => &self.buyer_id,
+            OfferParty::Seller => &self.seller_id,
+        }
+    }
+
+    pub fn is_expired(&self, height: i64) -> bool {
+        height >= self.expires_at_block
+    }
+}
+
+/// Mensagem que quem propõe assina para se comprometer com o valor
+pub fn offer_message(product_id: &str, amount_sats: i64) -> String {
+    format!("PAPERMARKET_OFFER:{}:{}", product_id, amount_sats)
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Fazer oferta (comprador)
+#[derive(Debug, Deserialize)]
+pub struct CreateOfferRequest {
+    pub product_id:  String,
+    pub amount_sats: i64,
+    pub signature:   String,   // assinatura de `PAPERMARKET_OFFER:{product_id}:{amount_sats}`
+}
+
+/// Contraproposta (qualquer parte, em resposta à oferta pendente)
+#[derive(Debug, Deserialize)]
+pub struct CounterOfferRequest {
+    pub amount_sats: i64,
+    pub signature:   String,
+}
+
+/// Filtros da listagem de ofertas
+#[derive(Debug, Deserialize)]
+pub struct OfferFilters {
+    pub role:   Option<OfferParty>,
+    pub status: Option<OfferStatus>,
+    pub page:   Option<i64>,
+    pub limit:  Option<i64>,
+}
+
+/// Listagem paginada de ofertas
+#[derive(Debug, Serialize)]
+pub struct OfferListResponse {
+    pub offers: Vec<Offer>,
+    pub total:  i64,
+    pub page:   i64,
+}
+
+/// Oferta com o histórico da negociação e o escrow (se aceita)
+#[derive(Debug, Serialize)]
+pub struct OfferResponse {
+    pub offer:    Offer,
+    pub thread:   Vec<Offer>,
+    pub contract: Option<Contract>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_pending_offers_change_status() {
+        use OfferStatus::*;
+
+        assert!(Pending.can_transition_to(Accepted));
+        assert!(Pending.can_transition_to(Countered));
+        assert!(Pending.can_transition_to(Expired));
+        assert!(!Pending.can_transition_to(Pending));
+        assert!(!Accepted.can_transition_to(Rejected));
+        assert!(!Countered.can_transition_to(Accepted));
+        assert!(!Expired.can_transition_to(Accepted));
+    }
+
+    #[test]
+    fn test_responder_is_counterparty() {
+        let proposal = Proposal {
+            proposer:    OfferParty::Buyer,
+            amount_sats: 900,
+            signature:   "sig".into(),
+        };
+        let mut offer = Offer::new(
+            "p".into(), "buyer".into(), "seller".into(), None, proposal, 30,
+        );
+        assert_eq!(offer.responder_id(), "seller");
+
+        offer.proposer = OfferParty::Seller;
+        assert_eq!(offer.responder_id(), "buyer");
+
+        assert!(!offer.is_expired(29));
+        assert!(offer.is_expired(30));
+    }
+}
@@ -0,0 +1,191 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::contract::Contract;
+
+// ─── Status da ordem ─────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum OrderStatus {
+    Pending,    // escrow criado, aguardando pagamento
+    Funded,     // fundos travados no escrow
+    Shipped,    // vendedor despachou o produto
+    Delivered,  // comprador confirmou o recebimento
+    Completed,  // escrow liberado ao vendedor
+    Cancelled,  // cancelada antes do pagamento
+    Refunded,   // escrow devolvido ao comprador
+}
+
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending   => "PENDING",
+            OrderStatus::Funded    => "FUNDED",
+            OrderStatus::Shipped   => "SHIPPED",
+            OrderStatus::Delivered => "DELIVERED",
+            OrderStatus::Completed => "COMPLETED",
+            OrderStatus::Cancelled => "CANCELLED",
+            OrderStatus::Refunded  => "REFUNDED",
+        }
+    }
+
+    /// Transições válidas da ordem:
+    ///
+    /// ```text
+    /// PENDING   → FUNDED | CANCELLED
+    /// FUNDED    → SHIPPED | COMPLETED | REFUNDED
+    /// SHIPPED   → DELIVERED | COMPLETED | REFUNDED
+    /// DELIVERED → COMPLETED | REFUNDED
+    /// ```
+    ///
+    /// COMPLETED e REFUNDED acompanham o escrow (RELEASED / REFUNDED).
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending,   Funded)
+                | (Pending,   Cancelled)
+                | (Funded,    Shipped)
+                | (Funded,    Completed)
+                | (Funded,    Refunded)
+                | (Shipped,   Delivered)
+                | (Shipped,   Completed)
+                | (Shipped,   Refunded)
+                | (Delivered, Completed)
+                | (Delivered, Refunded)
+        )
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ─── Ordem de compra ─────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Order {
+    pub id:                    String,
+    pub product_id:            String,
+    pub buyer_id:              String,
+    pub seller_id:             String,
+    pub contract_id:           String,
+    pub quantity:              i64,
+    pub unit_price_sats:       i64,      // snapshot do preço na criação
+    pub total_sats:            i64,
+    pub shipping_address_hash: String,   // SHA-256 do endereço — nunca o endereço em claro
+    pub status:                OrderStatus,
+    pub created_at:            String,
+    pub updated_at:            String,
+}
+
+impl Order {
+    pub fn new(
+        product_id:            String,
+        buyer_id:              String,
+        seller_id:             String,
+        contract_id:           String,
+        quantity:              i64,
+        unit_price_sats:       i64,
+        shipping_address_hash: String,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:                    Uuid::new_v4().to_string(),
+            product_id,
+            buyer_id,
+            seller_id,
+            contract_id,
+            quantity,
+            unit_price_sats,
+            total_sats:            quantity * unit_price_sats,
+            shipping_address_hash,
+            status:                OrderStatus::Pending,
+            created_at:            now.clone(),
+            updated_at:            now,
+        }
+    }
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Criar ordem de compra
+#[derive(Debug, Deserialize)]
+pub struct CreateOrderRequest {
+    pub product_id:            String,
+    pub quantity:              i64,
+    pub shipping_address_hash: String,
+}
+
+/// Pagar ordem (travar fundos no escrow)
+#[derive(Debug, Deserialize)]
+pub struct FundOrderRequest {
+    pub lock_tx_id: String,   // TX do comprador que financia o escrow
+}
+
+/// Papel do usuário na ordem
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderRole {
+    Buyer,
+    Seller,
+}
+
+impl OrderRole {
+    /// Coluna de `orders` que guarda o usuário deste papel
+    pub fn user_column(&self) -> &'static str {
+        match self {
+            OrderRole::Buyer  => "buyer_id",
+            OrderRole::Seller => "seller_id",
+        }
+    }
+}
+
+/// Filtros da listagem de ordens
+#[derive(Debug, Deserialize)]
+pub struct OrderFilters {
+    pub role:   Option<OrderRole>,
+    pub status: Option<OrderStatus>,
+    pub page:   Option<i64>,
+    pub limit:  Option<i64>,
+}
+
+/// Listagem paginada de ordens
+#[derive(Debug, Serialize)]
+pub struct OrderListResponse {
+    pub orders: Vec<Order>,
+    pub total:  i64,
+    pub page:   i64,
+}
+
+/// Ordem com o contrato escrow vinculado
+#[derive(Debug, Serialize)]
+pub struct OrderResponse {
+    pub order:    Order,
+    pub contract: Contract,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use OrderStatus::*;
+
+    #[test]
+    fn test_happy_path() {
+        assert!(Pending.can_transition_to(Funded));
+        assert!(Funded.can_transition_to(Shipped));
+        assert!(Shipped.can_transition_to(Delivered));
+        assert!(Delivered.can_transition_to(Completed));
+    }
+
+    #[test]
+    fn test_cannot_ship_unfunded_order() {
+        assert!(!Pending.can_transition_to(Shipped));
+        assert!(!Pending.can_transition_to(Completed));
+        assert!(!Funded.can_transition_to(Cancelled));
+    }
+}
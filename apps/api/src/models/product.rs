@@ -2,6 +2,49 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::media::item_hash;
+
+// ─── Status do anúncio ───────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ProductStatus {
+    Active,    // listado e comprável
+    Paused,    // oculto temporariamente pelo vendedor
+    Sold,      // vendido — pode voltar a ser listado
+    Removed,   // removido (terminal)
+    Hidden,    // oculto pela moderação — só um moderador restaura
+}
+
+impl ProductStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProductStatus::Active  => "active",
+            ProductStatus::Paused  => "paused",
+            ProductStatus::Sold    => "sold",
+            ProductStatus::Removed => "removed",
+            ProductStatus::Hidden  => "hidden",
+        }
+    }
+
+    /// Transições permitidas ao vendedor
+    pub fn can_transition_to(&self, next: ProductStatus) -> bool {
+        use ProductStatus::*;
+        matches!(
+            (self, next),
+            (Active, Paused) | (Active, Sold) | (Active, Removed)
+                | (Paused, Active) | (Paused, Removed)
+                | (Sold, Active) | (Sold, Removed)
+        )
+    }
+}
+
+impl std::fmt::Display for ProductStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 // ─── Produto ─────────────────────────────────────────────────
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Product {
@@ -9,67 +52,252 @@ pub struct Product {
     pub seller_id:   String,
     pub title:       String,
     pub description: String,
-    pub price_sats:  i64,      // preço em satoshis de BPC
-    pub category:    String,
-    pub condition:   String,   // Novo | Usado
-    pub location:    String,
-    pub status:      String,   // active | sold | removed
+    pub price_sats:  i64,             // preço em satoshis de BPC
+    pub category:    String,          // slug da categoria
+    pub category_id: Option<String>,  // NULL = produto anterior à taxonomia
+    pub condition:   String,          // Novo | Usado
+    pub location:    String,          // "Cidade/UF" (exibição)
+    pub state:       Option<String>,  // UF
+    pub city:        Option<String>,
+    pub status:      ProductStatus,
+    pub stock:       i64,             // unidades em estoque
+    pub reserved:    i64,             // unidades presas em escrows abertos
+    pub version:     i64,             // controle de concorrência do estoque
     pub created_at:  String,
     pub updated_at:  String,
 }
 
+// ─── Campos do anúncio (já validados) ────────────────────────
+#[derive(Debug, Clone)]
+pub struct ProductListing {
+    pub title:       String,
+    pub description: String,
+    pub price_sats:  i64,
+    pub category_id: String,
+    pub category:    String,   // slug
+    pub condition:   String,
+    pub state:       String,   // UF
+    pub city:        String,
+    pub stock:       i64,
+}
+
 impl Product {
-    pub fn new(
-        seller_id:   String,
-        title:       String,
-        description: String,
-        price_sats:  i64,
-        category:    String,
-        condition:   String,
-        location:    String,
-    ) -> Self {
+    pub fn new(seller_id: String, listing: ProductListing) -> Self {
         let now = Utc::now().to_rfc3339();
         Self {
             id:          Uuid::new_v4().to_string(),
             seller_id,
-            title,
-            description,
-            price_sats,
-            category,
-            condition,
-            location,
-            status:      "active".into(),
+            title:       listing.title,
+            description: listing.description,
+            price_sats:  listing.price_sats,
+            category:    listing.category,
+            category_id: Some(listing.category_id),
+            condition:   listing.condition,
+            location:    format_location(&listing.state, &listing.city),
+            state:       Some(listing.state),
+            city:        Some(listing.city),
+            status:      ProductStatus::Active,
+            stock:       listing.stock,
+            reserved:    0,
+            version:     0,
             created_at:  now.clone(),
             updated_at:  now,
         }
     }
+
+    /// Unidades disponíveis para novas ordens
+    pub fn available(&self) -> i64 {
+        self.stock - self.reserved
+    }
 }
 
-// ─── Avaliação ───────────────────────────────────────────────
+// ─── Validação dos campos do anúncio ─────────────────────────
+
+/// Unidades federativas aceitas em `state`
+pub const BR_STATES: [&str; 27] = [
+    "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA",
+    "PB", "PR", "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SP", "SE", "TO",
+];
+
+pub fn validate_title(title: &str) -> Result<(), String> {
+    match title.trim().chars().count() {
+        0        => Err("Título é obrigatório".into()),
+        1..=120  => Ok(()),
+        _        => Err("Título deve ter até 120 caracteres".into()),
+    }
+}
+
+pub fn validate_description(description: &str) -> Result<(), String> {
+    if description.chars().count() > 5000 {
+        return Err("Descrição deve ter até 5000 caracteres".into());
+    }
+    Ok(())
+}
+
+pub fn validate_price(price_sats: i64) -> Result<(), String> {
+    if price_sats <= 0 {
+        return Err("Preço deve ser maior que zero".into());
+    }
+    Ok(())
+}
+
+pub fn validate_condition(condition: &str) -> Result<(), String> {
+    if !["Novo", "Usado"].contains(&condition) {
+        return Err("Condição deve ser 'Novo' ou 'Usado'".into());
+    }
+    Ok(())
+}
+
+/// Normaliza e valida UF + cidade; devolve `(UF, cidade)`
+pub fn validate_location(state: &str, city: &str) -> Result<(String, String), String> {
+    let state = state.trim().to_uppercase();
+    let city  = city.trim().to_string();
+
+    if !BR_STATES.contains(&state.as_str()) {
+        return Err("Estado deve ser uma UF válida (ex: SP)".into());
+    }
+    match city.chars().count() {
+        0       => Err("Cidade é obrigatória".into()),
+        1..=80  => Ok((state, city)),
+        _       => Err("Cidade deve ter até 80 caracteres".into()),
+    }
+}
+
+/// Texto de exibição de `location`
+pub fn format_location(state: &str, city: &str) -> String {
+    format!("{}/{}", city, state)
+}
+
+// ─── Revisão imutável do anúncio ─────────────────────────────
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
-pub struct Review {
+pub struct ProductRevision {
+    pub id:           String,
+    pub product_id:   String,
+    pub revision:     i64,
+    pub title:        String,
+    pub description:  String,
+    pub price_sats:   i64,
+    pub category:     String,
+    pub condition:    String,
+    pub location:     String,
+    pub image_hashes: String,   // SHA-256 das imagens em ordem, separados por vírgula
+    pub item_hash:    String,
+    pub created_at:   String,
+}
+
+impl ProductRevision {
+    /// Fotografa o estado atual do produto (o número da revisão é
+    /// atribuído no INSERT)
+    pub fn from_product(product: &Product, image_hashes: &[String]) -> Self {
+        Self {
+            id:           Uuid::new_v4().to_string(),
+            product_id:   product.id.clone(),
+            revision:     0,
+            title:        product.title.clone(),
+            description:  product.description.clone(),
+            price_sats:   product.price_sats,
+            category:     product.category.clone(),
+            condition:    product.condition.clone(),
+            location:     product.location.clone(),
+            image_hashes: image_hashes.join(","),
+            item_hash:    item_hash(&product.title, &product.description, image_hashes),
+            created_at:   Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn images(&self) -> Vec<String> {
+        self.image_hashes
+            .split(',')
+            .filter(|h| !h.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Refaz o hash a partir do conteúdo gravado
+    pub fn computed_item_hash(&self) -> String {
+        item_hash(&self.title, &self.description, &self.images())
+    }
+
+    /// Mesmo conteúdo visível ao comprador (ignora id/número/data)
+    pub fn same_content(&self, other: &ProductRevision) -> bool {
+        self.item_hash == other.item_hash
+            && self.price_sats == other.price_sats
+            && self.category == other.category
+            && self.condition == other.condition
+            && self.location == other.location
+    }
+}
+
+// ─── Reserva de estoque ──────────────────────────────────────
+// Mantida enquanto o escrow está PENDING/LOCKED/DISPUTED. Todo escrow
+// tem a sua; `order_id` só existe quando o escrow veio de uma ordem.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Reservation {
     pub id:          String,
     pub product_id:  String,
-    pub reviewer_id: String,
-    pub rating:      i64,      // 1 a 5
-    pub comment:     Option<String>,
+    pub order_id:    Option<String>,
+    pub contract_id: String,
+    pub quantity:    i64,
+    pub status:      String,   // HELD | CONSUMED | RELEASED
     pub created_at:  String,
+    pub updated_at:  String,
+}
+
+impl Reservation {
+    pub fn new(
+        product_id:  String,
+        order_id:    Option<String>,
+        contract_id: String,
+        quantity:    i64,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:          Uuid::new_v4().to_string(),
+            product_id,
+            order_id,
+            contract_id,
+            quantity,
+            status:      "HELD".into(),
+            created_at:  now.clone(),
+            updated_at:  now,
+        }
+    }
+}
+
+// ─── Avaliação ───────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Review {
+    pub id:           String,
+    pub product_id:   String,
+    pub reviewer_id:  String,
+    pub rating:       i64,      // 1 a 5
+    pub comment:      Option<String>,
+    pub created_at:   String,
+    pub contract_id:  Option<String>,   // escrow RELEASED que originou a avaliação
+    pub verified:     bool,             // compra confirmada on-chain
+    pub seller_reply: Option<String>,
+    pub replied_at:   Option<String>,
 }
 
 impl Review {
     pub fn new(
         product_id:  String,
         reviewer_id: String,
+        contract_id: String,
         rating:      i64,
         comment:     Option<String>,
     ) -> Self {
         Self {
-            id:          Uuid::new_v4().to_string(),
+            id:           Uuid::new_v4().to_string(),
             product_id,
             reviewer_id,
             rating,
             comment,
-            created_at:  Utc::now().to_rfc3339(),
+            created_at:   Utc::now().to_rfc3339(),
+            contract_id:  Some(contract_id),
+            verified:     true,
+            seller_reply: None,
+            replied_at:   None,
         }
     }
 }
@@ -82,47 +310,274 @@ pub struct CreateProductRequest {
     pub title:       String,
     pub description: String,
     pub price_sats:  i64,
-    pub category:    String,
+    pub category:    String,        // slug de categoria ativa
     pub condition:   String,
-    pub location:    String,
+    pub state:       String,        // UF
+    pub city:        String,
+    pub stock:       Option<i64>,   // padrão: 1 unidade
 }
 
-/// Editar produto
+/// Editar produto — todos os campos são validados antes de gravar
 #[derive(Debug, Deserialize)]
 pub struct UpdateProductRequest {
     pub title:       Option<String>,
     pub description: Option<String>,
     pub price_sats:  Option<i64>,
-    pub category:    Option<String>,
+    pub category:    Option<String>,          // slug
     pub condition:   Option<String>,
-    pub location:    Option<String>,
-    pub status:      Option<String>,
+    pub state:       Option<String>,
+    pub city:        Option<String>,
+    pub status:      Option<ProductStatus>,   // segue can_transition_to
+    pub stock:       Option<i64>,
 }
 
 /// Filtros de listagem
 #[derive(Debug, Deserialize)]
 pub struct ProductFilters {
-    pub category:   Option<String>,
+    pub category:   Option<String>,   // slug — inclui subcategorias
     pub condition:  Option<String>,
+    pub state:      Option<String>,   // UF
     pub min_price:  Option<i64>,
     pub max_price:  Option<i64>,
     pub search:     Option<String>,
-    pub page:       Option<i64>,
+    pub cursor:     Option<String>,   // `next_cursor` da página anterior
     pub limit:      Option<i64>,
 }
 
+/// Cursor opaco da listagem: posição (created_at, id) do último item
+pub fn encode_cursor(created_at: &str, id: &str) -> String {
+    hex::encode(format!("{}|{}", created_at, id))
+}
+
+pub fn decode_cursor(cursor: &str) -> Option<(String, String)> {
+    let raw = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+    let (created_at, id) = raw.split_once('|')?;
+    Some((created_at.to_string(), id.to_string()))
+}
+
 /// Criar avaliação
 #[derive(Debug, Deserialize)]
 pub struct CreateReviewRequest {
-    pub rating:  i64,
-    pub comment: Option<String>,
+    pub rating:      i64,
+    pub comment:     Option<String>,
+    pub contract_id: Option<String>,   // compra avaliada (padrão: a mais antiga sem avaliação)
 }
 
-/// Produto com média de avaliações (resposta da API)
+/// Resposta do vendedor a uma avaliação
+#[derive(Debug, Deserialize)]
+pub struct ReplyReviewRequest {
+    pub reply: String,
+}
+
+/// Paginação da listagem de avaliações
+#[derive(Debug, Deserialize)]
+pub struct ReviewFilters {
+    pub page:  Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Avaliação com o username de quem avaliou
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReviewResponse {
+    #[sqlx(flatten)]
+    pub review:   Review,
+    pub reviewer: String,
+}
+
+/// Listagem paginada de avaliações
 #[derive(Debug, Serialize)]
+pub struct ReviewListResponse {
+    pub reviews: Vec<ReviewResponse>,
+    pub total:   i64,
+    pub page:    i64,
+}
+
+/// Produto com média de avaliações (resposta da API)
+#[derive(Debug, Serialize, sqlx::FromRow)]
 pub struct ProductResponse {
+    #[sqlx(flatten)]
     pub product:       Product,
     pub seller:        String,   // username do vendedor
     pub avg_rating:    Option<f64>,
     pub review_count:  i64,
-}
\ No newline at end of file
+    pub thumbnail:     Option<String>,   // sha256 da miniatura da capa (GET /api/media/:sha256)
+}
+
+/// Página da listagem de produtos
+#[derive(Debug, Serialize)]
+pub struct ProductListResponse {
+    pub products:    Vec<ProductResponse>,
+    pub total:       i64,
+    pub next_cursor: Option<String>,   // ausente na última página
+}
+
+// ─── Busca textual ───────────────────────────────────────────
+
+/// Ordenação dos resultados da busca
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    #[default]
+    Relevance,
+    PriceAsc,
+    PriceDesc,
+    Newest,
+    Rating,
+}
+
+impl SearchSort {
+    /// Cláusula ORDER BY; sem termo de busca, relevância vira "mais recentes"
+    pub fn order_by(&self, has_query: bool) -> &'static str {
+        match self {
+            SearchSort::Relevance if has_query => "rank ASC, p.created_at DESC",
+            SearchSort::Relevance | SearchSort::Newest => "p.created_at DESC",
+            SearchSort::PriceAsc  => "p.price_sats ASC, p.created_at DESC",
+            SearchSort::PriceDesc => "p.price_sats DESC, p.created_at DESC",
+            SearchSort::Rating    => "avg_rating DESC NULLS LAST, review_count DESC, p.created_at DESC",
+        }
+    }
+}
+
+/// Monta a expressão MATCH do FTS5 a partir do texto digitado.
+/// Cada palavra vira um prefixo entre aspas (`"cafe"*`), então
+/// operadores e aspas do usuário nunca quebram a sintaxe do FTS5.
+pub fn fts_match_expr(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .take(8)
+        .map(|t| format!("\"{}\"*", t))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Parâmetros de GET /marketplace/products/search
+#[derive(Debug, Deserialize)]
+pub struct SearchFilters {
+    pub q:          Option<String>,
+    pub category:   Option<String>,   // slug — inclui subcategorias
+    pub condition:  Option<String>,
+    pub min_price:  Option<i64>,
+    pub max_price:  Option<i64>,
+    pub sort:       Option<SearchSort>,
+    pub page:       Option<i64>,
+    pub limit:      Option<i64>,
+}
+
+/// Resultado da busca com vendedor, avaliação e trecho destacado
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SearchHit {
+    #[sqlx(flatten)]
+    pub product:         Product,
+    pub seller:          String,
+    pub avg_rating:      Option<f64>,
+    pub review_count:    i64,
+    pub rank:            f64,              // bm25 — menor é mais relevante
+    pub title_highlight: Option<String>,   // título com <mark>…</mark>
+    pub snippet:         Option<String>,   // trecho da descrição com <mark>…</mark>
+}
+
+/// Contagem de resultados por valor de um filtro
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchFacets {
+    pub category:  Vec<FacetCount>,
+    pub condition: Vec<FacetCount>,
+}
+
+/// Resposta da busca
+#[derive(Debug, Serialize)]
+pub struct SearchResponse {
+    pub results: Vec<SearchHit>,
+    pub total:   i64,
+    pub page:    i64,
+    pub facets:  SearchFacets,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_match_expr_quotes_terms() {
+        assert_eq!(fts_match_expr("câmera  usada").as_deref(), Some("\"câmera\"* \"usada\"*"));
+        assert_eq!(fts_match_expr("\"drop\" OR -x*").as_deref(), Some("\"drop\"* \"OR\"* \"x\"*"));
+        assert_eq!(fts_match_expr("  ;; "), None);
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = encode_cursor("2025-01-01T00:00:00+00:00", "abc");
+        assert_eq!(
+            decode_cursor(&cursor),
+            Some(("2025-01-01T00:00:00+00:00".into(), "abc".into()))
+        );
+        assert_eq!(decode_cursor("zz"), None);
+        assert_eq!(decode_cursor(&hex::encode("sem-separador")), None);
+    }
+
+    #[test]
+    fn test_relevance_without_query_is_newest() {
+        assert_eq!(SearchSort::Relevance.order_by(false), SearchSort::Newest.order_by(false));
+        assert!(SearchSort::Relevance.order_by(true).starts_with("rank"));
+    }
+
+    #[test]
+    fn test_revision_content() {
+        let product = Product::new("seller".into(), ProductListing {
+            title:       "Câmera".into(),
+            description: "Analógica".into(),
+            price_sats:  1000,
+            category_id: "cat-foto".into(),
+            category:    "foto".into(),
+            condition:   "Usado".into(),
+            state:       "SP".into(),
+            city:        "Santos".into(),
+            stock:       1,
+        });
+        let hashes = vec!["aa".to_string(), "bb".to_string()];
+
+        let rev = ProductRevision::from_product(&product, &hashes);
+        assert_eq!(rev.images(), hashes);
+        assert_eq!(rev.computed_item_hash(), rev.item_hash);
+        assert!(ProductRevision::from_product(&product, &[]).images().is_empty());
+
+        let mut edited = ProductRevision::from_product(&product, &hashes);
+        assert!(rev.same_content(&edited));
+        edited.price_sats = 900;
+        assert!(!rev.same_content(&edited));
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        use ProductStatus::*;
+
+        assert!(Active.can_transition_to(Paused));
+        assert!(Paused.can_transition_to(Active));
+        assert!(Sold.can_transition_to(Active));
+        assert!(!Paused.can_transition_to(Sold));
+        assert!(!Removed.can_transition_to(Active));
+        assert!(!Active.can_transition_to(Active));
+        assert!(!Active.can_transition_to(Hidden));
+        assert!(!Hidden.can_transition_to(Active));
+    }
+
+    #[test]
+    fn test_validate_listing_fields() {
+        assert_eq!(validate_location(" sp ", " Santos "), Ok(("SP".into(), "Santos".into())));
+        assert!(validate_location("XX", "Santos").is_err());
+        assert!(validate_location("SP", "  ").is_err());
+
+        assert!(validate_title("  ").is_err());
+        assert!(validate_title(&"a".repeat(121)).is_err());
+        assert!(validate_price(0).is_err());
+        assert!(validate_condition("Seminovo").is_err());
+        assert!(validate_description(&"a".repeat(5001)).is_err());
+    }
+}
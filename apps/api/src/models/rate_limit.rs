@@ -0,0 +1,166 @@
+use std::env;
+use std::time::Instant;
+
+// ─── Rotas com limite próprio ────────────────────────────────
+// Cada uma lê `RATE_LIMIT_<NOME>` no formato `<requisições>/<segundos>`
+// (ex.: `10/60`); `off` desliga o limite da rota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitedRoute {
+    Login,          // /auth/login — Argon2 a cada tentativa
+    Register,       // /auth/register
+    Auth,           // challenge, verify, recover, refresh
+    MiningSubmit,   // /mining/submit
+    WebSocket,      // /ws
+    TwoFactor,      // rotas que conferem código TOTP (/auth/2fa/*, /wallet/send, assinatura de contrato)
+}
+
+impl LimitedRoute {
+    pub const ALL: [LimitedRoute; 6] = [
+        LimitedRoute::Login,
+        LimitedRoute::Register,
+        LimitedRoute::Auth,
+        LimitedRoute::MiningSubmit,
+        LimitedRoute::WebSocket,
+        LimitedRoute::TwoFactor,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitedRoute::Login        => "login",
+            LimitedRoute::Register     => "register",
+            LimitedRoute::Auth         => "auth",
+            LimitedRoute::MiningSubmit => "mining_submit",
+            LimitedRoute::WebSocket    => "ws",
+            LimitedRoute::TwoFactor    => "two_factor",
+        }
+    }
+
+    fn env_key(&self) -> String {
+        format!("RATE_LIMIT_{}", self.as_str().to_uppercase())
+    }
+
+    fn default_policy(&self) -> RateLimitPolicy {
+        match self {
+            LimitedRoute::Login        => RateLimitPolicy::new(10, 60),
+            LimitedRoute::Register     => RateLimitPolicy::new(5, 3600),
+            LimitedRoute::Auth         => RateLimitPolicy::new(30, 60),
+            LimitedRoute::MiningSubmit => RateLimitPolicy::new(60, 60),
+            LimitedRoute::WebSocket    => RateLimitPolicy::new(10, 60),
+            LimitedRoute::TwoFactor    => RateLimitPolicy::new(10, 60),
+        }
+    }
+
+    /// Política da rota (env ou padrão); `None` = sem limite
+    pub fn policy(&self) -> Option<RateLimitPolicy> {
+        match env::var(self.env_key()) {
+            Ok(value) if value.trim().eq_ignore_ascii_case("off") => None,
+            Ok(value) => Some(RateLimitPolicy::parse(&value).unwrap_or_else(|| {
+                tracing::warn!("{} inválido ({}), usando o padrão", self.env_key(), value);
+                self.default_policy()
+            })),
+            Err(_) => Some(self.default_policy()),
+        }
+    }
+}
+
+// ─── Política: capacidade e período para reencher tudo ───────
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitPolicy {
+    pub capacity:    u32,   // rajada máxima
+    pub period_secs: u64,   // tempo para o balde voltar a encher
+}
+
+impl RateLimitPolicy {
+    pub fn new(capacity: u32, period_secs: u64) -> Self {
+        Self { capacity, period_secs }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let (capacity, period) = text.trim().split_once('/')?;
+        let capacity: u32 = capacity.trim().parse().ok()?;
+        let period_secs: u64 = period.trim().parse().ok()?;
+
+        (capacity > 0 && period_secs > 0).then(|| Self::new(capacity, period_secs))
+    }
+
+    /// Fichas recuperadas por segundo
+    fn refill_rate(&self) -> f64 {
+        self.capacity as f64 / self.period_secs as f64
+    }
+}
+
+// ─── Balde de fichas ─────────────────────────────────────────
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    tokens:      f64,
+    updated_at:  Instant,
+}
+
+impl TokenBucket {
+    /// Balde novo começa cheio
+    pub fn new(policy: &RateLimitPolicy, now: Instant) -> Self {
+        Self {
+            tokens:     policy.capacity as f64,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, policy: &RateLimitPolicy, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * policy.refill_rate()).min(policy.capacity as f64);
+        self.updated_at = now;
+    }
+
+    /// Gasta uma ficha; sem fichas devolve os segundos até a próxima
+    pub fn try_take(&mut self, policy: &RateLimitPolicy, now: Instant) -> Result<(), i64> {
+        self.refill(policy, now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - self.tokens) / policy.refill_rate()).ceil().max(1.0) as i64)
+        }
+    }
+
+    /// Cheio de novo — pode sair da memória sem mudar o resultado
+    pub fn is_full(&self, policy: &RateLimitPolicy, now: Instant) -> bool {
+        let mut bucket = self.clone();
+        bucket.refill(policy, now);
+        bucket.tokens >= policy.capacity as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_policy() {
+        assert_eq!(RateLimitPolicy::parse("10/60"), Some(RateLimitPolicy::new(10, 60)));
+        assert_eq!(RateLimitPolicy::parse(" 5 / 3600 "), Some(RateLimitPolicy::new(5, 3600)));
+        assert_eq!(RateLimitPolicy::parse("0/60"), None);
+        assert_eq!(RateLimitPolicy::parse("10"), None);
+    }
+
+    #[test]
+    fn test_bucket_burst_and_refill() {
+        let policy = RateLimitPolicy::new(3, 30);   // 1 ficha a cada 10 s
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&policy, start);
+
+        assert!(bucket.try_take(&policy, start).is_ok());
+        assert!(bucket.try_take(&policy, start).is_ok());
+        assert!(bucket.try_take(&policy, start).is_ok());
+        assert_eq!(bucket.try_take(&policy, start), Err(10));
+
+        let later = start + Duration::from_secs(4);
+        assert_eq!(bucket.try_take(&policy, later), Err(6));
+
+        let refilled = start + Duration::from_secs(10);
+        assert!(bucket.try_take(&policy, refilled).is_ok());
+        assert!(!bucket.is_full(&policy, refilled));
+        assert!(bucket.is_full(&policy, refilled + Duration::from_secs(30)));
+    }
+}
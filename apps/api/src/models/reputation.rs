@@ -0,0 +1,181 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::blockchain::pow::sha256_hex;
+
+// ─── Componentes da reputação de um vendedor ─────────────────
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ReputationEntry {
+    pub address:          String,
+    pub completed:        i64,           // escrows liberados sem disputa
+    pub refunds:          i64,           // escrows pagos e reembolsados sem disputa
+    pub disputes_won:     i64,           // disputa resolvida a favor do vendedor
+    pub disputes_lost:    i64,           // disputa resolvida com reembolso
+    pub verified_reviews: i64,
+    pub avg_rating:       Option<f64>,   // média das avaliações verificadas
+}
+
+impl ReputationEntry {
+    /// Pontuação de 0 a 1000, partindo de 500 (neutro):
+    ///
+    /// ```text
+    /// +10 por venda concluída (até +300)
+    /// +20 por disputa ganha, −50 por disputa perdida
+    /// −15 por reembolso
+    /// ±100 pela média das avaliações verificadas (peso cheio com 10+)
+    /// ```
+    pub fn score(&self) -> i64 {
+        let mut score = 500;
+
+        score += (self.completed * 10).min(300);
+        score += self.disputes_won * 20;
+        score -= self.disputes_lost * 50;
+        score -= self.refunds * 15;
+
+        if let Some(avg) = self.avg_rating {
+            let weight = self.verified_reviews.min(10) as f64 / 10.0;
+            score += ((avg - 3.0) * 50.0 * weight).round() as i64;
+        }
+
+        score.clamp(0, 1000)
+    }
+
+    /// Folha da árvore: hash de todos os componentes. A média entra
+    /// em centésimos para não depender da formatação de ponto flutuante.
+    pub fn leaf_hash(&self) -> String {
+        let avg_centis = self.avg_rating.map(|avg| (avg * 100.0).round() as i64).unwrap_or(0);
+
+        sha256_hex(&format!(
+            "{}|{}|{}|{}|{}|{}|{}|{}",
+            self.address,
+            self.score(),
+            self.completed,
+            self.refunds,
+            self.disputes_won,
+            self.disputes_lost,
+            self.verified_reviews,
+            avg_centis,
+        ))
+    }
+}
+
+// ─── Fotografia ancorada em bloco ────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReputationSnapshot {
+    pub id:           String,
+    pub height:       i64,              // altura em que foi calculada
+    pub root:         String,           // merkle root das folhas
+    pub leaf_count:   i64,
+    pub block_hash:   Option<String>,   // bloco que ancorou a root
+    pub block_height: Option<i64>,
+    pub created_at:   String,
+}
+
+impl ReputationSnapshot {
+    pub fn new(height: i64, root: String, leaf_count: i64) -> Self {
+        Self {
+            id:           Uuid::new_v4().to_string(),
+            height,
+            root,
+            leaf_count,
+            block_hash:   None,
+            block_height: None,
+            created_at:   Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── Folha gravada na fotografia ─────────────────────────────
+#[derive(Debug, sqlx::FromRow)]
+pub struct ReputationLeaf {
+    pub position:  i64,
+    pub score:     i64,
+    pub leaf_hash: String,
+    #[sqlx(flatten)]
+    pub entry:     ReputationEntry,
+}
+
+// ─── Prova de inclusão ───────────────────────────────────────
+
+/// Lado do irmão em cada nível da árvore
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MerkleSide {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct MerkleStep {
+    pub hash: String,
+    pub side: MerkleSide,
+}
+
+/// Prova de que a folha do endereço está na root ancorada
+#[derive(Debug, Serialize)]
+pub struct ReputationProof {
+    pub entry:        ReputationEntry,   // componentes na fotografia
+    pub score:        i64,
+    pub leaf_hash:    String,
+    pub position:     i64,
+    pub path:         Vec<MerkleStep>,
+    pub root:         String,
+    pub block_hash:   String,
+    pub block_height: i64,
+    pub valid:        bool,              // caminho refaz a root (conferido no servidor)
+}
+
+/// Resposta de GET /api/reputation/:address
+#[derive(Debug, Serialize)]
+pub struct ReputationResponse {
+    pub address:    String,
+    pub score:      i64,                     // calculada agora
+    pub components: ReputationEntry,
+    pub proof:      Option<ReputationProof>, // última fotografia ancorada
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> ReputationEntry {
+        ReputationEntry {
+            address:          "BPC1".into(),
+            completed:        0,
+            refunds:          0,
+            disputes_won:     0,
+            disputes_lost:    0,
+            verified_reviews: 0,
+            avg_rating:       None,
+        }
+    }
+
+    #[test]
+    fn test_score_components() {
+        let mut e = entry();
+        assert_eq!(e.score(), 500);
+
+        e.completed = 50;
+        assert_eq!(e.score(), 800);
+
+        e.disputes_lost = 2;
+        e.refunds = 2;
+        assert_eq!(e.score(), 670);
+
+        e.verified_reviews = 5;
+        e.avg_rating = Some(5.0);
+        assert_eq!(e.score(), 720);
+    }
+
+    #[test]
+    fn test_score_is_clamped() {
+        let mut e = entry();
+        e.disputes_lost = 100;
+        assert_eq!(e.score(), 0);
+
+        e.disputes_lost = 0;
+        e.disputes_won = 100;
+        assert_eq!(e.score(), 1000);
+    }
+}
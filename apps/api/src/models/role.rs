@@ -0,0 +1,157 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Papel de acesso ─────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum Role {
+    User,        // todo usuário cadastrado
+    Seller,      // pode anunciar
+    Arbiter,     // resolve disputas de escrow
+    Moderator,   // fila de denúncias e termos proibidos
+    Admin,       // concede e revoga papéis; satisfaz qualquer exigência
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User      => "user",
+            Role::Seller    => "seller",
+            Role::Arbiter   => "arbiter",
+            Role::Moderator => "moderator",
+            Role::Admin     => "admin",
+        }
+    }
+
+    /// Papéis concedidos no cadastro
+    pub fn defaults() -> [Role; 2] {
+        [Role::User, Role::Seller]
+    }
+
+    /// `user` é implícito — não se concede nem se revoga
+    pub fn is_grantable(&self) -> bool {
+        *self != Role::User
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Se os papéis atendem à exigência (admin atende a todas)
+pub fn satisfies(roles: &[Role], required: Role) -> bool {
+    roles.contains(&required) || roles.contains(&Role::Admin)
+}
+
+// ─── Concessão / revogação ───────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "TEXT", rename_all = "UPPERCASE")]
+pub enum RoleAction {
+    Grant,
+    Revoke,
+}
+
+// ─── Registro de auditoria ───────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RoleAudit {
+    pub id:            String,
+    pub user_id:       String,
+    pub role:          Role,
+    pub action:        RoleAction,
+    pub actor_address: Option<String>,   // None = cadastro ou ADMIN_ADDRESSES
+    pub reason:        String,
+    pub created_at:    String,
+}
+
+impl RoleAudit {
+    pub fn new(
+        user_id:       String,
+        role:          Role,
+        action:        RoleAction,
+        actor_address: Option<String>,
+        reason:        String,
+    ) -> Self {
+        Self {
+            id:            Uuid::new_v4().to_string(),
+            user_id,
+            role,
+            action,
+            actor_address,
+            reason,
+            created_at:    Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Admin concede papel
+#[derive(Debug, Deserialize)]
+pub struct GrantRoleRequest {
+    pub role:   Role,
+    pub reason: String,
+}
+
+/// Motivo da revogação (?reason=)
+#[derive(Debug, Deserialize)]
+pub struct RevokeRoleQuery {
+    pub reason: String,
+}
+
+/// Papéis atuais de um usuário
+#[derive(Debug, Serialize)]
+pub struct UserRolesResponse {
+    pub user_id:  String,
+    pub username: String,
+    pub roles:    Vec<Role>,
+}
+
+/// Parâmetros da auditoria de papéis
+#[derive(Debug, Deserialize)]
+pub struct RoleAuditFilters {
+    pub user_id: Option<String>,
+    pub role:    Option<Role>,
+    pub page:    Option<i64>,
+    pub limit:   Option<i64>,
+}
+
+/// Página da auditoria de papéis
+#[derive(Debug, Serialize)]
+pub struct RoleAuditResponse {
+    pub entries: Vec<RoleAudit>,
+    pub total:   i64,
+    pub page:    i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_satisfies_any_role() {
+        assert!(satisfies(&[Role::Admin], Role::Moderator));
+        assert!(satisfies(&[Role::Admin], Role::Arbiter));
+        assert!(satisfies(&[Role::User, Role::Seller], Role::Seller));
+        assert!(!satisfies(&[Role::User, Role::Seller], Role::Moderator));
+        assert!(!satisfies(&[Role::Moderator], Role::Admin));
+    }
+
+    #[test]
+    fn test_user_role_is_implicit() {
+        assert!(!Role::User.is_grantable());
+        assert!(Role::Arbiter.is_grantable());
+        assert!(Role::defaults().contains(&Role::User));
+    }
+
+    #[test]
+    fn test_role_serialization() {
+        assert_eq!(serde_json::to_string(&Role::Moderator).unwrap(), "\"moderator\"");
+        let role: Role = serde_json::from_str("\"arbiter\"").unwrap();
+        assert_eq!(role, Role::Arbiter);
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Tipo de evento de segurança ─────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SecurityEventKind {
+    RecoverySucceeded,   // nova masterkey definida após provar a posse da chave
+    RecoveryFailed,      // desafio inválido ou assinatura que não confere
+    TotpEnabled,         // 2FA confirmado com o primeiro código
+    TotpDisabled,
+    BackupCodesRegenerated,
+    BackupCodeUsed,      // login com código de recuperação
+    LoginLocked,         // falhas seguidas de login → bloqueio temporário
+    TotpLocked,          // códigos TOTP errados seguidos → bloqueio temporário
+}
+
+// ─── Evento ──────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SecurityEvent {
+    pub id:         String,
+    pub user_id:    Option<String>,
+    pub address:    Option<String>,
+    pub event_type: SecurityEventKind,
+    pub ip_address: Option<String>,
+    pub detail:     Option<String>,
+    pub created_at: String,
+}
+
+impl SecurityEvent {
+    pub fn new(
+        user_id:    Option<String>,
+        address:    Option<String>,
+        event_type: SecurityEventKind,
+        ip_address: Option<String>,
+        detail:     Option<String>,
+    ) -> Self {
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            user_id,
+            address,
+            event_type,
+            ip_address,
+            detail,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Nova masterkey: 8 a 128 caracteres
+pub fn validate_masterkey(masterkey: &str) -> Result<(), String> {
+    let len = masterkey.chars().count();
+    if !(8..=128).contains(&len) {
+        return Err("Masterkey deve ter entre 8 e 128 caracteres".into());
+    }
+    Ok(())
+}
+
+/// Duração do bloqueio após `failed_count` falhas seguidas: nada até
+/// o limite, depois `base_secs` dobrando a cada nova falha, até `max_secs`
+pub fn lockout_seconds(failed_count: i64, threshold: i64, base_secs: i64, max_secs: i64) -> i64 {
+    if failed_count < threshold {
+        return 0;
+    }
+    let doublings = (failed_count - threshold).min(20) as u32;
+    base_secs.saturating_mul(1 << doublings).min(max_secs)
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Recuperação: desafio RECOVER assinado + nova credencial de login
+#[derive(Debug, Deserialize)]
+pub struct RecoverRequest {
+    pub challenge_id:  String,
+    pub signature:     String,   // assinatura com a chave re-derivada das 12 palavras
+    pub new_masterkey: String,
+    pub totp_code:     Option<String>,   // exigido com 2FA ativo (TOTP ou código de recuperação)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_masterkey() {
+        assert!(validate_masterkey("senha-forte").is_ok());
+        assert!(validate_masterkey("curta").is_err());
+        assert!(validate_masterkey(&"x".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn test_lockout_seconds() {
+        assert_eq!(lockout_seconds(4, 5, 30, 3600), 0);
+        assert_eq!(lockout_seconds(5, 5, 30, 3600), 30);
+        assert_eq!(lockout_seconds(7, 5, 30, 3600), 120);
+        assert_eq!(lockout_seconds(40, 5, 30, 3600), 3600);
+    }
+
+    #[test]
+    fn test_event_kind_serialization() {
+        let json = serde_json::to_string(&SecurityEventKind::RecoveryFailed).unwrap();
+        assert_eq!(json, "\"RECOVERY_FAILED\"");
+    }
+}
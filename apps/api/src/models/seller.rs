@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::product::ProductResponse;
+
+const MAX_BIO_CHARS:      usize = 500;
+const MAX_POLICIES_CHARS: usize = 2000;
+
+// ─── Perfil público ──────────────────────────────────────────
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SellerProfile {
+    pub username:      String,
+    pub address:       String,           // endereço BPC (GET /api/reputation/:address)
+    pub bio:           Option<String>,
+    pub avatar_sha256: Option<String>,   // GET /api/media/:sha256
+    pub policies:      Option<String>,
+    pub member_since:  String,           // users.created_at
+}
+
+// ─── Métricas da vitrine ─────────────────────────────────────
+// Contadores vêm do SQL; `dispute_rate` é derivada.
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SellerStats {
+    pub active_products:   i64,
+    pub sales_count:       i64,          // escrows RELEASED como vendedor
+    pub avg_rating:        Option<f64>,  // todas as avaliações de todos os produtos
+    pub review_count:      i64,
+    pub escrows:           i64,          // escrows com fundos travados
+    pub disputes:          i64,          // escrows que passaram por disputa
+    pub avg_response_secs: Option<i64>,  // criação do escrow → assinatura do vendedor
+    #[sqlx(skip)]
+    pub dispute_rate:      Option<f64>,  // disputes / escrows
+}
+
+impl SellerStats {
+    pub fn with_dispute_rate(mut self) -> Self {
+        self.dispute_rate = dispute_rate(self.disputes, self.escrows);
+        self
+    }
+}
+
+/// Fração de escrows disputados; `None` sem histórico
+pub fn dispute_rate(disputes: i64, escrows: i64) -> Option<f64> {
+    (escrows > 0).then(|| disputes.min(escrows) as f64 / escrows as f64)
+}
+
+/// Limites dos campos editáveis; avatar é um SHA-256 em hex
+pub fn validate_profile(req: &UpdateProfileRequest) -> Result<(), String> {
+    if req.bio.as_deref().is_some_and(|b| b.chars().count() > MAX_BIO_CHARS) {
+        return Err(format!("Bio deve ter até {} caracteres", MAX_BIO_CHARS));
+    }
+    if req.policies.as_deref().is_some_and(|p| p.chars().count() > MAX_POLICIES_CHARS) {
+        return Err(format!("Políticas devem ter até {} caracteres", MAX_POLICIES_CHARS));
+    }
+    if let Some(ref avatar) = req.avatar_sha256 {
+        let valid = avatar.is_empty()
+            || (avatar.len() == 64 && avatar.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+        if !valid {
+            return Err("Avatar deve ser o SHA-256 (hex) de uma imagem enviada".into());
+        }
+    }
+
+    Ok(())
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Editar o próprio perfil. Campo `""` limpa o valor; ausente mantém.
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileRequest {
+    pub bio:           Option<String>,
+    pub avatar_sha256: Option<String>,
+    pub policies:      Option<String>,
+}
+
+/// Parâmetros de GET /api/sellers/:username
+#[derive(Debug, Deserialize)]
+pub struct StorefrontFilters {
+    pub page:  Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Vitrine: perfil, métricas e produtos ativos
+#[derive(Debug, Serialize)]
+pub struct StorefrontResponse {
+    #[serde(flatten)]
+    pub profile:  SellerProfile,
+    pub stats:    SellerStats,
+    pub products: Vec<ProductResponse>,
+    pub page:     i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispute_rate() {
+        assert_eq!(dispute_rate(0, 0), None);
+        assert_eq!(dispute_rate(1, 4), Some(0.25));
+        assert_eq!(dispute_rate(0, 3), Some(0.0));
+    }
+
+    #[test]
+    fn test_validate_profile() {
+        let req = |bio: &str, avatar: &str| UpdateProfileRequest {
+            bio:           Some(bio.into()),
+            avatar_sha256: Some(avatar.into()),
+            policies:      None,
+        };
+
+        assert!(validate_profile(&req("Fotógrafo em Santos", &"ab".repeat(32))).is_ok());
+        assert!(validate_profile(&req("", "")).is_ok());
+        assert!(validate_profile(&req("", &"AB".repeat(32))).is_err());
+        assert!(validate_profile(&req("", "../etc/passwd")).is_err());
+        assert!(validate_profile(&req(&"x".repeat(501), "")).is_err());
+    }
+}
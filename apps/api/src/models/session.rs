@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Sessão (um login / dispositivo) ─────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Session {
+    pub id:            String,
+    pub user_id:       String,
+    #[serde(skip_serializing)]
+    pub refresh_hash:  String,
+    #[serde(skip_serializing)]
+    pub access_jti:    String,
+    pub user_agent:    Option<String>,
+    pub ip_address:    Option<String>,
+    pub created_at:    String,
+    pub last_used_at:  String,
+    pub expires_at:    String,
+    pub revoked_at:    Option<String>,
+}
+
+impl Session {
+    pub fn new(
+        user_id:      String,
+        refresh_hash: String,
+        access_jti:   String,
+        user_agent:   Option<String>,
+        ip_address:   Option<String>,
+        ttl_days:     i64,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id:            Uuid::new_v4().to_string(),
+            user_id,
+            refresh_hash,
+            access_jti,
+            user_agent,
+            ip_address,
+            created_at:    now.to_rfc3339(),
+            last_used_at:  now.to_rfc3339(),
+            expires_at:    (now + Duration::days(ttl_days)).to_rfc3339(),
+            revoked_at:    None,
+        }
+    }
+
+    /// Sessão não revogada e dentro da validade
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none()
+            && DateTime::parse_from_rfc3339(&self.expires_at)
+                .map(|exp| exp > now)
+                .unwrap_or(false)
+    }
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Troca de refresh token
+#[derive(Debug, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+/// Par de tokens devolvido no login, cadastro e refresh
+#[derive(Debug, Serialize)]
+pub struct TokenPair {
+    pub token:         String,   // access token (JWT curto)
+    pub refresh_token: String,   // exibido uma vez — só o hash fica no banco
+    pub expires_in:    i64,      // segundos até o access token expirar
+}
+
+/// Sessão ativa na listagem do usuário
+#[derive(Debug, Serialize)]
+pub struct SessionResponse {
+    #[serde(flatten)]
+    pub session: Session,
+    pub current: bool,           // sessão do token usado na requisição
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_expiry() {
+        let session = Session::new("u1".into(), "h".into(), "j".into(), None, None, 30);
+        assert!(session.is_active(Utc::now()));
+        assert!(!session.is_active(Utc::now() + Duration::days(31)));
+    }
+
+    #[test]
+    fn test_revoked_session_is_inactive() {
+        let mut session = Session::new("u1".into(), "h".into(), "j".into(), None, None, 30);
+        session.revoked_at = Some(Utc::now().to_rfc3339());
+        assert!(!session.is_active(Utc::now()));
+    }
+
+    #[test]
+    fn test_hashes_never_serialized() {
+        let session = Session::new("u1".into(), "segredo".into(), "jti".into(), None, None, 30);
+        let json = serde_json::to_string(&session).unwrap();
+        assert!(!json.contains("segredo"));
+        assert!(!json.contains("refresh_hash"));
+    }
+}
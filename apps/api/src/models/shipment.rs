@@ -0,0 +1,194 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// ─── Evento de envio ─────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, sqlx::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ShipmentEventType {
+    Shipped,             // vendedor despachou (criado com o envio)
+    InTransit,           // atualização de rastreio
+    OutForDelivery,
+    Delivered,           // entregue segundo a transportadora
+    Exception,           // extravio, devolução, endereço não encontrado...
+    DeliveryConfirmed,   // comprador confirmou o recebimento
+}
+
+impl ShipmentEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShipmentEventType::Shipped           => "SHIPPED",
+            ShipmentEventType::InTransit         => "IN_TRANSIT",
+            ShipmentEventType::OutForDelivery    => "OUT_FOR_DELIVERY",
+            ShipmentEventType::Delivered         => "DELIVERED",
+            ShipmentEventType::Exception         => "EXCEPTION",
+            ShipmentEventType::DeliveryConfirmed => "DELIVERY_CONFIRMED",
+        }
+    }
+
+    /// Quem pode registrar o evento: rastreio é do vendedor,
+    /// confirmação é do comprador; ambos podem relatar problema.
+    pub fn allowed_for(&self, role: &str) -> bool {
+        use ShipmentEventType::*;
+        match self {
+            Shipped                                => false,
+            InTransit | OutForDelivery | Delivered => role == "seller",
+            DeliveryConfirmed                      => role == "buyer",
+            Exception                              => role == "seller" || role == "buyer",
+        }
+    }
+}
+
+impl std::fmt::Display for ShipmentEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// ─── Envio ───────────────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Shipment {
+    pub id:                        String,
+    pub contract_id:               String,
+    pub carrier:                   String,
+    pub tracking_code:             String,
+    pub shipped_at_block:          i64,
+    pub estimated_delivery_blocks: i64,
+    pub auto_release_block:        i64,           // escrow liberado se não houver disputa
+    pub delivered_at_block:        Option<i64>,
+    pub created_at:                String,
+    pub updated_at:                String,
+}
+
+impl Shipment {
+    pub fn new(
+        contract_id:               String,
+        carrier:                   String,
+        tracking_code:             String,
+        shipped_at_block:          i64,
+        estimated_delivery_blocks: i64,
+        dispute_window_blocks:     i64,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            id:                        Uuid::new_v4().to_string(),
+            contract_id,
+            carrier,
+            tracking_code,
+            shipped_at_block,
+            estimated_delivery_blocks,
+            auto_release_block:        shipped_at_block + estimated_delivery_blocks + dispute_window_blocks,
+            delivered_at_block:        None,
+            created_at:                now.clone(),
+            updated_at:                now,
+        }
+    }
+}
+
+// ─── Registro na linha do tempo ──────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ShipmentEvent {
+    pub id:           String,
+    pub shipment_id:  String,
+    pub event_type:   ShipmentEventType,
+    pub reported_by:  String,   // buyer | seller
+    pub description:  Option<String>,
+    pub block_height: i64,
+    pub created_at:   String,
+}
+
+impl ShipmentEvent {
+    pub fn new(
+        shipment_id:  String,
+        event_type:   ShipmentEventType,
+        reported_by:  String,
+        description:  Option<String>,
+        block_height: i64,
+    ) -> Self {
+        Self {
+            id:           Uuid::new_v4().to_string(),
+            shipment_id,
+            event_type,
+            reported_by,
+            description,
+            block_height,
+            created_at:   Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Transportadora até 40 caracteres; código de rastreio com 4 a 40
+/// letras, dígitos ou hífen (formatos variam entre transportadoras)
+pub fn validate_tracking(carrier: &str, tracking_code: &str) -> Result<(), String> {
+    let carrier = carrier.trim();
+    if carrier.is_empty() || carrier.chars().count() > 40 {
+        return Err("Transportadora deve ter entre 1 e 40 caracteres".into());
+    }
+
+    let code = tracking_code.trim();
+    let valid = (4..=40).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid {
+        return Err("Código de rastreio deve ter 4 a 40 letras, dígitos ou hífen".into());
+    }
+
+    Ok(())
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Vendedor registra o envio
+#[derive(Debug, Deserialize)]
+pub struct CreateShipmentRequest {
+    pub carrier:                   String,
+    pub tracking_code:             String,
+    pub estimated_delivery_blocks: i64,
+}
+
+/// Novo evento de rastreio ou confirmação de entrega
+#[derive(Debug, Deserialize)]
+pub struct ShipmentEventRequest {
+    pub event_type:  ShipmentEventType,
+    pub description: Option<String>,
+}
+
+/// Envio com a linha do tempo
+#[derive(Debug, Serialize)]
+pub struct ShipmentResponse {
+    #[serde(flatten)]
+    pub shipment:          Shipment,
+    pub events:            Vec<ShipmentEvent>,
+    pub blocks_to_release: Option<i64>,   // None se o escrow não está mais LOCKED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_release_block() {
+        let shipment = Shipment::new("c1".into(), "Correios".into(), "AA123456789BR".into(), 100, 30, 20);
+        assert_eq!(shipment.auto_release_block, 150);
+    }
+
+    #[test]
+    fn test_validate_tracking() {
+        assert!(validate_tracking("Correios", "AA123456789BR").is_ok());
+        assert!(validate_tracking(" ", "AA123456789BR").is_err());
+        assert!(validate_tracking("Jadlog", "12").is_err());
+        assert!(validate_tracking("Jadlog", "ABC 123").is_err());
+    }
+
+    #[test]
+    fn test_event_roles() {
+        use ShipmentEventType::*;
+
+        assert!(InTransit.allowed_for("seller"));
+        assert!(!InTransit.allowed_for("buyer"));
+        assert!(DeliveryConfirmed.allowed_for("buyer"));
+        assert!(!DeliveryConfirmed.allowed_for("seller"));
+        assert!(Exception.allowed_for("buyer"));
+        assert!(!Shipped.allowed_for("seller"));
+    }
+}
@@ -0,0 +1,106 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::totp::base32_encode;
+
+/// Quantidade de códigos de recuperação emitidos por vez
+pub const BACKUP_CODE_COUNT: usize = 10;
+
+// ─── TOTP do usuário ─────────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UserTotp {
+    pub user_id:        String,
+    #[serde(skip_serializing)]
+    pub secret:         String,
+    pub enabled_at:     Option<String>,
+    pub last_used_step: i64,
+    pub created_at:     String,
+}
+
+impl UserTotp {
+    /// Setup confirmado — a partir daqui o login pede o código
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+// ─── Códigos de recuperação ──────────────────────────────────
+// 8 caracteres base32 (40 bits) no formato `abcd-efgh`
+pub fn generate_backup_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| {
+            let code = base32_encode(&rng.gen::<[u8; 5]>()).to_lowercase();
+            format!("{}-{}", &code[..4], &code[4..])
+        })
+        .collect()
+}
+
+/// Forma canônica antes do hash: sem hífen/espaços, minúsculas
+pub fn normalize_backup_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Código TOTP tem 6 dígitos; o resto é tratado como código de recuperação
+pub fn is_totp_format(code: &str) -> bool {
+    let code = code.trim();
+    code.len() == 6 && code.chars().all(|c| c.is_ascii_digit())
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Setup: segredo para digitar ou URI para o QR code
+#[derive(Debug, Serialize)]
+pub struct TotpSetupResponse {
+    pub secret:      String,
+    pub otpauth_uri: String,
+}
+
+/// Confirmação/desativação com um código atual
+#[derive(Debug, Deserialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Códigos de recuperação em texto puro — exibidos UMA vez
+#[derive(Debug, Serialize)]
+pub struct BackupCodesResponse {
+    pub backup_codes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TwoFactorStatus {
+    pub enabled:                bool,
+    pub enabled_at:             Option<String>,
+    pub backup_codes_remaining: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_codes_format() {
+        let codes = generate_backup_codes();
+        assert_eq!(codes.len(), BACKUP_CODE_COUNT);
+        assert!(codes.iter().all(|c| c.len() == 9 && c.as_bytes()[4] == b'-'));
+        assert!(codes.iter().all(|c| !is_totp_format(c)));
+    }
+
+    #[test]
+    fn test_normalize_backup_code() {
+        assert_eq!(normalize_backup_code(" ABCD-efgh "), "abcdefgh");
+        assert_eq!(normalize_backup_code("abcd efgh"), "abcdefgh");
+    }
+
+    #[test]
+    fn test_totp_format() {
+        assert!(is_totp_format("012345"));
+        assert!(!is_totp_format("12345"));
+        assert!(!is_totp_format("abcd-efgh"));
+    }
+}
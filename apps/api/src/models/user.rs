@@ -2,6 +2,9 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::role::Role;
+use crate::models::session::TokenPair;
+
 // ─── Usuário ─────────────────────────────────────────────────
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct User {
@@ -50,7 +53,9 @@ impl Wallet {
 #[derive(Debug, Deserialize)]
 pub struct RegisterRequest {
     pub username:   String,
-    pub masterkey:  String,    // senha do usuário — derivada em chave privada
+    pub pubkey:     Option<String>, // secp256k1 comprimida (hex), gerada no cliente
+    pub signature:  Option<String>, // prova de posse — ver `register_message`
+    pub masterkey:  Option<String>, // só no modo dev (DEV_SERVER_KEYGEN)
 }
 
 /// Payload de login
@@ -58,30 +63,41 @@ pub struct RegisterRequest {
 pub struct LoginRequest {
     pub username:   String,
     pub masterkey:  String,
+    pub totp_code:  Option<String>,   // exigido com 2FA ativo (TOTP ou código de recuperação)
 }
 
-/// Resposta de registro — inclui as 12 palavras (exibidas UMA vez)
+/// Resposta de registro — no modo dev inclui as 12 palavras (exibidas UMA vez)
 #[derive(Debug, Serialize)]
 pub struct RegisterResponse {
-    pub token:      String,    // JWT
+    #[serde(flatten)]
+    pub tokens:     TokenPair, // access + refresh token
     pub address:    String,    // endereço BPC
     pub pubkey:     String,    // chave pública
-    pub mnemonic:   Vec<String>, // 12 palavras — nunca armazenadas no servidor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic:   Option<Vec<String>>, // modo dev: 12 palavras — nunca armazenadas
 }
 
 /// Resposta de login
 #[derive(Debug, Serialize)]
 pub struct LoginResponse {
-    pub token:      String,    // JWT
+    #[serde(flatten)]
+    pub tokens:     TokenPair, // access + refresh token
     pub address:    String,    // endereço BPC
     pub username:   String,
+    pub roles:      Vec<Role>,
 }
 
 /// Claims do JWT
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub:        String,    // user id
     pub username:   String,
     pub address:    String,    // endereço BPC
+    #[serde(default)]
+    pub roles:      Vec<Role>, // papéis na emissão (informativo; guardas usam o banco)
+    #[serde(default)]
+    pub jti:        String,    // id do token (lista de revogação)
+    #[serde(default)]
+    pub sid:        String,    // sessão que emitiu o token
     pub exp:        usize,     // expiração (timestamp Unix)
-}
\ No newline at end of file
+}
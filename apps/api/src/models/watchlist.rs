@@ -0,0 +1,164 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::product::{fts_match_expr, ProductResponse};
+
+// Limite de buscas salvas por usuário
+pub const MAX_SAVED_SEARCHES: i64 = 20;
+
+// ─── Produto acompanhado ─────────────────────────────────────
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct WatchlistItem {
+    pub user_id:         String,
+    pub product_id:      String,
+    pub last_price_sats: i64,      // base para o alerta de queda de preço
+    pub created_at:      String,
+}
+
+impl WatchlistItem {
+    pub fn new(user_id: String, product_id: String, last_price_sats: i64) -> Self {
+        Self {
+            user_id,
+            product_id,
+            last_price_sats,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ─── Busca salva ─────────────────────────────────────────────
+// Mesmos filtros de GET /marketplace/products; o texto livre é
+// guardado também como expressão FTS5 para casar produtos novos.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SavedSearch {
+    pub id:         String,
+    pub user_id:    String,
+    pub name:       String,
+    pub query:      Option<String>,
+    #[serde(skip_serializing)]
+    pub fts_query:  Option<String>,
+    pub category:   Option<String>,   // slug — inclui subcategorias
+    pub condition:  Option<String>,
+    pub state:      Option<String>,   // UF
+    pub min_price:  Option<i64>,
+    pub max_price:  Option<i64>,
+    pub created_at: String,
+}
+
+impl SavedSearch {
+    pub fn new(user_id: String, req: CreateSavedSearchRequest) -> Self {
+        let query = req.query.map(|q| q.trim().to_string()).filter(|q| !q.is_empty());
+        Self {
+            id:         Uuid::new_v4().to_string(),
+            user_id,
+            name:       req.name.trim().to_string(),
+            fts_query:  query.as_deref().and_then(fts_match_expr),
+            query,
+            category:   req.category,
+            condition:  req.condition,
+            state:      req.state.map(|s| s.trim().to_uppercase()),
+            min_price:  req.min_price,
+            max_price:  req.max_price,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Nome obrigatório, faixa de preço coerente e ao menos um filtro
+pub fn validate_saved_search(req: &CreateSavedSearchRequest) -> Result<(), String> {
+    if req.name.trim().is_empty() || req.name.trim().chars().count() > 80 {
+        return Err("Nome deve ter entre 1 e 80 caracteres".into());
+    }
+    if req.min_price.is_some_and(|p| p < 0) || req.max_price.is_some_and(|p| p < 0) {
+        return Err("Preço não pode ser negativo".into());
+    }
+    if let (Some(min), Some(max)) = (req.min_price, req.max_price) {
+        if min > max {
+            return Err("Preço mínimo maior que o máximo".into());
+        }
+    }
+
+    let has_query = req.query.as_deref().and_then(fts_match_expr).is_some();
+    let has_filter = has_query
+        || req.category.is_some()
+        || req.condition.is_some()
+        || req.state.is_some()
+        || req.min_price.is_some()
+        || req.max_price.is_some();
+
+    if !has_filter {
+        return Err("Informe ao menos um filtro".into());
+    }
+
+    Ok(())
+}
+
+// ─── DTOs ────────────────────────────────────────────────────
+
+/// Acompanhar produto
+#[derive(Debug, Deserialize)]
+pub struct AddWatchRequest {
+    pub product_id: String,
+}
+
+/// Salvar busca (campos iguais aos de `ProductFilters`)
+#[derive(Debug, Deserialize)]
+pub struct CreateSavedSearchRequest {
+    pub name:      String,
+    pub query:     Option<String>,
+    pub category:  Option<String>,
+    pub condition: Option<String>,
+    pub state:     Option<String>,
+    pub min_price: Option<i64>,
+    pub max_price: Option<i64>,
+}
+
+/// Item da watchlist com o produto atual
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct WatchlistEntry {
+    pub watched_since:   String,
+    pub last_price_sats: i64,
+    #[sqlx(flatten)]
+    pub product:         ProductResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(name: &str) -> CreateSavedSearchRequest {
+        CreateSavedSearchRequest {
+            name:      name.into(),
+            query:     None,
+            category:  None,
+            condition: None,
+            state:     None,
+            min_price: None,
+            max_price: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_saved_search() {
+        assert!(validate_saved_search(&req("vazia")).is_err());
+        assert!(validate_saved_search(&CreateSavedSearchRequest { query: Some("  ?! ".into()), ..req("só símbolos") }).is_err());
+        assert!(validate_saved_search(&CreateSavedSearchRequest { category: Some("foto".into()), ..req("") }).is_err());
+        assert!(validate_saved_search(&CreateSavedSearchRequest { min_price: Some(10), max_price: Some(5), ..req("faixa") }).is_err());
+        assert!(validate_saved_search(&CreateSavedSearchRequest { query: Some("câmera analógica".into()), ..req("câmeras") }).is_ok());
+    }
+
+    #[test]
+    fn test_saved_search_normalizes() {
+        let search = SavedSearch::new("u1".into(), CreateSavedSearchRequest {
+            query: Some("  leica m6 ".into()),
+            state: Some("sp".into()),
+            ..req(" Leica ")
+        });
+
+        assert_eq!(search.name, "Leica");
+        assert_eq!(search.query.as_deref(), Some("leica m6"));
+        assert_eq!(search.fts_query.as_deref(), Some("\"leica\"* \"m6\"*"));
+        assert_eq!(search.state.as_deref(), Some("SP"));
+    }
+}
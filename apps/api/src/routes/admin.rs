@@ -0,0 +1,160 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::middleware::from_fn;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::role::{
+    GrantRoleRequest, RevokeRoleQuery, Role, RoleAudit, RoleAuditFilters, RoleAuditResponse,
+    UserRolesResponse,
+};
+use crate::blockchain::roles::{grant_role, load_roles, revoke_role};
+use crate::middleware::auth::role_guard;
+
+// ─── Configuração das rotas ──────────────────────────────────
+// Escopo inteiro exige o papel admin.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/admin")
+            .wrap(from_fn(role_guard(Role::Admin)))
+            .route("/users/{username}/roles",        web::get().to(get_user_roles))
+            .route("/users/{username}/roles",        web::post().to(grant_user_role))
+            .route("/users/{username}/roles/{role}", web::delete().to(revoke_user_role))
+            .route("/role-audit",                    web::get().to(list_role_audit)),
+    );
+}
+
+// ─── GET /api/admin/users/:username/roles ────────────────────
+async fn get_user_roles(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    let user_id = user_id_by_username(pool.as_ref(), &username).await?;
+    let roles = load_roles(pool.as_ref(), &user_id).await?;
+
+    Ok(HttpResponse::Ok().json(UserRolesResponse { user_id, username, roles }))
+}
+
+// ─── POST /api/admin/users/:username/roles ───────────────────
+// Novos papéis entram no token no próximo login; as guardas só
+// consultam o banco, então a concessão já vale para elas.
+async fn grant_user_role(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<GrantRoleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    if !body.role.is_grantable() {
+        return Err(AppError::Validation("O papel user é implícito".into()));
+    }
+    let reason = required_reason(&body.reason)?;
+
+    let username = path.into_inner();
+    let user_id = user_id_by_username(pool.as_ref(), &username).await?;
+
+    let granted = grant_role(pool.as_ref(), &user_id, body.role, Some(&claims.address), &reason).await?;
+    if !granted {
+        return Err(AppError::AlreadyExists(format!("{} já tem o papel {}", username, body.role)));
+    }
+
+    let roles = load_roles(pool.as_ref(), &user_id).await?;
+
+    Ok(HttpResponse::Created().json(UserRolesResponse { user_id, username, roles }))
+}
+
+// ─── DELETE /api/admin/users/:username/roles/:role?reason= ───
+// Vale de imediato: as guardas conferem o banco a cada requisição.
+async fn revoke_user_role(
+    pool:  web::Data<SqlitePool>,
+    req:   HttpRequest,
+    path:  web::Path<(String, Role)>,
+    query: web::Query<RevokeRoleQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (username, role) = path.into_inner();
+    if !role.is_grantable() {
+        return Err(AppError::Validation("O papel user é implícito".into()));
+    }
+    let reason = required_reason(&query.reason)?;
+
+    let user_id = user_id_by_username(pool.as_ref(), &username).await?;
+
+    // Evita que o último acesso administrativo se tranque para fora
+    if role == Role::Admin && user_id == claims.sub {
+        return Err(AppError::Conflict("Admin não pode revogar o próprio papel admin".into()));
+    }
+
+    let revoked = revoke_role(pool.as_ref(), &user_id, role, &claims.address, &reason).await?;
+    if !revoked {
+        return Err(AppError::NotFound(format!("{} não tem o papel {}", username, role)));
+    }
+
+    let roles = load_roles(pool.as_ref(), &user_id).await?;
+
+    Ok(HttpResponse::Ok().json(UserRolesResponse { user_id, username, roles }))
+}
+
+// ─── GET /api/admin/role-audit ───────────────────────────────
+// Concessões e revogações (?user_id=&role=), mais recentes primeiro.
+async fn list_role_audit(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<RoleAuditFilters>,
+) -> Result<HttpResponse, AppError> {
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let conditions = "(?1 IS NULL OR user_id = ?1) AND (?2 IS NULL OR role = ?2)";
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM role_audit WHERE {}",
+        conditions
+    ))
+    .bind(&filters.user_id)
+    .bind(filters.role)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let entries = sqlx::query_as::<_, RoleAudit>(&format!(
+        "SELECT * FROM role_audit WHERE {} ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
+        conditions
+    ))
+    .bind(&filters.user_id)
+    .bind(filters.role)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(RoleAuditResponse { entries, total, page }))
+}
+
+// ─── Motivo obrigatório (vai para a auditoria) ───────────────
+fn required_reason(reason: &str) -> Result<String, AppError> {
+    let reason = reason.trim();
+    if reason.is_empty() || reason.chars().count() > 500 {
+        return Err(AppError::Validation("Motivo deve ter entre 1 e 500 caracteres".into()));
+    }
+    Ok(reason.to_string())
+}
+
+// ─── user_id a partir do username ────────────────────────────
+async fn user_id_by_username(pool: &SqlitePool, username: &str) -> Result<String, AppError> {
+    sqlx::query_scalar::<_, String>("SELECT id FROM users WHERE username = ?")
+        .bind(username)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Usuário não encontrado".into()))
+}
@@ -0,0 +1,401 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::product::Product;
+use crate::models::auction::{
+    bid_message, Auction, AuctionKind, AuctionResponse, AuctionStatus, AuctionTerms, Bid,
+    CreateAuctionRequest, PlaceBidRequest,
+};
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::auctions::{award_auction, find_auction, settle_auction};
+use crate::crypto::signing::verify_signature;
+use crate::ws::hub::Hub;
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/auctions")
+            .route("",              web::post().to(create_auction))
+            .route("",              web::get().to(list_auctions))
+            .route("/{id}",         web::get().to(get_auction))
+            .route("/{id}/bids",    web::post().to(place_bid))
+            .route("/{id}/settle",  web::post().to(settle))
+            .route("/{id}/cancel",  web::post().to(cancel_auction)),
+    );
+}
+
+// ─── POST /api/auctions ──────────────────────────────────────
+async fn create_auction(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateAuctionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    // 1. Validações
+    let reserve_price_sats   = body.reserve_price_sats.unwrap_or(0);
+    let min_increment_sats   = body.min_increment_sats.unwrap_or(1);
+    let decay_per_block_sats = body.decay_per_block_sats.unwrap_or(0);
+    let extension_blocks     = match body.kind {
+        AuctionKind::English => body.extension_blocks.unwrap_or(5),
+        AuctionKind::Dutch   => 0,
+    };
+
+    if body.start_price_sats <= 0 {
+        return Err(AppError::Validation("Preço inicial deve ser maior que zero".into()));
+    }
+    if reserve_price_sats < 0 || min_increment_sats <= 0 || extension_blocks < 0 {
+        return Err(AppError::Validation("Parâmetros do leilão inválidos".into()));
+    }
+    if !(1..=10_000).contains(&body.duration_blocks) {
+        return Err(AppError::Validation("Duração deve ser entre 1 e 10000 blocos".into()));
+    }
+    if body.kind == AuctionKind::Dutch {
+        if decay_per_block_sats <= 0 {
+            return Err(AppError::Validation(
+                "Leilão holandês exige decay_per_block_sats maior que zero".into(),
+            ));
+        }
+        if reserve_price_sats > body.start_price_sats {
+            return Err(AppError::Validation(
+                "Preço mínimo não pode ser maior que o preço inicial".into(),
+            ));
+        }
+    }
+
+    // 2. Produto ativo do próprio vendedor, sem outro leilão aberto
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let product = sqlx::query_as::<_, Product>(
+        "SELECT * FROM products WHERE id = ? AND status = 'active'",
+    )
+    .bind(&body.product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    if product.seller_id != user_id {
+        return Err(AppError::Unauthorized);
+    }
+    if product.available() < 1 {
+        return Err(AppError::Conflict("Produto sem estoque disponível".into()));
+    }
+
+    let open_auctions = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM auctions WHERE product_id = ? AND status = 'OPEN'",
+    )
+    .bind(&body.product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if open_auctions > 0 {
+        return Err(AppError::AlreadyExists("Produto já está em leilão".into()));
+    }
+
+    // 3. Janela em blocos
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+
+    let terms = AuctionTerms {
+        kind:             body.kind,
+        start_price_sats: body.start_price_sats,
+        reserve_price_sats,
+        min_increment_sats,
+        decay_per_block_sats,
+        extension_blocks,
+    };
+
+    let auction = Auction::new(
+        product.id,
+        product.seller_id,
+        terms,
+        current_height,
+        current_height + body.duration_blocks,
+    );
+
+    sqlx::query(
+        "INSERT INTO auctions (id, product_id, seller_id, kind, start_price_sats, reserve_price_sats,
+         min_increment_sats, decay_per_block_sats, start_block, end_block, extension_blocks,
+         highest_bid_sats, status, winner_id, contract_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, NULL, NULL, ?, ?)",
+    )
+    .bind(&auction.id)
+    .bind(&auction.product_id)
+    .bind(&auction.seller_id)
+    .bind(auction.kind)
+    .bind(auction.start_price_sats)
+    .bind(auction.reserve_price_sats)
+    .bind(auction.min_increment_sats)
+    .bind(auction.decay_per_block_sats)
+    .bind(auction.start_block)
+    .bind(auction.end_block)
+    .bind(auction.extension_blocks)
+    .bind(auction.status)
+    .bind(&auction.created_at)
+    .bind(&auction.updated_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(auction))
+}
+
+// ─── GET /api/auctions ───────────────────────────────────────
+async fn list_auctions(
+    pool: web::Data<SqlitePool>,
+) -> Result<HttpResponse, AppError> {
+    let auctions = sqlx::query_as::<_, Auction>(
+        "SELECT * FROM auctions WHERE status = 'OPEN' ORDER BY end_block ASC",
+    )
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(auctions))
+}
+
+// ─── GET /api/auctions/:id ───────────────────────────────────
+async fn get_auction(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let auction = find_auction(pool.as_ref(), &path.into_inner()).await?;
+
+    let bids = sqlx::query_as::<_, Bid>(
+        "SELECT * FROM bids WHERE auction_id = ? ORDER BY amount_sats DESC, created_at ASC",
+    )
+    .bind(&auction.id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+    let current_price = auction.price_at(current_height);
+    let min_next_bid = (auction.kind == AuctionKind::English
+        && auction.status == AuctionStatus::Open)
+        .then(|| auction.min_next_bid());
+
+    Ok(HttpResponse::Ok().json(AuctionResponse {
+        auction,
+        bids,
+        current_price,
+        min_next_bid,
+    }))
+}
+
+// ─── POST /api/auctions/:id/bids ─────────────────────────────
+async fn place_bid(
+    pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<PlaceBidRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let auction = find_auction(pool.as_ref(), &path.into_inner()).await?;
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+
+    // 1. Leilão aberto e dentro da janela
+    if auction.status != AuctionStatus::Open || current_height >= auction.end_block {
+        return Err(AppError::Validation("Leilão encerrado".into()));
+    }
+
+    // 2. Licitante (não pode ser o vendedor)
+    let (bidder_id, bidder_pubkey) = sqlx::query_as::<_, (String, String)>(
+        "SELECT user_id, pubkey FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if bidder_id == auction.seller_id {
+        return Err(AppError::Validation("Você não pode dar lance no próprio leilão".into()));
+    }
+
+    // 3. Compromisso assinado com o valor do lance
+    verify_signature(
+        &bid_message(&auction.id, body.amount_sats),
+        &body.signature,
+        &bidder_pubkey,
+    )
+    .map_err(|_| AppError::InvalidSignature)?;
+
+    let bid = Bid::new(
+        auction.id.clone(),
+        bidder_id.clone(),
+        bidder_pubkey,
+        body.amount_sats,
+        body.signature.clone(),
+        current_height,
+    );
+
+    match auction.kind {
+        // Inglês: lance mínimo + extensão anti-sniping
+        AuctionKind::English => {
+            let min_bid = auction.min_next_bid();
+            if body.amount_sats < min_bid {
+                return Err(AppError::Validation(format!(
+                    "Lance mínimo é {} sats",
+                    min_bid
+                )));
+            }
+
+            let end_block = auction.end_block_after_bid(current_height);
+
+            // Só grava se ninguém deu lance desde a leitura
+            let affected = sqlx::query(
+                "UPDATE auctions SET highest_bid_sats = ?, end_block = ?, updated_at = ?
+                 WHERE id = ? AND status = 'OPEN' AND highest_bid_sats IS ?",
+            )
+            .bind(body.amount_sats)
+            .bind(end_block)
+            .bind(Utc::now().to_rfc3339())
+            .bind(&auction.id)
+            .bind(auction.highest_bid_sats)
+            .execute(pool.as_ref())
+            .await?
+            .rows_affected();
+
+            if affected == 0 {
+                return Err(AppError::Conflict("Outro lance foi registrado, tente novamente".into()));
+            }
+
+            insert_bid(pool.as_ref(), &bid).await?;
+
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "bid":       bid,
+                "end_block": end_block,
+                "extended":  end_block != auction.end_block,
+            })))
+        }
+
+        // Holandês: primeiro lance ≥ preço corrente leva pelo preço corrente
+        AuctionKind::Dutch => {
+            let price = auction.price_at(current_height);
+            if body.amount_sats < price {
+                return Err(AppError::Validation(format!(
+                    "Preço atual é {} sats",
+                    price
+                )));
+            }
+
+            // Sem estoque para reservar a premiação falha e o leilão
+            // termina UNSOLD — o lance não é registrado
+            let contract = award_auction(pool.as_ref(), hub.as_ref(), &auction, &bidder_id, price).await?;
+            insert_bid(pool.as_ref(), &bid).await?;
+
+            Ok(HttpResponse::Created().json(serde_json::json!({
+                "bid":      bid,
+                "price":    price,
+                "contract": contract,
+            })))
+        }
+    }
+}
+
+// ─── POST /api/auctions/:id/settle ───────────────────────────
+async fn settle(
+    pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let auction = find_auction(pool.as_ref(), &path.into_inner()).await?;
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+
+    if auction.status != AuctionStatus::Open {
+        return Err(AppError::Validation("Leilão já foi encerrado".into()));
+    }
+    if current_height < auction.end_block {
+        return Err(AppError::Validation(format!(
+            "Leilão encerra no bloco {}",
+            auction.end_block
+        )));
+    }
+
+    let contract = settle_auction(pool.as_ref(), hub.as_ref(), &auction).await?;
+    let auction  = find_auction(pool.as_ref(), &auction.id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "auction":  auction,
+        "contract": contract,
+    })))
+}
+
+// ─── POST /api/auctions/:id/cancel ───────────────────────────
+async fn cancel_auction(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let auction = find_auction(pool.as_ref(), &path.into_inner()).await?;
+
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if user_id != auction.seller_id {
+        return Err(AppError::Unauthorized);
+    }
+
+    // Só sem lances — compromissos assinados não podem ser descartados
+    let affected = sqlx::query(
+        "UPDATE auctions SET status = 'CANCELLED', updated_at = ?
+         WHERE id = ? AND status = 'OPEN' AND highest_bid_sats IS NULL
+         AND NOT EXISTS (SELECT 1 FROM bids WHERE auction_id = auctions.id)",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(&auction.id)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Validation(
+            "Só é possível cancelar leilões abertos e sem lances".into(),
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Leilão cancelado" })))
+}
+
+// ─── Salvar lance ────────────────────────────────────────────
+async fn insert_bid(pool: &SqlitePool, bid: &Bid) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO bids (id, auction_id, bidder_id, bidder_pubkey, amount_sats, signature, block_height, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&bid.id)
+    .bind(&bid.auction_id)
+    .bind(&bid.bidder_id)
+    .bind(&bid.bidder_pubkey)
+    .bind(bid.amount_sats)
+    .bind(&bid.signature)
+    .bind(bid.block_height)
+    .bind(&bid.created_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
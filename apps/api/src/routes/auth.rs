@@ -1,30 +1,68 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use argon2::password_hash::{rand_core::OsRng, SaltString};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use sqlx::SqlitePool;
 use std::env;
 use chrono::Utc;
+use uuid::Uuid;
 
 use crate::models::user::{
     Claims, LoginRequest, LoginResponse, RegisterRequest, RegisterResponse, User, Wallet,
 };
 use crate::crypto::bip39::generate_mnemonic;
-use crate::crypto::keys::derive_keypair;
+use crate::crypto::keys::{derive_keypair, pubkey_to_address};
+use crate::crypto::signing::verify_signature;
+use crate::models::role::Role;
+use crate::models::challenge::{
+    register_message, AuthChallenge, ChallengePurpose, ChallengeRequest, ChallengeResponse,
+    VerifyChallengeRequest,
+};
+use crate::models::security::{validate_masterkey, RecoverRequest, SecurityEvent, SecurityEventKind};
+use crate::models::session::{RefreshRequest, Session, SessionResponse, TokenPair};
+use crate::blockchain::roles::{grant_role, load_roles};
+use crate::blockchain::security::{
+    check_login_lockout, check_recovery_limit, clear_login_failures, record_login_failure,
+    record_security_event,
+};
+use crate::blockchain::two_factor::{check_second_factor, SecondFactor};
+use crate::blockchain::sessions::{
+    access_token_minutes, auth_challenge_seconds, create_session, generate_refresh_token,
+    hash_refresh_token, refresh_token_days, revoke_all_sessions, revoke_session, rotate_session,
+    session_for_refresh,
+};
 use crate::errors::AppError;
+use crate::middleware::rate_limit::limited;
+use crate::models::rate_limit::LimitedRoute;
+use crate::middleware::client_ip::client_ip;
+use crate::routes::two_factor;
 
 // ─── Configuração das rotas ──────────────────────────────────
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/auth")
-            .route("/register", web::post().to(register))
-            .route("/login",    web::post().to(login)),
+            .service(limited("/register",  LimitedRoute::Register).route(web::post().to(register)))
+            .service(limited("/login",     LimitedRoute::Login).route(web::post().to(login)))
+            .service(limited("/challenge", LimitedRoute::Auth).route(web::post().to(challenge)))
+            .service(limited("/verify",    LimitedRoute::Auth).route(web::post().to(verify)))
+            .service(limited("/recover",   LimitedRoute::Auth).route(web::post().to(recover)))
+            .service(limited("/refresh",   LimitedRoute::Auth).route(web::post().to(refresh)))
+            .route("/logout",        web::post().to(logout))
+            .route("/logout-all",    web::post().to(logout_all))
+            .route("/sessions",      web::get().to(list_sessions))
+            .route("/sessions/{id}", web::delete().to(revoke_session_by_id))
+            .route("/2fa",              web::get().to(two_factor::status))
+            .service(limited("/2fa/setup",        LimitedRoute::TwoFactor).route(web::post().to(two_factor::setup)))
+            .service(limited("/2fa/enable",       LimitedRoute::TwoFactor).route(web::post().to(two_factor::enable)))
+            .service(limited("/2fa/disable",      LimitedRoute::TwoFactor).route(web::post().to(two_factor::disable)))
+            .service(limited("/2fa/backup-codes", LimitedRoute::TwoFactor).route(web::post().to(two_factor::regenerate_backup_codes))),
     );
 }
 
 // ─── POST /api/auth/register ─────────────────────────────────
 async fn register(
     pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
     body: web::Json<RegisterRequest>,
 ) -> Result<HttpResponse, AppError> {
 
@@ -40,26 +78,53 @@ async fn register(
         return Err(AppError::AlreadyExists("Username já está em uso".into()));
     }
 
-    // 2. Gerar hash da masterkey com Argon2
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let password_hash = argon2
-        .hash_password(body.masterkey.as_bytes(), &salt)
-        .map_err(|e| AppError::Internal(e.to_string()))?
-        .to_string();
+    // 2. Chaves: pubkey do cliente (padrão) ou geradas aqui (modo dev)
+    let (password_hash, pubkey, address, mnemonic) = match (&body.pubkey, &body.signature) {
+        (Some(pubkey), Some(signature)) => {
+            let pubkey = pubkey.trim().to_lowercase();
 
-    // 3. Gerar 12 palavras (mnemônico BIP-39 simulado)
-    let mnemonic = generate_mnemonic();
+            // Prova de posse: assinatura de username + pubkey com a chave privada
+            verify_signature(&register_message(&body.username, &pubkey), signature, &pubkey)
+                .map_err(|_| AppError::InvalidSignature)?;
 
-    // 4. Derivar par de chaves a partir do mnemônico + masterkey
-    let (pubkey, address) = derive_keypair(&mnemonic, &body.masterkey)
-        .map_err(|e| AppError::Internal(e.to_string()))?;
+            // Sem masterkey no servidor — login só por desafio assinado
+            let address = pubkey_to_address(&pubkey);
+            (String::new(), pubkey, address, None)
+        }
+        (None, None) if server_keygen_enabled() => {
+            let masterkey = body.masterkey.as_deref().ok_or_else(|| {
+                AppError::Validation("Informe a masterkey".into())
+            })?;
+
+            // Hash da masterkey com Argon2
+            let salt = SaltString::generate(&mut OsRng);
+            let password_hash = Argon2::default()
+                .hash_password(masterkey.as_bytes(), &salt)
+                .map_err(|e| AppError::Internal(e.to_string()))?
+                .to_string();
+
+            // 12 palavras (mnemônico BIP-39 simulado) + derivação do par de chaves.
+            // A masterkey é a passphrase: sem ela as palavras não recriam a chave,
+            // então contas de dev não passam por /recover.
+            let mnemonic = generate_mnemonic();
+            let (pubkey, address) = derive_keypair(&mnemonic, masterkey)
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+
+            (password_hash, pubkey, address, Some(mnemonic))
+        }
+        _ => {
+            return Err(AppError::Validation(
+                "Cadastro exige pubkey e signature (chaves geradas no cliente)".into(),
+            ))
+        }
+    };
 
-    // 5. Verificar se endereço já existe
+    // 3. Verificar se endereço / chave já existem
     let addr_exists = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM wallets WHERE address = ?",
+        "SELECT COUNT(*) FROM wallets WHERE address = ? OR pubkey = ?",
     )
     .bind(&address)
+    .bind(&pubkey)
     .fetch_one(pool.as_ref())
     .await?;
 
@@ -67,7 +132,7 @@ async fn register(
         return Err(AppError::AlreadyExists("Endereço já existe".into()));
     }
 
-    // 6. Salvar usuário no banco
+    // 4. Salvar usuário no banco
     let user = User::new(body.username.clone(), password_hash);
     sqlx::query(
         "INSERT INTO users (id, username, password, created_at) VALUES (?, ?, ?, ?)",
@@ -79,7 +144,7 @@ async fn register(
     .execute(pool.as_ref())
     .await?;
 
-    // 7. Salvar carteira no banco
+    // 5. Salvar carteira no banco
     let wallet = Wallet::new(user.id.clone(), address.clone(), pubkey.clone());
     sqlx::query(
         "INSERT INTO wallets (id, user_id, address, pubkey, created_at) VALUES (?, ?, ?, ?, ?)",
@@ -92,33 +157,83 @@ async fn register(
     .execute(pool.as_ref())
     .await?;
 
-    // 8. Gerar JWT
-    let token = generate_token(&user.id, &user.username, &address)?;
+    // 6. Papéis padrão (user + seller)
+    for role in Role::defaults() {
+        grant_role(pool.as_ref(), &user.id, role, None, "cadastro").await?;
+    }
+
+    // 7. Abrir sessão (access + refresh token)
+    let tokens = start_session(pool.as_ref(), &req, &user.id, &user.username, &address).await?;
 
     Ok(HttpResponse::Created().json(RegisterResponse {
-        token,
+        tokens,
         address,
         pubkey,
-        mnemonic, // exibido UMA vez — nunca armazenado no servidor
+        mnemonic, // só no modo dev — exibido UMA vez, nunca armazenado
     }))
 }
 
 // ─── POST /api/auth/login ────────────────────────────────────
 async fn login(
     pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
     body: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, AppError> {
 
+    // 1. Bloqueado por falhas seguidas deste IP → 429 antes de gastar Argon2
+    let ip_address = client_ip(&req);
+    check_login_lockout(pool.as_ref(), &body.username, ip_address.as_deref()).await?;
+
+    // 2. Masterkey e segundo fator; falhas contam para o bloqueio
+    //    (pedido sem código no primeiro passo do 2FA não conta)
+    let (user, wallet) = match check_credentials(pool.as_ref(), &req, &body).await {
+        Ok(found) => found,
+        Err(e) => {
+            let failed = match e {
+                AppError::InvalidCredentials => true,
+                AppError::TwoFactorRequired  => body.totp_code.is_some(),
+                _ => false,
+            };
+            if failed {
+                record_login_failure(pool.as_ref(), &body.username, ip_address.as_deref()).await?;
+            }
+            return Err(e);
+        }
+    };
+    clear_login_failures(pool.as_ref(), &body.username, ip_address.as_deref()).await?;
+
+    // 3. Abrir sessão com os papéis atuais
+    let tokens = start_session(pool.as_ref(), &req, &user.id, &user.username, &wallet.address).await?;
+    let roles = load_roles(pool.as_ref(), &user.id).await?;
+
+    Ok(HttpResponse::Ok().json(LoginResponse {
+        tokens,
+        address: wallet.address,
+        username: user.username,
+        roles,
+    }))
+}
+
+// ─── Conferir masterkey (+ 2FA) ──────────────────────────────
+async fn check_credentials(
+    pool: &SqlitePool,
+    req:  &HttpRequest,
+    body: &LoginRequest,
+) -> Result<(User, Wallet), AppError> {
+
     // 1. Buscar usuário
     let user = sqlx::query_as::<_, User>(
         "SELECT * FROM users WHERE username = ?",
     )
     .bind(&body.username)
-    .fetch_optional(pool.as_ref())
+    .fetch_optional(pool)
     .await?
     .ok_or(AppError::InvalidCredentials)?;
 
-    // 2. Verificar masterkey
+    // 2. Verificar masterkey (contas com chave do cliente não têm — só desafio)
+    if user.password.is_empty() {
+        return Err(AppError::InvalidCredentials);
+    }
     let parsed_hash = PasswordHash::new(&user.password)
         .map_err(|e| AppError::Internal(e.to_string()))?;
 
@@ -131,35 +246,441 @@ async fn login(
         "SELECT * FROM wallets WHERE user_id = ?",
     )
     .bind(&user.id)
-    .fetch_one(pool.as_ref())
+    .fetch_one(pool)
     .await?;
 
-    // 4. Gerar JWT
-    let token = generate_token(&user.id, &user.username, &wallet.address)?;
+    // 4. Segundo fator, se ativo
+    second_factor(pool, req, &wallet, body.totp_code.as_deref()).await?;
+
+    Ok((user, wallet))
+}
+
+// ─── POST /api/auth/challenge ────────────────────────────────
+// Primeiro passo do login sem masterkey: devolve o texto que o
+// cliente assina com a chave da carteira. A resposta é a mesma
+// para carteiras inexistentes — o endpoint não revela cadastros.
+async fn challenge(
+    pool: web::Data<SqlitePool>,
+    body: web::Json<ChallengeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let address = body.address.trim();
+    if address.is_empty() || address.len() > 64 {
+        return Err(AppError::Validation("Endereço inválido".into()));
+    }
+
+    // Desafios vencidos não servem mais para nada
+    sqlx::query("DELETE FROM auth_challenges WHERE expires_at < ?")
+        .bind(Utc::now().to_rfc3339())
+        .execute(pool.as_ref())
+        .await?;
+
+    let challenge = AuthChallenge::new(address.to_string(), body.purpose, auth_challenge_seconds());
+
+    sqlx::query(
+        "INSERT INTO auth_challenges (id, address, purpose, nonce, message, expires_at, used_at, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, NULL, ?)",
+    )
+    .bind(&challenge.id)
+    .bind(&challenge.address)
+    .bind(challenge.purpose)
+    .bind(&challenge.nonce)
+    .bind(&challenge.message)
+    .bind(&challenge.expires_at)
+    .bind(&challenge.created_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(ChallengeResponse {
+        challenge_id: challenge.id,
+        message:      challenge.message,
+        expires_at:   challenge.expires_at,
+    }))
+}
+
+// ─── POST /api/auth/verify ───────────────────────────────────
+// Segundo passo: assinatura secp256k1 do desafio com a pubkey da
+// carteira.
+async fn verify(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<VerifyChallengeRequest>,
+) -> Result<HttpResponse, AppError> {
+
+    // 1. Consumir o desafio de login e achar a carteira
+    let (challenge, wallet) =
+        consume_challenge(pool.as_ref(), &body.challenge_id, ChallengePurpose::Login).await?;
+
+    // 2. Assinatura do texto exato do desafio
+    verify_signature(&challenge.message, &body.signature, &wallet.pubkey)
+        .map_err(|_| AppError::InvalidSignature)?;
+
+    // 3. Segundo fator, se ativo
+    second_factor(pool.as_ref(), &req, &wallet, body.totp_code.as_deref()).await?;
+
+    let username = sqlx::query_scalar::<_, String>("SELECT username FROM users WHERE id = ?")
+        .bind(&wallet.user_id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    // 4. Abrir sessão
+    let tokens = start_session(pool.as_ref(), &req, &wallet.user_id, &username, &wallet.address).await?;
+    let roles = load_roles(pool.as_ref(), &wallet.user_id).await?;
 
     Ok(HttpResponse::Ok().json(LoginResponse {
-        token,
+        tokens,
         address: wallet.address,
-        username: user.username,
+        username,
+        roles,
     }))
 }
 
-// ─── Gerar JWT ───────────────────────────────────────────────
-fn generate_token(
+// ─── POST /api/auth/recover ──────────────────────────────────
+// Esqueceu a masterkey mas tem as 12 palavras: o cliente re-deriva a
+// chave da carteira, assina um desafio RECOVER e define uma nova
+// masterkey. O mnemônico nunca sai do cliente. Todas as sessões
+// abertas caem (a credencial antiga pode ter vazado).
+async fn recover(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<RecoverRequest>,
+) -> Result<HttpResponse, AppError> {
+    let ip_address = client_ip(&req);
+
+    validate_masterkey(&body.new_masterkey).map_err(AppError::Validation)?;
+
+    // 1. Limite por IP antes de qualquer consulta ao desafio
+    check_recovery_limit(pool.as_ref(), None, ip_address.as_deref()).await?;
+
+    let address = sqlx::query_scalar::<_, String>("SELECT address FROM auth_challenges WHERE id = ?")
+        .bind(&body.challenge_id)
+        .fetch_optional(pool.as_ref())
+        .await?;
+
+    // 2. Limite por carteira (ataques distribuídos entre IPs)
+    check_recovery_limit(pool.as_ref(), address.as_deref(), None).await?;
+
+    // 3. Desafio RECOVER + assinatura com a chave re-derivada
+    //    e, com 2FA ativo, código do app ou de recuperação — a chave da
+    //    carteira sozinha não dispensa o segundo fator
+    let proved = match consume_challenge(pool.as_ref(), &body.challenge_id, ChallengePurpose::Recover).await {
+        Ok((challenge, wallet)) => match verify_signature(&challenge.message, &body.signature, &wallet.pubkey) {
+            Ok(_)  => second_factor(pool.as_ref(), &req, &wallet, body.totp_code.as_deref())
+                .await
+                .map(|_| wallet),
+            Err(_) => Err(AppError::InvalidSignature),
+        },
+        Err(e) => Err(e),
+    };
+
+    let wallet = match proved {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            record_security_event(pool.as_ref(), &SecurityEvent::new(
+                None,
+                address,
+                SecurityEventKind::RecoveryFailed,
+                ip_address,
+                Some(e.to_string()),
+            ))
+            .await?;
+            return Err(e);
+        }
+    };
+
+    // 4. Nova masterkey (Argon2) e queda das sessões antigas
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(body.new_masterkey.as_bytes(), &salt)
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .to_string();
+
+    sqlx::query("UPDATE users SET password = ? WHERE id = ?")
+        .bind(&password_hash)
+        .bind(&wallet.user_id)
+        .execute(pool.as_ref())
+        .await?;
+
+    let revoked = revoke_all_sessions(pool.as_ref(), &wallet.user_id).await?;
+
+    record_security_event(pool.as_ref(), &SecurityEvent::new(
+        Some(wallet.user_id.clone()),
+        Some(wallet.address.clone()),
+        SecurityEventKind::RecoverySucceeded,
+        ip_address,
+        Some(format!("{} sessão(ões) encerrada(s)", revoked)),
+    ))
+    .await?;
+
+    // 5. Nova sessão com a credencial recém-definida
+    let username = sqlx::query_scalar::<_, String>("SELECT username FROM users WHERE id = ?")
+        .bind(&wallet.user_id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    let tokens = start_session(pool.as_ref(), &req, &wallet.user_id, &username, &wallet.address).await?;
+    let roles = load_roles(pool.as_ref(), &wallet.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(LoginResponse {
+        tokens,
+        address: wallet.address,
+        username,
+        roles,
+    }))
+}
+
+// ─── POST /api/auth/refresh ──────────────────────────────────
+// Troca o refresh token por um novo par; o anterior deixa de valer
+// e, se reaparecer, derruba a sessão (indício de vazamento).
+async fn refresh(
+    pool: web::Data<SqlitePool>,
+    body: web::Json<RefreshRequest>,
+) -> Result<HttpResponse, AppError> {
+    let session = session_for_refresh(pool.as_ref(), &body.refresh_token).await?;
+
+    let (username, address) = sqlx::query_as::<_, (String, String)>(
+        "SELECT u.username, w.address FROM users u JOIN wallets w ON w.user_id = u.id WHERE u.id = ?",
+    )
+    .bind(&session.user_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    // Papéis recarregados: concessões e revogações entram no novo token
+    let roles = load_roles(pool.as_ref(), &session.user_id).await?;
+    let jti = Uuid::new_v4().to_string();
+    let refresh_token = generate_refresh_token();
+
+    rotate_session(pool.as_ref(), &session, &refresh_token, &jti).await?;
+    let token = generate_token(&session.user_id, &username, &address, roles, &jti, &session.id)?;
+
+    Ok(HttpResponse::Ok().json(TokenPair {
+        token,
+        refresh_token,
+        expires_in: access_token_minutes() * 60,
+    }))
+}
+
+// ─── POST /api/auth/logout ───────────────────────────────────
+// Encerra a sessão do token usado; o access token é revogado já.
+async fn logout(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    revoke_session(pool.as_ref(), &claims.sid, &claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Sessão encerrada" })))
+}
+
+// ─── POST /api/auth/logout-all ───────────────────────────────
+// Sair de todos os dispositivos, inclusive o atual.
+async fn logout_all(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let revoked = revoke_all_sessions(pool.as_ref(), &claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message":          "Todas as sessões foram encerradas",
+        "sessions_revoked": revoked,
+    })))
+}
+
+// ─── GET /api/auth/sessions ──────────────────────────────────
+// Sessões ativas do usuário, mais recentes primeiro.
+async fn list_sessions(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let sessions = sqlx::query_as::<_, Session>(
+        "SELECT * FROM sessions
+         WHERE user_id = ? AND revoked_at IS NULL AND expires_at > ?
+         ORDER BY last_used_at DESC",
+    )
+    .bind(&claims.sub)
+    .bind(Utc::now().to_rfc3339())
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    let sessions: Vec<SessionResponse> = sessions
+        .into_iter()
+        .map(|session| SessionResponse {
+            current: session.id == claims.sid,
+            session,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+// ─── DELETE /api/auth/sessions/:id ───────────────────────────
+// Encerra outra sessão (ex: dispositivo perdido).
+async fn revoke_session_by_id(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    if !revoke_session(pool.as_ref(), &path.into_inner(), &claims.sub).await? {
+        return Err(AppError::NotFound("Sessão não encontrada".into()));
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Sessão encerrada" })))
+}
+
+// ─── Consumir desafio ────────────────────────────────────────
+// Válido, da finalidade certa e ainda não usado; o consumo vem
+// antes da verificação, então cada nonce admite uma tentativa.
+async fn consume_challenge(
+    pool:         &SqlitePool,
+    challenge_id: &str,
+    purpose:      ChallengePurpose,
+) -> Result<(AuthChallenge, Wallet), AppError> {
+    let challenge = sqlx::query_as::<_, AuthChallenge>(
+        "SELECT * FROM auth_challenges WHERE id = ?",
+    )
+    .bind(challenge_id)
+    .fetch_optional(pool)
+    .await?
+    .filter(|c| c.purpose == purpose && c.is_valid(Utc::now()))
+    .ok_or(AppError::InvalidCredentials)?;
+
+    // Guardado contra uso concorrente
+    let consumed = sqlx::query(
+        "UPDATE auth_challenges SET used_at = ? WHERE id = ? AND used_at IS NULL",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(&challenge.id)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if consumed == 0 {
+        return Err(AppError::InvalidCredentials);
+    }
+
+    let wallet = sqlx::query_as::<_, Wallet>("SELECT * FROM wallets WHERE address = ?")
+        .bind(&challenge.address)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(AppError::InvalidCredentials)?;
+
+    Ok((challenge, wallet))
+}
+
+// ─── Segundo fator (login e recuperação) ─────────────────────
+// Senha ou assinatura já conferidas; com 2FA ativo falta o código
+// do app ou um de recuperação (uso registrado como evento).
+async fn second_factor(
+    pool:   &SqlitePool,
+    req:    &HttpRequest,
+    wallet: &Wallet,
+    code:   Option<&str>,
+) -> Result<(), AppError> {
+    if check_second_factor(pool, &wallet.user_id, code, true).await? == SecondFactor::BackupCode {
+        let ip_address = client_ip(req);
+
+        record_security_event(pool, &SecurityEvent::new(
+            Some(wallet.user_id.clone()),
+            Some(wallet.address.clone()),
+            SecurityEventKind::BackupCodeUsed,
+            ip_address,
+            None,
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+// ─── Geração de chaves no servidor (modo dev) ────────────────
+// Com `DEV_SERVER_KEYGEN=true` o cadastro por masterkey continua
+// disponível: o servidor gera o mnemônico e o devolve. Em produção
+// o cliente gera as chaves e o servidor nunca vê a seed.
+fn server_keygen_enabled() -> bool {
+    env::var("DEV_SERVER_KEYGEN")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+// ─── Abrir sessão ────────────────────────────────────────────
+// Cria a sessão com o hash do refresh token e emite o primeiro
+// access token dela (claims `jti` e `sid`).
+async fn start_session(
+    pool:     &SqlitePool,
+    req:      &HttpRequest,
     user_id:  &str,
     username: &str,
     address:  &str,
-) -> Result<String, AppError> {
-    let expiry_hours: i64 = env::var("JWT_EXPIRY_HOURS")
-        .unwrap_or_else(|_| "72".into())
-        .parse()
-        .unwrap_or(72);
+) -> Result<TokenPair, AppError> {
+    let roles = load_roles(pool, user_id).await?;
+    let jti = Uuid::new_v4().to_string();
+    let refresh_token = generate_refresh_token();
+
+    let user_agent = req
+        .headers()
+        .get(actix_web::http::header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|ua| ua.chars().take(200).collect::<String>());
+    let ip_address = client_ip(req);
+
+    let session = Session::new(
+        user_id.to_string(),
+        hash_refresh_token(&refresh_token),
+        jti.clone(),
+        user_agent,
+        ip_address,
+        refresh_token_days(),
+    );
+    create_session(pool, &session).await?;
 
+    let token = generate_token(user_id, username, address, roles, &jti, &session.id)?;
+
+    Ok(TokenPair {
+        token,
+        refresh_token,
+        expires_in: access_token_minutes() * 60,
+    })
+}
+
+// ─── Gerar JWT (access token curto) ──────────────────────────
+fn generate_token(
+    user_id:    &str,
+    username:   &str,
+    address:    &str,
+    roles:      Vec<Role>,
+    jti:        &str,
+    session_id: &str,
+) -> Result<String, AppError> {
     let claims = Claims {
         sub:      user_id.to_string(),
         username: username.to_string(),
         address:  address.to_string(),
-        exp:      (Utc::now().timestamp() + expiry_hours * 3600) as usize,
+        roles,
+        jti:      jti.to_string(),
+        sid:      session_id.to_string(),
+        exp:      (Utc::now().timestamp() + access_token_minutes() * 60) as usize,
     };
 
     let secret = env::var("JWT_SECRET")
@@ -171,4 +692,18 @@ fn generate_token(
         &EncodingKey::from_secret(secret.as_bytes()),
     )
     .map_err(|e| AppError::Internal(e.to_string()))
-}
\ No newline at end of file
+}
+
+// ─── Validar JWT ─────────────────────────────────────────────
+pub fn decode_token(token: &str) -> Result<Claims, AppError> {
+    let secret = env::var("JWT_SECRET")
+        .unwrap_or_else(|_| "secret".into());
+
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| AppError::InvalidToken)
+}
@@ -0,0 +1,231 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::category::{
+    build_tree, validate_slug, Category, CategoryFilters, CreateCategoryRequest,
+    UpdateCategoryRequest,
+};
+use crate::blockchain::categories::is_in_subtree;
+use crate::blockchain::roles::require_role;
+use crate::models::role::Role;
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/categories")
+            .route("",      web::get().to(list_categories))
+            .route("",      web::post().to(create_category))
+            .route("/{id}", web::put().to(update_category))
+            .route("/{id}", web::delete().to(delete_category)),
+    );
+}
+
+// ─── GET /api/categories ─────────────────────────────────────
+// Árvore completa; `?all=true` inclui as inativas.
+async fn list_categories(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<CategoryFilters>,
+) -> Result<HttpResponse, AppError> {
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT * FROM categories WHERE ?1 = 1 OR active = 1",
+    )
+    .bind(filters.all.unwrap_or(false))
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(build_tree(categories)))
+}
+
+// ─── POST /api/categories (admin) ────────────────────────────
+async fn create_category(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateCategoryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    require_role(pool.as_ref(), &claims, Role::Admin).await?;
+
+    validate_slug(&body.slug).map_err(AppError::Validation)?;
+    ensure_slug_free(pool.as_ref(), &body.slug, "").await?;
+    if body.name.trim().is_empty() {
+        return Err(AppError::Validation("Nome é obrigatório".into()));
+    }
+
+    if let Some(ref parent_id) = body.parent_id {
+        ensure_exists(pool.as_ref(), parent_id).await?;
+    }
+
+    let category = Category::new(
+        body.parent_id.clone(),
+        body.slug.clone(),
+        body.name.trim().to_string(),
+        body.position.unwrap_or(0),
+    );
+
+    sqlx::query(
+        "INSERT INTO categories (id, parent_id, slug, name, position, active, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&category.id)
+    .bind(&category.parent_id)
+    .bind(&category.slug)
+    .bind(&category.name)
+    .bind(category.position)
+    .bind(category.active)
+    .bind(&category.created_at)
+    .bind(&category.updated_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(category))
+}
+
+// ─── PUT /api/categories/:id (admin) ─────────────────────────
+async fn update_category(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<UpdateCategoryRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    require_role(pool.as_ref(), &claims, Role::Admin).await?;
+
+    let id = path.into_inner();
+    let mut category = ensure_exists(pool.as_ref(), &id).await?;
+
+    // Validar tudo antes de gravar
+    if let Some(ref slug) = body.slug {
+        validate_slug(slug).map_err(AppError::Validation)?;
+        ensure_slug_free(pool.as_ref(), slug, &id).await?;
+        category.slug = slug.clone();
+    }
+    if let Some(ref name) = body.name {
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Nome é obrigatório".into()));
+        }
+        category.name = name.trim().to_string();
+    }
+    if let Some(ref parent_id) = body.parent_id {
+        if parent_id.is_empty() {
+            category.parent_id = None;
+        } else {
+            ensure_exists(pool.as_ref(), parent_id).await?;
+            if is_in_subtree(pool.as_ref(), &id, parent_id).await? {
+                return Err(AppError::Validation(
+                    "Uma categoria não pode ficar dentro dela mesma".into(),
+                ));
+            }
+            category.parent_id = Some(parent_id.clone());
+        }
+    }
+    if let Some(position) = body.position {
+        category.position = position;
+    }
+    if let Some(active) = body.active {
+        category.active = active;
+    }
+    category.updated_at = Utc::now().to_rfc3339();
+
+    sqlx::query(
+        "UPDATE categories SET parent_id = ?, slug = ?, name = ?, position = ?, active = ?, updated_at = ?
+         WHERE id = ?",
+    )
+    .bind(&category.parent_id)
+    .bind(&category.slug)
+    .bind(&category.name)
+    .bind(category.position)
+    .bind(category.active)
+    .bind(&category.updated_at)
+    .bind(&id)
+    .execute(pool.as_ref())
+    .await?;
+
+    // Produtos guardam o slug desnormalizado
+    sqlx::query("UPDATE products SET category = ? WHERE category_id = ? AND category != ?")
+        .bind(&category.slug)
+        .bind(&id)
+        .bind(&category.slug)
+        .execute(pool.as_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(category))
+}
+
+// ─── DELETE /api/categories/:id (admin) ──────────────────────
+// Só categorias vazias; com produtos ou filhas, desative (`active: false`).
+async fn delete_category(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    require_role(pool.as_ref(), &claims, Role::Admin).await?;
+
+    let id = path.into_inner();
+    ensure_exists(pool.as_ref(), &id).await?;
+
+    let in_use = sqlx::query_scalar::<_, i64>(
+        "SELECT (SELECT COUNT(*) FROM products WHERE category_id = ?1)
+              + (SELECT COUNT(*) FROM categories WHERE parent_id = ?1)",
+    )
+    .bind(&id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if in_use > 0 {
+        return Err(AppError::Conflict(
+            "Categoria com produtos ou subcategorias — desative em vez de remover".into(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM categories WHERE id = ?")
+        .bind(&id)
+        .execute(pool.as_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Categoria removida" })))
+}
+
+// ─── Buscar categoria ou 404 ─────────────────────────────────
+async fn ensure_exists(pool: &SqlitePool, id: &str) -> Result<Category, AppError> {
+    sqlx::query_as::<_, Category>("SELECT * FROM categories WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Categoria não encontrada".into()))
+}
+
+// ─── Slug não usado por outra categoria ──────────────────────
+async fn ensure_slug_free(pool: &SqlitePool, slug: &str, except_id: &str) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM categories WHERE slug = ? AND id != ?",
+    )
+    .bind(slug)
+    .bind(except_id)
+    .fetch_one(pool)
+    .await?;
+
+    if exists > 0 {
+        return Err(AppError::AlreadyExists(format!("Slug '{}' já está em uso", slug)));
+    }
+
+    Ok(())
+}
@@ -1,25 +1,42 @@
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use sqlx::SqlitePool;
-use std::env;
 
 use crate::errors::AppError;
+use crate::middleware::rate_limit::limited;
+use crate::models::rate_limit::LimitedRoute;
 use crate::models::user::Claims;
+use crate::models::role::Role;
 use crate::models::contract::{
-    Contract, ContractEvent, ContractResponse, ContractSignature,
-    ContractState, CreateEscrowRequest, DisputeRequest, SignContractRequest,
+    Contract, ContractEvent, ContractFilters, ContractItemResponse, ContractListResponse,
+    ContractResponse, ContractSignature, ContractState, ContractSummaryQuery, CreateEscrowRequest,
+    DisputeRequest, FundContractRequest, SignContractRequest,
 };
-use crate::blockchain::pow::sha256_hex;
-use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::{
+    contract_stats, find_contract, fund_escrow, open_escrow, record_event, transition_contract,
+    user_contract_summary,
+};
+use crate::blockchain::revisions::contract_revision;
+use crate::blockchain::roles::require_role;
 use crate::crypto::signing::verify_signature;
+use crate::routes::shipping;
+use crate::routes::two_factor::require_totp_header;
 
 // ─── Configuração das rotas ──────────────────────────────────
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/contracts")
             .route("",              web::post().to(create_escrow))
+            .route("",              web::get().to(list_contracts))
+            .route("/stats",        web::get().to(get_stats))
+            .route("/summary",      web::get().to(get_summary))
             .route("/{id}",         web::get().to(get_contract))
-            .route("/{id}/sign",    web::post().to(sign_contract))
-            .route("/{id}/dispute", web::post().to(dispute_contract)),
+            .route("/{id}/item",    web::get().to(get_contract_item))
+            .route("/{id}/fund",    web::post().to(fund_contract))
+            .service(limited("/{id}/sign", LimitedRoute::TwoFactor).route(web::post().to(sign_contract)))
+            .route("/{id}/dispute", web::post().to(dispute_contract))
+            .route("/{id}/shipment",        web::post().to(shipping::create_shipment))
+            .route("/{id}/shipment",        web::get().to(shipping::get_shipment))
+            .route("/{id}/shipment/events", web::post().to(shipping::add_shipment_event)),
     );
 }
 
@@ -37,120 +54,150 @@ async fn create_escrow(
         .cloned()
         .ok_or(AppError::Unauthorized)?;
 
-    // 2. Buscar pubkey do comprador
-    let buyer_pubkey = sqlx::query_scalar::<_, String>(
-        "SELECT pubkey FROM wallets WHERE address = ?",
-    )
-    .bind(&claims.address)
-    .fetch_one(pool.as_ref())
-    .await?;
-
-    // 3. Verificar se produto existe
-    let product = sqlx::query!(
-        "SELECT id, title, description, price_sats, seller_id FROM products WHERE id = ? AND status = 'active'",
-        body.product_id
+    // 2. Escrow direto é pelo preço de lista — valores negociados via /api/offers
+    let price_sats = sqlx::query_scalar::<_, i64>(
+        "SELECT price_sats FROM products WHERE id = ? AND status = 'active'",
     )
+    .bind(&body.product_id)
     .fetch_optional(pool.as_ref())
     .await?
     .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
 
-    // 4. Validar que comprador não é o vendedor
-    let seller_address = sqlx::query_scalar::<_, String>(
-        "SELECT address FROM wallets WHERE user_id = ?",
+    if body.amount_sats != price_sats {
+        return Err(AppError::Validation(format!(
+            "Valor do escrow deve ser o preço do produto ({} sats); para negociar use /api/offers",
+            price_sats
+        )));
+    }
+
+    // 3. Abrir contrato escrow (reserva 1 unidade)
+    let contract = open_escrow(
+        pool.as_ref(),
+        &claims.address,
+        &body.product_id,
+        body.amount_sats,
+        1,
     )
-    .bind(&product.seller_id)
-    .fetch_one(pool.as_ref())
     .await?;
 
-    if seller_address == claims.address {
-        return Err(AppError::Validation("Você não pode comprar seu próprio produto".into()));
-    }
+    Ok(HttpResponse::Created().json(&contract))
+}
 
-    // 5. Calcular taxa do escrow
-    let escrow_fee_percent: f64 = env::var("ESCROW_FEE_PERCENT")
-        .unwrap_or_else(|_| "0.5".into())
-        .parse()
-        .unwrap_or(0.5);
+// ─── POST /api/contracts/:id/fund ────────────────────────────
+// Trava os fundos de escrows sem ordem (direto, leilão, oferta);
+// ordens podem usar /api/orders/:id/fund, que valida o mesmo.
+async fn fund_contract(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<FundContractRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let contract = find_contract(pool.as_ref(), &path.into_inner()).await?;
+    fund_escrow(pool.as_ref(), &contract, &claims.address, &body.lock_tx_id).await?;
+
+    let contract = find_contract(pool.as_ref(), &contract.id).await?;
+
+    Ok(HttpResponse::Ok().json(&contract))
+}
+
+// ─── GET /api/contracts ──────────────────────────────────────
+async fn list_contracts(
+    pool:    web::Data<SqlitePool>,
+    req:     HttpRequest,
+    filters: web::Query<ContractFilters>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
 
-    let fee_sats = (body.amount_sats as f64 * escrow_fee_percent / 100.0) as i64;
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
 
-    // 6. Buscar pubkey do vendedor
-    let seller_pubkey = sqlx::query_scalar::<_, String>(
-        "SELECT pubkey FROM wallets WHERE user_id = ?",
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
     )
-    .bind(&product.seller_id)
+    .bind(&claims.address)
     .fetch_one(pool.as_ref())
     .await?;
 
-    // 7. Pubkey do árbitro (fixo — PaperMarket)
-    let arbiter_pubkey = env::var("ARBITER_PUBKEY")
-        .unwrap_or_else(|_| "PAPERMARKET_ARB_MASTER_01".into());
-
-    // 8. Hash do item (SHA-256 do título + descrição)
-    let item_data = format!("{}{}", product.title, product.description);
-    let item_hash = sha256_hex(&item_data);
-
-    // 9. Altura atual e expiração (100 blocos)
-    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
-    let created_at_block = current_height;
-    let expires_at_block = current_height + 100;
-
-    // 10. Criar contrato
-    let contract = Contract::new(
-        body.product_id.clone(),
-        buyer_pubkey,
-        seller_pubkey,
-        arbiter_pubkey,
-        body.amount_sats,
-        fee_sats,
-        item_hash,
-        created_at_block,
-        expires_at_block,
+    // Montar filtro: papel específico ou qualquer parte do contrato
+    let party = match filters.role {
+        Some(role) => format!("{} = ?1", role.pubkey_column()),
+        None => "(buyer_pubkey = ?1 OR seller_pubkey = ?1 OR arbiter_pubkey = ?1)".into(),
+    };
+    let conditions = format!("{} AND (?2 IS NULL OR state = ?2)", party);
+
+    let total_query = format!("SELECT COUNT(*) FROM contracts WHERE {}", conditions);
+    let list_query = format!(
+        "SELECT * FROM contracts WHERE {} ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
+        conditions
     );
 
-    sqlx::query(
-        "INSERT INTO contracts (id, version, product_id, buyer_pubkey, seller_pubkey, arbiter_pubkey,
-         amount_sats, fee_sats, item_hash, state, created_at_block, expires_at_block,
-         lock_tx_id, release_tx_id, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, NULL, ?, ?)",
-    )
-    .bind(&contract.id)
-    .bind(&contract.version)
-    .bind(&contract.product_id)
-    .bind(&contract.buyer_pubkey)
-    .bind(&contract.seller_pubkey)
-    .bind(&contract.arbiter_pubkey)
-    .bind(contract.amount_sats)
-    .bind(contract.fee_sats)
-    .bind(&contract.item_hash)
-    .bind(&contract.state)
-    .bind(contract.created_at_block)
-    .bind(contract.expires_at_block)
-    .bind(&contract.created_at)
-    .bind(&contract.updated_at)
-    .execute(pool.as_ref())
-    .await?;
+    let total = sqlx::query_scalar::<_, i64>(&total_query)
+        .bind(&pubkey)
+        .bind(filters.state)
+        .fetch_one(pool.as_ref())
+        .await?;
 
-    // 11. Registrar evento CREATED
-    let event = ContractEvent::new(
-        contract.id.clone(),
-        "CREATED".into(),
-        Some(format!("Contrato criado pelo comprador. Expira no bloco {}.", expires_at_block)),
-    );
+    let contracts = sqlx::query_as::<_, Contract>(&list_query)
+        .bind(&pubkey)
+        .bind(filters.state)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool.as_ref())
+        .await?;
 
-    sqlx::query(
-        "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-         VALUES (?, ?, ?, ?, ?)",
+    Ok(HttpResponse::Ok().json(ContractListResponse {
+        contracts,
+        total,
+        page,
+    }))
+}
+
+// ─── GET /api/contracts/stats ────────────────────────────────
+async fn get_stats(
+    pool: web::Data<SqlitePool>,
+) -> Result<HttpResponse, AppError> {
+    let stats = contract_stats(pool.as_ref()).await?;
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+// ─── GET /api/contracts/summary ──────────────────────────────
+async fn get_summary(
+    pool:  web::Data<SqlitePool>,
+    req:   HttpRequest,
+    query: web::Query<ContractSummaryQuery>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let within_blocks = query.within_blocks.unwrap_or(10);
+    if within_blocks < 0 {
+        return Err(AppError::Validation("within_blocks não pode ser negativo".into()));
+    }
+
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
     )
-    .bind(&event.id)
-    .bind(&event.contract_id)
-    .bind(&event.event_type)
-    .bind(&event.description)
-    .bind(&event.created_at)
-    .execute(pool.as_ref())
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
     .await?;
 
-    Ok(HttpResponse::Created().json(&contract))
+    let summary = user_contract_summary(pool.as_ref(), &pubkey, within_blocks).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
 }
 
 // ─── GET /api/contracts/:id ──────────────────────────────────
@@ -176,7 +223,7 @@ async fn get_contract(
     .await?;
 
     let events = sqlx::query_as::<_, ContractEvent>(
-        "SELECT * FROM contract_events WHERE contract_id = ORDER BY created_at ASC",
+        "SELECT * FROM contract_events WHERE contract_id = ? ORDER BY created_at ASC",
     )
     .bind(&id)
     .fetch_all(pool.as_ref())
@@ -194,6 +241,33 @@ async fn get_contract(
     }))
 }
 
+// ─── GET /api/contracts/:id/item ─────────────────────────────
+// Texto e imagens da revisão em que o escrow foi aberto — é o que o
+// árbitro compara com o item entregue numa disputa.
+async fn get_contract_item(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let contract = find_contract(pool.as_ref(), &path.into_inner()).await?;
+    let revision = contract_revision(pool.as_ref(), &contract).await?;
+
+    let latest_revision = sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(revision), 0) FROM product_revisions WHERE product_id = ?",
+    )
+    .bind(&contract.product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ContractItemResponse {
+        hash_matches: revision.computed_item_hash() == contract.item_hash,
+        images:       revision.images(),
+        contract_id:  contract.id,
+        item_hash:    contract.item_hash,
+        revision,
+        latest_revision,
+    }))
+}
+
 // ─── POST /api/contracts/:id/sign ────────────────────────────
 async fn sign_contract(
     pool: web::Data<SqlitePool>,
@@ -210,22 +284,20 @@ async fn sign_contract(
     let id = path.into_inner();
 
     // Buscar contrato
-    let contract = sqlx::query_as::<_, Contract>(
-        "SELECT * FROM contracts WHERE id = ?",
-    )
-    .bind(&id)
-    .fetch_optional(pool.as_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Contrato não encontrado".into()))?;
+    let contract = find_contract(pool.as_ref(), &id).await?;
 
-    // Verificar estado
-    if contract.state == ContractState::Released.as_str()
-        || contract.state == ContractState::Refunded.as_str()
-    {
+    // Verificar estado — só contratos com fundos travados podem ser liberados
+    if contract.state.is_final() {
         return Err(AppError::InvalidContractState(
             "Contrato já foi finalizado".into(),
         ));
     }
+    if !contract.state.can_transition_to(ContractState::Released) {
+        return Err(AppError::InvalidContractState(format!(
+            "Contrato em estado {} não pode ser assinado — fundos ainda não travados",
+            contract.state
+        )));
+    }
 
     // Buscar pubkey do signatário
     let signer_pubkey = sqlx::query_scalar::<_, String>(
@@ -246,6 +318,11 @@ async fn sign_contract(
         return Err(AppError::Unauthorized);
     };
 
+    // Árbitro com papel revogado não decide mais disputas
+    if role == "arbiter" {
+        require_role(pool.as_ref(), &claims, Role::Arbiter).await?;
+    }
+
     // Verificar se já assinou
     let already_signed = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(*) FROM contract_signatures WHERE contract_id = ? AND role = ?",
@@ -263,6 +340,9 @@ async fn sign_contract(
     verify_signature(&id, &body.signature, &signer_pubkey)
         .map_err(|_| AppError::InvalidSignature)?;
 
+    // Assinatura decide para onde vão os fundos — com 2FA ativo, exige TOTP atual
+    require_totp_header(pool.as_ref(), &req, &claims).await?;
+
     // Salvar assinatura
     let sig = ContractSignature::new(
         id.clone(),
@@ -290,18 +370,7 @@ async fn sign_contract(
         "SIGNED".into(),
         Some(format!("Assinado pelo {}", role)),
     );
-
-    sqlx::query(
-        "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-         VALUES (?, ?, ?, ?, ?)",
-    )
-    .bind(&event.id)
-    .bind(&event.contract_id)
-    .bind(&event.event_type)
-    .bind(&event.description)
-    .bind(&event.created_at)
-    .execute(pool.as_ref())
-    .await?;
+    record_event(pool.as_ref(), &event).await?;
 
     // Verificar se atingiu 2/3 assinaturas → liberar fundos
     let sig_count = sqlx::query_scalar::<_, i64>(
@@ -312,32 +381,13 @@ async fn sign_contract(
     .await?;
 
     if sig_count >= 2 {
-        let now = chrono::Utc::now().to_rfc3339();
-
-        sqlx::query(
-            "UPDATE contracts SET state = 'RELEASED', updated_at = ? WHERE id = ?",
-        )
-        .bind(&now)
-        .bind(&id)
-        .execute(pool.as_ref())
-        .await?;
-
-        let release_event = ContractEvent::new(
-            id.clone(),
-            "RELEASED".into(),
-            Some("2/3 assinaturas atingidas. Fundos liberados ao vendedor.".into()),
-        );
-
-        sqlx::query(
-            "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-             VALUES (?, ?, ?, ?, ?)",
+        transition_contract(
+            pool.as_ref(),
+            &contract,
+            ContractState::Released,
+            None,
+            "2/3 assinaturas atingidas. Fundos liberados ao vendedor.".into(),
         )
-        .bind(&release_event.id)
-        .bind(&release_event.contract_id)
-        .bind(&release_event.event_type)
-        .bind(&release_event.description)
-        .bind(&release_event.created_at)
-        .execute(pool.as_ref())
         .await?;
     }
 
@@ -365,16 +415,10 @@ async fn dispute_contract(
     let id = path.into_inner();
 
     // Buscar contrato
-    let contract = sqlx::query_as::<_, Contract>(
-        "SELECT * FROM contracts WHERE id = ?",
-    )
-    .bind(&id)
-    .fetch_optional(pool.as_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Contrato não encontrado".into()))?;
+    let contract = find_contract(pool.as_ref(), &id).await?;
 
     // Só pode abrir disputa se estiver LOCKED
-    if contract.state != ContractState::Locked.as_str() {
+    if !contract.state.can_transition_to(ContractState::Disputed) {
         return Err(AppError::InvalidContractState(
             "Só é possível abrir disputa em contratos LOCKED".into(),
         ));
@@ -392,39 +436,19 @@ async fn dispute_contract(
         return Err(AppError::Unauthorized);
     }
 
-    let now = chrono::Utc::now().to_rfc3339();
-
     // Atualizar estado para DISPUTED
-    sqlx::query(
-        "UPDATE contracts SET state = 'DISPUTED', updated_at = ? WHERE id = ?",
+    transition_contract(
+        pool.as_ref(),
+        &contract,
+        ContractState::Disputed,
+        None,
+        format!("Disputa aberta: {}", body.reason),
     )
-    .bind(&now)
-    .bind(&id)
-    .execute(pool.as_ref())
-    .await?;
-
-    // Registrar evento DISPUTED
-    let event = ContractEvent::new(
-        id.clone(),
-        "DISPUTED".into(),
-        Some(format!("Disputa aberta: {}", body.reason)),
-    );
-
-    sqlx::query(
-        "INSERT INTO contract_events (id, contract_id, event_type, description, created_at)
-         VALUES (?, ?, ?, ?, ?)",
-    )
-    .bind(&event.id)
-    .bind(&event.contract_id)
-    .bind(&event.event_type)
-    .bind(&event.description)
-    .bind(&event.created_at)
-    .execute(pool.as_ref())
     .await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "message": "Disputa aberta. O árbitro irá analisar o caso.",
         "contract_id": id,
-        "state": "DISPUTED",
+        "state": ContractState::Disputed,
     })))
 }
\ No newline at end of file
@@ -3,10 +3,22 @@ use chrono::Utc;
 use sqlx::SqlitePool;
 
 use crate::errors::AppError;
+use crate::routes::media;
+use crate::blockchain::revisions::{list_revisions, snapshot_revision};
+use crate::blockchain::categories::{active_category, category_filter};
+use crate::blockchain::notifications::{alert_price_drop, alert_saved_searches};
+use crate::blockchain::moderation::{check_banned_terms, ensure_not_suspended};
+use crate::blockchain::roles::require_role;
 use crate::models::user::Claims;
+use crate::models::role::Role;
+use crate::ws::hub::Hub;
 use crate::models::product::{
-    CreateProductRequest, CreateReviewRequest, Product,
-    ProductFilters, ProductResponse, Review, UpdateProductRequest,
+    decode_cursor, encode_cursor, format_location, fts_match_expr, validate_condition,
+    validate_description, validate_location, validate_price, validate_title,
+    CreateProductRequest, CreateReviewRequest, FacetCount, Product, ProductFilters,
+    ProductListResponse, ProductListing, ProductResponse, ProductStatus, ReplyReviewRequest,
+    Review, ReviewFilters, ReviewListResponse, ReviewResponse, SearchFacets, SearchFilters,
+    SearchHit, SearchResponse, UpdateProductRequest,
 };
 
 // ─── Configuração das rotas ──────────────────────────────────
@@ -15,82 +27,229 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/marketplace")
             .route("/products",              web::get().to(list_products))
             .route("/products",              web::post().to(create_product))
+            .route("/products/search",       web::get().to(search_products))
             .route("/products/{id}",         web::get().to(get_product))
             .route("/products/{id}",         web::put().to(update_product))
             .route("/products/{id}",         web::delete().to(delete_product))
-            .route("/products/{id}/reviews", web::post().to(create_review)),
+            .route("/products/{id}/reviews", web::post().to(create_review))
+            .route("/products/{id}/reviews", web::get().to(list_reviews))
+            .route("/products/{id}/reviews/{review_id}/reply", web::post().to(reply_review))
+            .route("/products/{id}/revisions", web::get().to(get_revisions))
+            .route("/products/{id}/media",   web::post().to(media::upload_media))
+            .route("/products/{id}/media",   web::get().to(media::list_media))
+            .route("/products/{id}/media/{media_id}", web::delete().to(media::delete_media)),
     );
 }
 
 // ─── GET /api/marketplace/products ──────────────────────────
+// Paginação por cursor (created_at, id): páginas estáveis mesmo
+// com produtos sendo criados enquanto o cliente navega.
 async fn list_products(
     pool:    web::Data<SqlitePool>,
     filters: web::Query<ProductFilters>,
 ) -> Result<HttpResponse, AppError> {
-    let page  = filters.page.unwrap_or(1);
-    let limit = filters.limit.unwrap_or(20);
-    let offset = (page - 1) * limit;
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let search = filters.search.as_deref().and_then(fts_match_expr);
+
+    let cursor = match filters.cursor.as_deref() {
+        Some(raw) => Some(
+            decode_cursor(raw).ok_or_else(|| AppError::Validation("Cursor inválido".into()))?,
+        ),
+        None => None,
+    };
+    let (after_created_at, after_id) = cursor.unzip();
+
+    // ?1 = categoria, ?2 = condição, ?3/?4 = faixa de preço, ?5 = busca, ?6 = UF
+    let conditions = format!(
+        "p.status = 'active'
+         AND (?1 IS NULL OR {category})
+         AND (?2 IS NULL OR p.condition = ?2)
+         AND (?3 IS NULL OR p.price_sats >= ?3)
+         AND (?4 IS NULL OR p.price_sats <= ?4)
+         AND (?5 IS NULL OR p.id IN (SELECT product_id FROM products_fts WHERE products_fts MATCH ?5))
+         AND (?6 IS NULL OR p.state = upper(?6))",
+        category = category_filter("?1"),
+    );
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM products p WHERE {}", conditions),
+    )
+    .bind(&filters.category)
+    .bind(&filters.condition)
+    .bind(filters.min_price)
+    .bind(filters.max_price)
+    .bind(&search)
+    .bind(&filters.state)
+    .fetch_one(pool.as_ref())
+    .await?;
 
-    // Montar query dinâmica com filtros
-    let mut conditions = vec!["p.status = 'active'"];
-    let mut query = String::from(
-        "SELECT p.*, u.username as seller_username,
-                AVG(r.rating) as avg_rating,
-                COUNT(r.id) as review_count
+    // Um item a mais indica que existe próxima página
+    let mut products = sqlx::query_as::<_, ProductResponse>(&format!(
+        "SELECT p.*, u.username AS seller,
+                r.avg_rating, COALESCE(r.review_count, 0) AS review_count,
+                (SELECT m.thumb_sha256 FROM product_media m
+                 WHERE m.product_id = p.id AND m.kind = 'IMAGE'
+                 ORDER BY m.position ASC LIMIT 1) AS thumbnail
          FROM products p
-         LEFT JOIN users u ON p.seller_id = u.id
-         LEFT JOIN reviews r ON p.id = r.product_id
-         WHERE p.status = 'active'"
-    );
+         JOIN users u ON u.id = p.seller_id
+         LEFT JOIN (
+             SELECT product_id, AVG(rating) AS avg_rating, COUNT(*) AS review_count
+             FROM reviews WHERE hidden_at IS NULL GROUP BY product_id
+         ) r ON r.product_id = p.id
+         WHERE {}
+           AND (?7 IS NULL OR (p.created_at, p.id) < (?7, ?8))
+         ORDER BY p.created_at DESC, p.id DESC
+         LIMIT ?9",
+        conditions
+    ))
+    .bind(&filters.category)
+    .bind(&filters.condition)
+    .bind(filters.min_price)
+    .bind(filters.max_price)
+    .bind(&search)
+    .bind(&filters.state)
+    .bind(after_created_at)
+    .bind(after_id)
+    .bind(limit + 1)
+    .fetch_all(pool.as_ref())
+    .await?;
 
-    if filters.category.is_some() {
-        query.push_str(" AND p.category = ?");
-    }
-    if filters.condition.is_some() {
-        query.push_str(" AND p.condition = ?");
-    }
-    if filters.min_price.is_some() {
-        query.push_str(" AND p.price_sats >= ?");
-    }
-    if filters.max_price.is_some() {
-        query.push_str(" AND p.price_sats <= ?");
-    }
-    if filters.search.is_some() {
-        query.push_str(" AND (p.title LIKE ? OR p.description LIKE ?)");
-    }
+    let next_cursor = if products.len() as i64 > limit {
+        products.truncate(limit as usize);
+        products
+            .last()
+            .map(|last| encode_cursor(&last.product.created_at, &last.product.id))
+    } else {
+        None
+    };
+
+    Ok(HttpResponse::Ok().json(ProductListResponse {
+        products,
+        total,
+        next_cursor,
+    }))
+}
 
-    query.push_str(" GROUP BY p.id ORDER BY p.created_at DESC LIMIT ? OFFSET ?");
+// ─── GET /api/marketplace/products/search ───────────────────
+// Busca FTS5 ranqueada (bm25, título pesa 10× a descrição), com
+// trechos destacados e contagem por categoria/condição.
+async fn search_products(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<SearchFilters>,
+) -> Result<HttpResponse, AppError> {
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
 
-    // Buscar produtos
-    let mut q = sqlx::query_as::<_, Product>(&query);
+    let matcher   = filters.q.as_deref().and_then(fts_match_expr);
+    let has_query = matcher.is_some();
+    let sort      = filters.sort.unwrap_or_default();
+
+    // ?1 = MATCH, ?2 = categoria, ?3 = condição, ?4/?5 = faixa de preço
+    let source = if has_query {
+        "products_fts JOIN products p ON p.id = products_fts.product_id"
+    } else {
+        "products p"
+    };
+    let text_filter = if has_query { "products_fts MATCH ?1" } else { "?1 IS NULL" };
+    let conditions = format!(
+        "{} AND p.status = 'active'
+         AND (?2 IS NULL OR {})
+         AND (?3 IS NULL OR p.condition = ?3)
+         AND (?4 IS NULL OR p.price_sats >= ?4)
+         AND (?5 IS NULL OR p.price_sats <= ?5)",
+        text_filter,
+        category_filter("?2"),
+    );
 
-    if let Some(ref cat) = filters.category {
-        q = q.bind(cat);
-    }
-    if let Some(ref cond) = filters.condition {
-        q = q.bind(cond);
-    }
-    if let Some(min) = filters.min_price {
-        q = q.bind(min);
-    }
-    if let Some(max) = filters.max_price {
-        q = q.bind(max);
-    }
-    if let Some(ref search) = filters.search {
-        let pattern = format!("%{}%", search);
-        q = q.bind(pattern.clone()).bind(pattern);
-    }
+    let (rank, title_highlight, snippet) = if has_query {
+        (
+            "bm25(products_fts, 0.0, 10.0, 1.0)",
+            "highlight(products_fts, 1, '<mark>', '</mark>')",
+            "snippet(products_fts, 2, '<mark>', '</mark>', '…', 16)",
+        )
+    } else {
+        ("0.0", "NULL", "NULL")
+    };
+
+    let list_query = format!(
+        "SELECT p.*, u.username AS seller,
+                r.avg_rating, COALESCE(r.review_count, 0) AS review_count,
+                {rank} AS rank, {title_highlight} AS title_highlight, {snippet} AS snippet
+         FROM {source}
+         JOIN users u ON u.id = p.seller_id
+         LEFT JOIN (
+             SELECT product_id, AVG(rating) AS avg_rating, COUNT(*) AS review_count
+             FROM reviews WHERE hidden_at IS NULL GROUP BY product_id
+         ) r ON r.product_id = p.id
+         WHERE {conditions}
+         ORDER BY {order}
+         LIMIT ?6 OFFSET ?7",
+        order = sort.order_by(has_query),
+    );
+
+    let results = sqlx::query_as::<_, SearchHit>(&list_query)
+        .bind(&matcher)
+        .bind(&filters.category)
+        .bind(&filters.condition)
+        .bind(filters.min_price)
+        .bind(filters.max_price)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool.as_ref())
+        .await?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM {} WHERE {}", source, conditions),
+    )
+    .bind(&matcher)
+    .bind(&filters.category)
+    .bind(&filters.condition)
+    .bind(filters.min_price)
+    .bind(filters.max_price)
+    .fetch_one(pool.as_ref())
+    .await?;
 
-    q = q.bind(limit).bind(offset);
+    // Cada faceta ignora o próprio filtro, para mostrar as alternativas
+    let facet = |column: &str| {
+        format!(
+            "SELECT p.{column} AS value, COUNT(*) AS count FROM {source} WHERE {conditions}
+             GROUP BY p.{column} ORDER BY count DESC, value ASC",
+        )
+    };
+
+    let category = sqlx::query_as::<_, FacetCount>(&facet("category"))
+        .bind(&matcher)
+        .bind(None::<String>)
+        .bind(&filters.condition)
+        .bind(filters.min_price)
+        .bind(filters.max_price)
+        .fetch_all(pool.as_ref())
+        .await?;
 
-    let products = q.fetch_all(pool.as_ref()).await?;
+    let condition = sqlx::query_as::<_, FacetCount>(&facet("condition"))
+        .bind(&matcher)
+        .bind(&filters.category)
+        .bind(None::<String>)
+        .bind(filters.min_price)
+        .bind(filters.max_price)
+        .fetch_all(pool.as_ref())
+        .await?;
 
-    Ok(HttpResponse::Ok().json(products))
+    Ok(HttpResponse::Ok().json(SearchResponse {
+        results,
+        total,
+        page,
+        facets: SearchFacets { category, condition },
+    }))
 }
 
 // ─── GET /api/marketplace/products/:id ──────────────────────
+// Produto oculto pela moderação ou removido só aparece para o
+// vendedor e para moderadores; para os demais é 404.
 async fn get_product(
     pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
     path: web::Path<String>,
 ) -> Result<HttpResponse, AppError> {
     let id = path.into_inner();
@@ -103,6 +262,19 @@ async fn get_product(
     .await?
     .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
 
+    if matches!(product.status, ProductStatus::Hidden | ProductStatus::Removed) {
+        let claims = req.extensions().get::<Claims>().cloned();
+        let visible = match claims {
+            Some(claims) if claims.sub == product.seller_id => true,
+            Some(claims) => require_role(pool.as_ref(), &claims, Role::Moderator).await.is_ok(),
+            None         => false,
+        };
+
+        if !visible {
+            return Err(AppError::NotFound("Produto não encontrado".into()));
+        }
+    }
+
     // Buscar username do vendedor
     let seller = sqlx::query_scalar::<_, String>(
         "SELECT username FROM users WHERE id = ?",
@@ -113,30 +285,43 @@ async fn get_product(
 
     // Buscar média de avaliações
     let avg_rating = sqlx::query_scalar::<_, Option<f64>>(
-        "SELECT AVG(rating) FROM reviews WHERE product_id = ?",
+        "SELECT AVG(rating) FROM reviews WHERE product_id = ? AND hidden_at IS NULL",
     )
     .bind(&id)
     .fetch_one(pool.as_ref())
     .await?;
 
     let review_count = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM reviews WHERE product_id = ?",
+        "SELECT COUNT(*) FROM reviews WHERE product_id = ? AND hidden_at IS NULL",
     )
     .bind(&id)
     .fetch_one(pool.as_ref())
     .await?;
 
+    // Miniatura da capa (primeira imagem)
+    let thumbnail = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT thumb_sha256 FROM product_media
+         WHERE product_id = ? AND kind = 'IMAGE'
+         ORDER BY position ASC LIMIT 1",
+    )
+    .bind(&id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .flatten();
+
     Ok(HttpResponse::Ok().json(ProductResponse {
         product,
         seller,
         avg_rating,
         review_count,
+        thumbnail,
     }))
 }
 
 // ─── POST /api/marketplace/products ─────────────────────────
 async fn create_product(
     pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
     req:  HttpRequest,
     body: web::Json<CreateProductRequest>,
 ) -> Result<HttpResponse, AppError> {
@@ -146,16 +331,20 @@ async fn create_product(
         .cloned()
         .ok_or(AppError::Unauthorized)?;
 
+    // Só quem tem o papel seller anuncia
+    require_role(pool.as_ref(), &claims, Role::Seller).await?;
+
     // Validações
-    if body.title.trim().is_empty() {
-        return Err(AppError::Validation("Título é obrigatório".into()));
-    }
-    if body.price_sats <= 0 {
-        return Err(AppError::Validation("Preço deve ser maior que zero".into()));
-    }
-    if !["Novo", "Usado"].contains(&body.condition.as_str()) {
-        return Err(AppError::Validation("Condição deve ser 'Novo' ou 'Usado'".into()));
+    validate_title(&body.title).map_err(AppError::Validation)?;
+    validate_description(&body.description).map_err(AppError::Validation)?;
+    validate_price(body.price_sats).map_err(AppError::Validation)?;
+    validate_condition(&body.condition).map_err(AppError::Validation)?;
+    let (state, city) = validate_location(&body.state, &body.city).map_err(AppError::Validation)?;
+    let stock = body.stock.unwrap_or(1);
+    if stock <= 0 {
+        return Err(AppError::Validation("Estoque deve ser maior que zero".into()));
     }
+    let category = active_category(pool.as_ref(), &body.category).await?;
 
     // Buscar user_id a partir do endereço
     let user_id = sqlx::query_scalar::<_, String>(
@@ -165,19 +354,25 @@ async fn create_product(
     .fetch_one(pool.as_ref())
     .await?;
 
-    let product = Product::new(
-        user_id,
-        body.title.clone(),
-        body.description.clone(),
-        body.price_sats,
-        body.category.clone(),
-        body.condition.clone(),
-        body.location.clone(),
-    );
+    ensure_not_suspended(pool.as_ref(), &user_id).await?;
+    check_banned_terms(pool.as_ref(), &[&body.title, &body.description]).await?;
+
+    let product = Product::new(user_id, ProductListing {
+        title:       body.title.trim().to_string(),
+        description: body.description.clone(),
+        price_sats:  body.price_sats,
+        category_id: category.id,
+        category:    category.slug,
+        condition:   body.condition.clone(),
+        state,
+        city,
+        stock,
+    });
 
     sqlx::query(
-        "INSERT INTO products (id, seller_id, title, description, price_sats, category, condition, location, status, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO products (id, seller_id, title, description, price_sats, category, category_id, condition,
+         location, state, city, status, stock, reserved, version, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&product.id)
     .bind(&product.seller_id)
@@ -185,20 +380,35 @@ async fn create_product(
     .bind(&product.description)
     .bind(product.price_sats)
     .bind(&product.category)
+    .bind(&product.category_id)
     .bind(&product.condition)
     .bind(&product.location)
-    .bind(&product.status)
+    .bind(&product.state)
+    .bind(&product.city)
+    .bind(product.status)
+    .bind(product.stock)
+    .bind(product.reserved)
+    .bind(product.version)
     .bind(&product.created_at)
     .bind(&product.updated_at)
     .execute(pool.as_ref())
     .await?;
 
+    snapshot_revision(pool.as_ref(), &product.id).await?;
+
+    // Avisar quem tem uma busca salva que casa com o anúncio. O produto
+    // já está gravado: falha no aviso não vira erro da criação
+    if let Err(e) = alert_saved_searches(pool.as_ref(), hub.as_ref(), &product).await {
+        tracing::warn!("Falha ao avisar buscas salvas do produto {}: {}", product.id, e);
+    }
+
     Ok(HttpResponse::Created().json(product))
 }
 
 // ─── PUT /api/marketplace/products/:id ──────────────────────
 async fn update_product(
     pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
     req:  HttpRequest,
     path: web::Path<String>,
     body: web::Json<UpdateProductRequest>,
@@ -219,50 +429,158 @@ async fn update_product(
     .fetch_one(pool.as_ref())
     .await?;
 
-    let owner_id = sqlx::query_scalar::<_, String>(
-        "SELECT seller_id FROM products WHERE id = ?",
-    )
-    .bind(&id)
-    .fetch_optional(pool.as_ref())
-    .await?
-    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+    let current = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(pool.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
 
-    if owner_id != user_id {
+    if current.seller_id != user_id {
         return Err(AppError::Unauthorized);
     }
+    if current.status == ProductStatus::Removed {
+        return Err(AppError::Conflict("Produto removido não pode ser editado".into()));
+    }
+    ensure_not_suspended(pool.as_ref(), &user_id).await?;
 
-    let now = Utc::now().to_rfc3339();
-
-    // Atualizar campos opcionais
+    // Validar todos os campos antes de gravar qualquer um
     if let Some(ref title) = body.title {
-        sqlx::query("UPDATE products SET title = ?, updated_at = ? WHERE id = ?")
-            .bind(title).bind(&now).bind(&id)
-            .execute(pool.as_ref()).await?;
+        validate_title(title).map_err(AppError::Validation)?;
     }
-    if let Some(ref desc) = body.description {
-        sqlx::query("UPDATE products SET description = ?, updated_at = ? WHERE id = ?")
-            .bind(desc).bind(&now).bind(&id)
-            .execute(pool.as_ref()).await?;
+    if let Some(ref description) = body.description {
+        validate_description(description).map_err(AppError::Validation)?;
     }
+    check_banned_terms(
+        pool.as_ref(),
+        &[body.title.as_deref().unwrap_or_default(), body.description.as_deref().unwrap_or_default()],
+    )
+    .await?;
     if let Some(price) = body.price_sats {
-        sqlx::query("UPDATE products SET price_sats = ?, updated_at = ? WHERE id = ?")
-            .bind(price).bind(&now).bind(&id)
-            .execute(pool.as_ref()).await?;
+        validate_price(price).map_err(AppError::Validation)?;
+    }
+    if let Some(ref condition) = body.condition {
+        validate_condition(condition).map_err(AppError::Validation)?;
+    }
+    if let Some(stock) = body.stock {
+        if stock < 0 {
+            return Err(AppError::Validation("Estoque não pode ser negativo".into()));
+        }
+        // Não é possível reduzir abaixo das unidades já reservadas
+        if stock < current.reserved {
+            return Err(AppError::Validation(
+                "Estoque não pode ser menor que as unidades reservadas".into(),
+            ));
+        }
     }
-    if let Some(ref status) = body.status {
-        sqlx::query("UPDATE products SET status = ?, updated_at = ? WHERE id = ?")
-            .bind(status).bind(&now).bind(&id)
-            .execute(pool.as_ref()).await?;
+    if let Some(status) = body.status {
+        if status != current.status && !current.status.can_transition_to(status) {
+            return Err(AppError::Validation(format!(
+                "Status não pode ir de '{}' para '{}'",
+                current.status, status
+            )));
+        }
     }
 
+    let category = match body.category {
+        Some(ref slug) => Some(active_category(pool.as_ref(), slug).await?),
+        None => None,
+    };
+
+    // UF e cidade são validadas juntas; o campo omitido vem do produto
+    let location = if body.state.is_some() || body.city.is_some() {
+        let state = body.state.as_deref().or(current.state.as_deref()).unwrap_or_default();
+        let city  = body.city.as_deref().or(current.city.as_deref()).unwrap_or_default();
+        Some(validate_location(state, city).map_err(AppError::Validation)?)
+    } else {
+        None
+    };
+
+    let now = Utc::now().to_rfc3339();
+
+    let (category_id, category_slug) = match category {
+        Some(c) => (Some(c.id), c.slug),
+        None    => (current.category_id.clone(), current.category.clone()),
+    };
+    let (state, city) = match location {
+        Some((state, city)) => (Some(state), Some(city)),
+        None                => (current.state.clone(), current.city.clone()),
+    };
+    let display_location = match (&state, &city) {
+        (Some(state), Some(city)) => format_location(state, city),
+        _                         => current.location.clone(),
+    };
+
+    // Um único UPDATE: status condicionado ao lido (outra edição concorrente
+    // não é sobrescrita) e estoque nunca abaixo do reservado no momento da
+    // escrita. Estoque alterado sobe `version` (ver inventory::reserve_stock).
+    let affected = sqlx::query(
+        "UPDATE products SET title = ?1, description = ?2, price_sats = ?3, category = ?4,
+         category_id = ?5, condition = ?6, location = ?7, state = ?8, city = ?9, status = ?10,
+         updated_at = ?11,
+         stock   = COALESCE(?14, stock),
+         version = version + CASE WHEN ?14 IS NULL THEN 0 ELSE 1 END
+         WHERE id = ?12 AND status = ?13 AND (?14 IS NULL OR reserved <= ?14)",
+    )
+    .bind(body.title.as_deref().map(str::trim).unwrap_or(&current.title))
+    .bind(body.description.as_deref().unwrap_or(&current.description))
+    .bind(body.price_sats.unwrap_or(current.price_sats))
+    .bind(&category_slug)
+    .bind(&category_id)
+    .bind(body.condition.as_deref().unwrap_or(&current.condition))
+    .bind(&display_location)
+    .bind(&state)
+    .bind(&city)
+    .bind(body.status.unwrap_or(current.status))
+    .bind(&now)
+    .bind(&id)
+    .bind(current.status)
+    .bind(body.stock)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Conflict(
+            "Produto mudou durante a edição (status ou reservas), tente novamente".into(),
+        ));
+    }
+
+    // Nova revisão se o conteúdo visível mudou; contratos abertos
+    // continuam presos à revisão anterior
+    snapshot_revision(pool.as_ref(), &id).await?;
+
     let product = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
         .bind(&id)
         .fetch_one(pool.as_ref())
         .await?;
 
+    // Watchlist: avisar se baixou e mover a base do aviso (a edição já
+    // foi gravada — falha no aviso só é registrada)
+    if product.price_sats != current.price_sats {
+        if let Err(e) = alert_price_drop(pool.as_ref(), hub.as_ref(), &product).await {
+            tracing::warn!("Falha ao avisar queda de preço do produto {}: {}", product.id, e);
+        }
+    }
+
     Ok(HttpResponse::Ok().json(product))
 }
 
+// ─── GET /api/marketplace/products/:id/revisions ────────────
+async fn get_revisions(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+
+    let revisions = list_revisions(pool.as_ref(), &id).await?;
+    if revisions.is_empty() {
+        // Produto anterior ao histórico: a primeira revisão nasce agora
+        return Ok(HttpResponse::Ok().json(vec![snapshot_revision(pool.as_ref(), &id).await?]));
+    }
+
+    Ok(HttpResponse::Ok().json(revisions))
+}
+
 // ─── DELETE /api/marketplace/products/:id ───────────────────
 async fn delete_product(
     pool: web::Data<SqlitePool>,
@@ -326,9 +644,9 @@ async fn create_review(
         return Err(AppError::Validation("Rating deve ser entre 1 e 5".into()));
     }
 
-    // Buscar reviewer_id
-    let reviewer_id = sqlx::query_scalar::<_, String>(
-        "SELECT user_id FROM wallets WHERE address = ?",
+    // Buscar avaliador
+    let (reviewer_id, reviewer_pubkey) = sqlx::query_as::<_, (String, String)>(
+        "SELECT user_id, pubkey FROM wallets WHERE address = ?",
     )
     .bind(&claims.address)
     .fetch_one(pool.as_ref())
@@ -346,29 +664,37 @@ async fn create_review(
         return Err(AppError::NotFound("Produto não encontrado".into()));
     }
 
-    // Verificar se já avaliou
-    let already_reviewed = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM reviews WHERE product_id = ? AND reviewer_id = ?",
+    // Só compra concluída (escrow RELEASED) do próprio avaliador, ainda não avaliada
+    let contract_id = sqlx::query_scalar::<_, String>(
+        "SELECT c.id FROM contracts c
+         WHERE c.product_id = ?1 AND c.buyer_pubkey = ?2 AND c.state = 'RELEASED'
+           AND (?3 IS NULL OR c.id = ?3)
+           AND NOT EXISTS (SELECT 1 FROM reviews r WHERE r.contract_id = c.id)
+         ORDER BY c.created_at ASC
+         LIMIT 1",
     )
     .bind(&product_id)
-    .bind(&reviewer_id)
-    .fetch_one(pool.as_ref())
-    .await?;
-
-    if already_reviewed > 0 {
-        return Err(AppError::AlreadyExists("Você já avaliou este produto".into()));
-    }
+    .bind(&reviewer_pubkey)
+    .bind(&body.contract_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| {
+        AppError::Validation(
+            "Só é possível avaliar compras concluídas e ainda não avaliadas".into(),
+        )
+    })?;
 
     let review = Review::new(
         product_id,
         reviewer_id,
+        contract_id,
         body.rating,
         body.comment.clone(),
     );
 
     sqlx::query(
-        "INSERT INTO reviews (id, product_id, reviewer_id, rating, comment, created_at)
-         VALUES (?, ?, ?, ?, ?, ?)",
+        "INSERT INTO reviews (id, product_id, reviewer_id, rating, comment, created_at, contract_id, verified)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&review.id)
     .bind(&review.product_id)
@@ -376,8 +702,118 @@ async fn create_review(
     .bind(review.rating)
     .bind(&review.comment)
     .bind(&review.created_at)
+    .bind(&review.contract_id)
+    .bind(review.verified)
     .execute(pool.as_ref())
     .await?;
 
     Ok(HttpResponse::Created().json(review))
+}
+
+// ─── GET /api/marketplace/products/:id/reviews ──────────────
+async fn list_reviews(
+    pool:    web::Data<SqlitePool>,
+    path:    web::Path<String>,
+    filters: web::Query<ReviewFilters>,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM reviews WHERE product_id = ? AND hidden_at IS NULL",
+    )
+    .bind(&product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let reviews = sqlx::query_as::<_, ReviewResponse>(
+        "SELECT r.*, u.username AS reviewer
+         FROM reviews r
+         JOIN users u ON u.id = r.reviewer_id
+         WHERE r.product_id = ? AND r.hidden_at IS NULL
+         ORDER BY r.verified DESC, r.created_at DESC
+         LIMIT ? OFFSET ?",
+    )
+    .bind(&product_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ReviewListResponse { reviews, total, page }))
+}
+
+// ─── POST /api/marketplace/products/:id/reviews/:review_id/reply
+async fn reply_review(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<(String, String)>,
+    body: web::Json<ReplyReviewRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (product_id, review_id) = path.into_inner();
+
+    let reply = body.reply.trim();
+    if reply.is_empty() || reply.len() > 2000 {
+        return Err(AppError::Validation("Resposta deve ter entre 1 e 2000 caracteres".into()));
+    }
+
+    // Apenas o vendedor do produto responde
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let seller_id = sqlx::query_scalar::<_, String>(
+        "SELECT seller_id FROM products WHERE id = ?",
+    )
+    .bind(&product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    if seller_id != user_id {
+        return Err(AppError::Unauthorized);
+    }
+
+    let review = sqlx::query_as::<_, Review>(
+        "SELECT * FROM reviews WHERE id = ? AND product_id = ?",
+    )
+    .bind(&review_id)
+    .bind(&product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Avaliação não encontrada".into()))?;
+
+    // Uma resposta por avaliação
+    let affected = sqlx::query(
+        "UPDATE reviews SET seller_reply = ?, replied_at = ? WHERE id = ? AND seller_reply IS NULL",
+    )
+    .bind(reply)
+    .bind(Utc::now().to_rfc3339())
+    .bind(&review.id)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::AlreadyExists("Avaliação já foi respondida".into()));
+    }
+
+    let review = sqlx::query_as::<_, Review>("SELECT * FROM reviews WHERE id = ?")
+        .bind(&review.id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(review))
 }
\ No newline at end of file
@@ -0,0 +1,311 @@
+use actix_multipart::Multipart;
+use actix_web::{http::header, web, HttpMessage, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::media::{
+    detect_media, item_hash, MediaKind, ProductMedia, ProductMediaResponse,
+};
+use crate::blockchain::revisions::{product_image_hashes, snapshot_revision};
+use crate::storage::images::inspect;
+use crate::storage::local::{
+    max_attachment_bytes, max_image_bytes, max_media_per_product, MediaStore,
+};
+
+// ─── Configuração das rotas ──────────────────────────────────
+// Upload/listagem ficam sob /marketplace/products/:id/media
+// (registradas em marketplace::config); aqui só o download.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/media")
+            .route("/{sha256}", web::get().to(get_media)),
+    );
+}
+
+// ─── POST /api/marketplace/products/:id/media ────────────────
+// multipart/form-data com um ou mais campos `file`. O tipo é
+// detectado pelos bytes; imagens são decodificadas e ganham miniatura.
+pub async fn upload_media(
+    pool:    web::Data<SqlitePool>,
+    store:   web::Data<MediaStore>,
+    req:     HttpRequest,
+    path:    web::Path<String>,
+    mut payload: Multipart,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let product_id = path.into_inner();
+    ensure_seller(pool.as_ref(), &product_id, &claims).await?;
+
+    let mut count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM product_media WHERE product_id = ?",
+    )
+    .bind(&product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let max_bytes = max_image_bytes().max(max_attachment_bytes());
+    let mut created = Vec::new();
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| AppError::Validation(e.to_string()))?;
+
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        if count >= max_media_per_product() {
+            return Err(AppError::Validation(format!(
+                "Limite de {} arquivos por produto",
+                max_media_per_product()
+            )));
+        }
+
+        let filename = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(|name| name.chars().take(255).collect::<String>());
+
+        // Lê em blocos, abortando assim que passar do maior limite
+        let mut bytes = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| AppError::Validation(e.to_string()))?;
+            if bytes.len() + chunk.len() > max_bytes {
+                return Err(AppError::Validation(format!(
+                    "Arquivo excede {} bytes",
+                    max_bytes
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let (kind, mime_type) = detect_media(&bytes).ok_or_else(|| {
+            AppError::Validation("Formato não suportado (PNG, JPEG, WebP, GIF, PDF ou texto)".into())
+        })?;
+
+        let limit = match kind {
+            MediaKind::Image      => max_image_bytes(),
+            MediaKind::Attachment => max_attachment_bytes(),
+        };
+        if bytes.len() > limit {
+            return Err(AppError::Validation(format!(
+                "{} excede {} bytes",
+                if kind == MediaKind::Image { "Imagem" } else { "Anexo" },
+                limit
+            )));
+        }
+
+        let position = sqlx::query_scalar::<_, i64>(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM product_media WHERE product_id = ? AND kind = ?",
+        )
+        .bind(&product_id)
+        .bind(kind)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+        let mut media = ProductMedia::new(
+            product_id.clone(),
+            kind,
+            String::new(),
+            mime_type.to_string(),
+            bytes.len() as i64,
+            filename,
+            position,
+        );
+
+        // Decodificação e disco fora do executor async
+        let store = store.clone();
+        let (sha256, image) = web::block(move || -> Result<_, AppError> {
+            let image = match kind {
+                MediaKind::Image => {
+                    let info  = inspect(&bytes).map_err(AppError::Validation)?;
+                    let thumb = store.put(&info.thumbnail).map_err(|e| AppError::Internal(e.to_string()))?;
+                    Some((info.width as i64, info.height as i64, thumb))
+                }
+                MediaKind::Attachment => None,
+            };
+            let sha256 = store.put(&bytes).map_err(|e| AppError::Internal(e.to_string()))?;
+            Ok((sha256, image))
+        })
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))??;
+
+        media.sha256 = sha256;
+        if let Some((width, height, thumb)) = image {
+            media.width        = Some(width);
+            media.height       = Some(height);
+            media.thumb_sha256 = Some(thumb);
+        }
+
+        let inserted = sqlx::query(
+            "INSERT OR IGNORE INTO product_media (id, product_id, kind, sha256, mime_type, size_bytes,
+             filename, width, height, thumb_sha256, position, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&media.id)
+        .bind(&media.product_id)
+        .bind(media.kind)
+        .bind(&media.sha256)
+        .bind(&media.mime_type)
+        .bind(media.size_bytes)
+        .bind(&media.filename)
+        .bind(media.width)
+        .bind(media.height)
+        .bind(&media.thumb_sha256)
+        .bind(media.position)
+        .bind(&media.created_at)
+        .execute(pool.as_ref())
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            return Err(AppError::AlreadyExists("Arquivo já anexado a este produto".into()));
+        }
+
+        count += 1;
+        created.push(media);
+    }
+
+    if created.is_empty() {
+        return Err(AppError::Validation("Envie ao menos um campo 'file'".into()));
+    }
+
+    snapshot_revision(pool.as_ref(), &product_id).await?;
+
+    Ok(HttpResponse::Created().json(created))
+}
+
+// ─── GET /api/marketplace/products/:id/media ─────────────────
+pub async fn list_media(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+
+    let (title, description) = sqlx::query_as::<_, (String, String)>(
+        "SELECT title, description FROM products WHERE id = ?",
+    )
+    .bind(&product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    let media = sqlx::query_as::<_, ProductMedia>(
+        "SELECT * FROM product_media WHERE product_id = ? ORDER BY position ASC, created_at ASC",
+    )
+    .bind(&product_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    let image_hashes = product_image_hashes(pool.as_ref(), &product_id).await?;
+    let item_hash    = item_hash(&title, &description, &image_hashes);
+    let (images, attachments) = media.into_iter().partition(|m| m.kind == MediaKind::Image);
+
+    Ok(HttpResponse::Ok().json(ProductMediaResponse {
+        images,
+        attachments,
+        item_hash,
+    }))
+}
+
+// ─── DELETE /api/marketplace/products/:id/media/:media_id ────
+// Só desvincula do produto: o arquivo pode estar em outro produto
+// ou comprometido num escrow já aberto.
+pub async fn delete_media(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (product_id, media_id) = path.into_inner();
+    ensure_seller(pool.as_ref(), &product_id, &claims).await?;
+
+    let deleted = sqlx::query("DELETE FROM product_media WHERE id = ? AND product_id = ?")
+        .bind(&media_id)
+        .bind(&product_id)
+        .execute(pool.as_ref())
+        .await?
+        .rows_affected();
+
+    if deleted == 0 {
+        return Err(AppError::NotFound("Arquivo não encontrado".into()));
+    }
+
+    snapshot_revision(pool.as_ref(), &product_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Arquivo removido" })))
+}
+
+// ─── GET /api/media/:sha256 ──────────────────────────────────
+// Conteúdo imutável: a URL é o próprio hash.
+async fn get_media(
+    pool:  web::Data<SqlitePool>,
+    store: web::Data<MediaStore>,
+    path:  web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let sha256 = path.into_inner();
+
+    let mime_type = sqlx::query_scalar::<_, String>(
+        "SELECT mime_type FROM product_media WHERE sha256 = ?1
+         UNION ALL
+         SELECT 'image/png' FROM product_media WHERE thumb_sha256 = ?1
+         LIMIT 1",
+    )
+    .bind(&sha256)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Arquivo não encontrado".into()))?;
+
+    let key = sha256.clone();
+    let bytes = web::block(move || store.get(&key))
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Arquivo não encontrado".into()))?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime_type)
+        .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+        .insert_header((header::ETAG, format!("\"{}\"", sha256)))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(bytes))
+}
+
+// ─── Produto existe e pertence ao usuário ────────────────────
+async fn ensure_seller(
+    pool:       &SqlitePool,
+    product_id: &str,
+    claims:     &Claims,
+) -> Result<(), AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    let owner_id = sqlx::query_scalar::<_, String>(
+        "SELECT seller_id FROM products WHERE id = ? AND status != 'removed'",
+    )
+    .bind(product_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    if owner_id != user_id {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,369 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::message::{
+    validate_payload, Message, MessageFilters, MessageThread, OpenThreadRequest,
+    SendMessageRequest, ThreadResponse, ThreadSummary,
+};
+use crate::blockchain::contracts::find_contract;
+use crate::ws::hub::Hub;
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/messages")
+            .route("/threads",           web::post().to(open_thread))
+            .route("/threads",           web::get().to(list_threads))
+            .route("/unread",            web::get().to(unread_count))
+            .route("/threads/{id}",      web::get().to(get_thread))
+            .route("/threads/{id}",      web::post().to(send_message))
+            .route("/threads/{id}/read", web::post().to(mark_read)),
+    );
+}
+
+// ─── POST /api/messages/threads ──────────────────────────────
+// Conversa do anúncio (comprador interessado → vendedor) ou do
+// contrato (comprador ↔ vendedor do escrow). Idempotente.
+async fn open_thread(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<OpenThreadRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (user_id, user_pubkey) = sqlx::query_as::<_, (String, String)>(
+        "SELECT user_id, pubkey FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let thread = match (&body.contract_id, &body.product_id) {
+        (Some(contract_id), _) => {
+            let contract = find_contract(pool.as_ref(), contract_id).await?;
+
+            if user_pubkey != contract.buyer_pubkey && user_pubkey != contract.seller_pubkey {
+                return Err(AppError::Unauthorized);
+            }
+
+            let (buyer_id, seller_id) = sqlx::query_as::<_, (String, String)>(
+                "SELECT b.user_id, s.user_id FROM wallets b, wallets s
+                 WHERE b.pubkey = ? AND s.pubkey = ?",
+            )
+            .bind(&contract.buyer_pubkey)
+            .bind(&contract.seller_pubkey)
+            .fetch_one(pool.as_ref())
+            .await?;
+
+            MessageThread::new(contract.product_id, Some(contract.id), buyer_id, seller_id)
+        }
+        (None, Some(product_id)) => {
+            let seller_id = sqlx::query_scalar::<_, String>(
+                "SELECT seller_id FROM products WHERE id = ?",
+            )
+            .bind(product_id)
+            .fetch_optional(pool.as_ref())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+            if seller_id == user_id {
+                return Err(AppError::Validation(
+                    "O vendedor responde pelas conversas abertas pelos compradores".into(),
+                ));
+            }
+
+            MessageThread::new(product_id.clone(), None, user_id, seller_id)
+        }
+        (None, None) => {
+            return Err(AppError::Validation("Informe product_id ou contract_id".into()));
+        }
+    };
+
+    // Reaproveitar conversa existente
+    let existing = sqlx::query_as::<_, MessageThread>(
+        "SELECT * FROM message_threads
+         WHERE (?1 IS NOT NULL AND contract_id = ?1)
+            OR (?1 IS NULL AND contract_id IS NULL AND product_id = ?2 AND buyer_id = ?3)",
+    )
+    .bind(&thread.contract_id)
+    .bind(&thread.product_id)
+    .bind(&thread.buyer_id)
+    .fetch_optional(pool.as_ref())
+    .await?;
+
+    if let Some(existing) = existing {
+        return Ok(HttpResponse::Ok().json(existing));
+    }
+
+    sqlx::query(
+        "INSERT INTO message_threads (id, product_id, contract_id, buyer_id, seller_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&thread.id)
+    .bind(&thread.product_id)
+    .bind(&thread.contract_id)
+    .bind(&thread.buyer_id)
+    .bind(&thread.seller_id)
+    .bind(&thread.created_at)
+    .bind(&thread.updated_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(thread))
+}
+
+// ─── GET /api/messages/threads ───────────────────────────────
+async fn list_threads(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let threads = sqlx::query_as::<_, ThreadSummary>(
+        "SELECT t.*,
+                w.address AS counterparty_address,
+                w.pubkey  AS counterparty_pubkey,
+                (SELECT COUNT(*) FROM messages m
+                 WHERE m.thread_id = t.id AND m.recipient_id = ?1 AND m.read_at IS NULL) AS unread
+         FROM message_threads t
+         JOIN wallets w ON w.user_id = CASE WHEN t.buyer_id = ?1 THEN t.seller_id ELSE t.buyer_id END
+         WHERE t.buyer_id = ?1 OR t.seller_id = ?1
+         ORDER BY t.updated_at DESC",
+    )
+    .bind(&user_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(threads))
+}
+
+// ─── GET /api/messages/unread ────────────────────────────────
+async fn unread_count(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let unread = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM messages WHERE recipient_id = ? AND read_at IS NULL",
+    )
+    .bind(&user_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "unread": unread })))
+}
+
+// ─── GET /api/messages/threads/:id ───────────────────────────
+async fn get_thread(
+    pool:    web::Data<SqlitePool>,
+    req:     HttpRequest,
+    path:    web::Path<String>,
+    filters: web::Query<MessageFilters>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(50).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let (thread, user_id) = load_thread(pool.as_ref(), &path.into_inner(), &claims).await?;
+    let counterparty_pubkey = counterparty_pubkey(pool.as_ref(), &thread, &user_id).await?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM messages WHERE thread_id = ?",
+    )
+    .bind(&thread.id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    // Mais recentes primeiro
+    let messages = sqlx::query_as::<_, Message>(
+        "SELECT * FROM messages WHERE thread_id = ? ORDER BY created_at DESC LIMIT ? OFFSET ?",
+    )
+    .bind(&thread.id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ThreadResponse {
+        thread,
+        counterparty_pubkey,
+        messages,
+        total,
+        page,
+    }))
+}
+
+// ─── POST /api/messages/threads/:id ──────────────────────────
+async fn send_message(
+    pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<SendMessageRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    validate_payload(&body.ciphertext, &body.nonce).map_err(AppError::Validation)?;
+
+    let (thread, user_id) = load_thread(pool.as_ref(), &path.into_inner(), &claims).await?;
+    let recipient_id = thread.counterparty(&user_id).unwrap_or_default().to_string();
+
+    let sender_pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let message = Message::new(
+        thread.id.clone(),
+        user_id,
+        recipient_id,
+        sender_pubkey,
+        body.ciphertext.clone(),
+        body.nonce.clone(),
+    );
+
+    sqlx::query(
+        "INSERT INTO messages (id, thread_id, sender_id, recipient_id, sender_pubkey, ciphertext, nonce, read_at, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, NULL, ?)",
+    )
+    .bind(&message.id)
+    .bind(&message.thread_id)
+    .bind(&message.sender_id)
+    .bind(&message.recipient_id)
+    .bind(&message.sender_pubkey)
+    .bind(&message.ciphertext)
+    .bind(&message.nonce)
+    .bind(&message.created_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    sqlx::query("UPDATE message_threads SET updated_at = ? WHERE id = ?")
+        .bind(&message.created_at)
+        .bind(&thread.id)
+        .execute(pool.as_ref())
+        .await?;
+
+    // Entrega em tempo real, se o destinatário estiver conectado
+    let recipient_address = sqlx::query_scalar::<_, String>(
+        "SELECT address FROM wallets WHERE user_id = ?",
+    )
+    .bind(&message.recipient_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    hub.send(&recipient_address, &serde_json::json!({
+        "type": "message",
+        "data": message,
+    }));
+
+    Ok(HttpResponse::Created().json(message))
+}
+
+// ─── POST /api/messages/threads/:id/read ─────────────────────
+async fn mark_read(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (thread, user_id) = load_thread(pool.as_ref(), &path.into_inner(), &claims).await?;
+
+    let marked = sqlx::query(
+        "UPDATE messages SET read_at = ?
+         WHERE thread_id = ? AND recipient_id = ? AND read_at IS NULL",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(&thread.id)
+    .bind(&user_id)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "marked_read": marked })))
+}
+
+// ─── Usuário autenticado ─────────────────────────────────────
+async fn user_id_for(pool: &SqlitePool, claims: &Claims) -> Result<String, AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user_id)
+}
+
+// ─── Carregar conversa (apenas participantes) ────────────────
+async fn load_thread(
+    pool:   &SqlitePool,
+    id:     &str,
+    claims: &Claims,
+) -> Result<(MessageThread, String), AppError> {
+    let user_id = user_id_for(pool, claims).await?;
+
+    let thread = sqlx::query_as::<_, MessageThread>("SELECT * FROM message_threads WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Conversa não encontrada".into()))?;
+
+    if thread.counterparty(&user_id).is_none() {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok((thread, user_id))
+}
+
+// ─── Pubkey da outra parte (chave do ECDH) ───────────────────
+async fn counterparty_pubkey(
+    pool:    &SqlitePool,
+    thread:  &MessageThread,
+    user_id: &str,
+) -> Result<String, AppError> {
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE user_id = ?",
+    )
+    .bind(thread.counterparty(user_id).unwrap_or_default())
+    .fetch_one(pool)
+    .await?;
+
+    Ok(pubkey)
+}
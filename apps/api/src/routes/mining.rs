@@ -3,19 +3,28 @@ use sqlx::SqlitePool;
 use std::env;
 
 use crate::errors::AppError;
+use crate::middleware::rate_limit::limited;
+use crate::models::rate_limit::LimitedRoute;
 use crate::models::user::Claims;
-use crate::models::block::{Block, MiningJob, MiningSubmit};
+use crate::models::block::{Block, BlockHeader, MiningJob, MiningSubmit};
 use crate::models::transaction::{Transaction, Utxo};
 use crate::blockchain::pow::{sha256_hex, meets_difficulty};
 use crate::blockchain::mempool::get_pending_transactions;
 use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::process_expired_contracts;
+use crate::blockchain::auctions::process_ended_auctions;
+use crate::blockchain::offers::process_expired_offers;
+use crate::blockchain::reputation::{anchor_snapshot, pending_snapshot, take_snapshot};
+use crate::blockchain::notifications::process_auction_alerts;
+use crate::blockchain::shipping::process_auto_releases;
+use crate::ws::hub::Hub;
 
 // ─── Configuração das rotas ──────────────────────────────────
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/mining")
             .route("/job",    web::get().to(get_job))
-            .route("/submit", web::post().to(submit_block)),
+            .service(limited("/submit", LimitedRoute::MiningSubmit).route(web::post().to(submit_block))),
     );
 }
 
@@ -51,6 +60,9 @@ async fn get_job(
     // Target: string de zeros de acordo com a dificuldade
     let target = "0".repeat(difficulty as usize);
 
+    // Root de reputação pendente entra no header deste bloco
+    let reputation_root = pending_snapshot(pool.as_ref()).await?.map(|s| s.root);
+
     Ok(HttpResponse::Ok().json(MiningJob {
         block_height: next_height,
         prev_hash,
@@ -58,12 +70,14 @@ async fn get_job(
         difficulty,
         target,
         reward_sats,
+        reputation_root,
     }))
 }
 
 // ─── POST /api/mining/submit ─────────────────────────────────
 async fn submit_block(
     pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
     req:  HttpRequest,
     body: web::Json<MiningSubmit>,
 ) -> Result<HttpResponse, AppError> {
@@ -90,17 +104,19 @@ async fn submit_block(
         return Err(AppError::InvalidBlock("prev_hash não confere com o topo da chain".into()));
     }
 
-    // 5. Recompor o header e verificar o hash (PoW)
-    let header_data = format!(
-        "{}{}{}{}{}{}",
-        body.block_height,
-        body.prev_hash,
-        body.merkle_root,
-        body.nonce,
+    // 5. Recompor o header e verificar o hash (PoW).
+    //    Havendo fotografia de reputação pendente, sua root fecha o header.
+    let snapshot = pending_snapshot(pool.as_ref()).await?;
+    let header = BlockHeader {
+        height:          body.block_height,
+        prev_hash:       body.prev_hash.clone(),
+        merkle_root:     body.merkle_root.clone(),
+        nonce:           body.nonce,
         difficulty,
-        body.timestamp,
-    );
-    let block_hash = sha256_hex(&header_data);
+        timestamp:       body.timestamp.clone(),
+        reputation_root: snapshot.as_ref().map(|s| s.root.clone()),
+    };
+    let block_hash = sha256_hex(&header.to_bytes());
 
     // 6. Verificar se o hash atende à dificuldade
     if !meets_difficulty(&block_hash, difficulty) {
@@ -130,19 +146,15 @@ async fn submit_block(
     // 9. Salvar bloco no banco
     let block = Block::new(
         block_hash.clone(),
-        body.block_height,
-        body.prev_hash.clone(),
-        body.merkle_root.clone(),
-        body.nonce,
-        difficulty,
+        header,
         reward_sats,
         claims.address.clone(),
         tx_count,
     );
 
     sqlx::query(
-        "INSERT INTO blocks (id, height, prev_hash, merkle_root, nonce, difficulty, reward_sats, miner_address, tx_count, mined_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO blocks (id, height, prev_hash, merkle_root, nonce, difficulty, reward_sats, miner_address, tx_count, mined_at, reputation_root)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
     )
     .bind(&block.id)
     .bind(block.height)
@@ -154,9 +166,14 @@ async fn submit_block(
     .bind(&block.miner_address)
     .bind(block.tx_count)
     .bind(&block.mined_at)
+    .bind(&block.reputation_root)
     .execute(pool.as_ref())
     .await?;
 
+    if let Some(ref snapshot) = snapshot {
+        anchor_snapshot(pool.as_ref(), &snapshot.id, &block_hash, block.height).await?;
+    }
+
     // 10. Confirmar TXs pendentes
     for tx in &pending_txs {
         sqlx::query(
@@ -196,12 +213,36 @@ async fn submit_block(
     .execute(pool.as_ref())
     .await?;
 
+    // 12. Reembolsar escrows expirados (libera reservas de estoque)
+    let refunded = process_expired_contracts(pool.as_ref()).await?;
+
+    // 13. Liberar escrows enviados sem disputa após o prazo
+    let auto_released = process_auto_releases(pool.as_ref()).await?;
+
+    // 14. Encerrar leilões que chegaram ao bloco final
+    let settled = process_ended_auctions(pool.as_ref(), hub.as_ref()).await?;
+
+    // 15. Expirar ofertas sem resposta
+    let offers_expired = process_expired_offers(pool.as_ref()).await?;
+
+    // 16. Fotografar reputações (ancoradas no próximo bloco)
+    let reputation = take_snapshot(pool.as_ref(), body.block_height).await?;
+
+    // 17. Avisar quem acompanha leilões perto do bloco final
+    let auction_alerts = process_auction_alerts(pool.as_ref(), hub.as_ref(), body.block_height).await?;
+
     Ok(HttpResponse::Created().json(serde_json::json!({
         "message":      "Bloco aceito!",
         "block_hash":   block_hash,
         "height":       body.block_height,
         "reward_sats":  reward_sats,
         "txs_included": tx_count,
+        "contracts_refunded": refunded,
+        "contracts_released": auto_released,
+        "auctions_settled":   settled,
+        "offers_expired":     offers_expired,
+        "reputation_root":    reputation.map(|s| s.root),
+        "auction_alerts":     auction_alerts,
     })))
 }
 
@@ -214,7 +255,7 @@ fn compute_merkle_root(txs: &[Transaction]) -> String {
     let mut hashes: Vec<String> = txs.iter().map(|tx| tx.id.clone()).collect();
 
     while hashes.len() > 1 {
-        if hashes.len() % 2 != 0 {
+        if !hashes.len().is_multiple_of(2) {
             hashes.push(hashes.last().unwrap().clone());
         }
         hashes = hashes
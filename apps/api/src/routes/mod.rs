@@ -3,4 +3,18 @@ pub mod wallet;
 pub mod marketplace;
 pub mod chain;
 pub mod mining;
-pub mod contracts;
\ No newline at end of file
+pub mod contracts;
+pub mod orders;
+pub mod auctions;
+pub mod offers;
+pub mod reputation;
+pub mod messages;
+pub mod media;
+pub mod categories;
+pub mod watchlist;
+pub mod notifications;
+pub mod sellers;
+pub mod moderation;
+pub mod shipping;
+pub mod admin;
+pub mod two_factor;
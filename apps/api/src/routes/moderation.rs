@@ -0,0 +1,401 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::middleware::from_fn;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::moderation::{
+    normalize_term, AuditFilters, AuditResponse, BanTermRequest, BannedTerm, CreateReportRequest,
+    DismissReportRequest, ModerationAction, ModerationActionKind, ModerationActionRequest,
+    ModerationTarget, QueueFilters, QueueItem, QueueResponse, Report,
+};
+use crate::blockchain::moderation::{apply_action, record_action, target_owner};
+use crate::middleware::auth::role_guard;
+use crate::models::role::Role;
+use crate::ws::hub::Hub;
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reports")
+            .route("", web::post().to(create_report)),
+    );
+    cfg.service(
+        web::scope("/moderation")
+            .wrap(from_fn(role_guard(Role::Moderator)))
+            .route("/queue",              web::get().to(get_queue))
+            .route("/reports",            web::get().to(list_reports))
+            .route("/reports/{id}/dismiss", web::post().to(dismiss_report))
+            .route("/actions",            web::post().to(take_action))
+            .route("/actions",            web::get().to(list_actions))
+            .route("/banned-terms",       web::get().to(list_banned_terms))
+            .route("/banned-terms",       web::post().to(ban_term))
+            .route("/banned-terms/{id}",  web::delete().to(unban_term)),
+    );
+}
+
+// ─── POST /api/reports ───────────────────────────────────────
+// Qualquer usuário denuncia anúncio ou avaliação de outra pessoa.
+async fn create_report(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateReportRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    if !body.target_type.is_reportable() {
+        return Err(AppError::Validation("Só anúncios e avaliações podem ser denunciados".into()));
+    }
+    if body.details.as_deref().is_some_and(|d| d.chars().count() > 1000) {
+        return Err(AppError::Validation("Detalhes devem ter até 1000 caracteres".into()));
+    }
+
+    let owner = target_owner(pool.as_ref(), body.target_type, &body.target_id).await?;
+
+    if owner == claims.sub {
+        return Err(AppError::Validation("Não é possível denunciar o próprio conteúdo".into()));
+    }
+
+    let already_open = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM reports
+         WHERE reporter_id = ? AND target_type = ? AND target_id = ? AND status = 'OPEN'",
+    )
+    .bind(&claims.sub)
+    .bind(body.target_type)
+    .bind(&body.target_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if already_open > 0 {
+        return Err(AppError::AlreadyExists("Você já denunciou este conteúdo".into()));
+    }
+
+    let body = body.into_inner();
+    let report = Report::new(
+        claims.sub,
+        body.target_type,
+        body.target_id,
+        body.reason,
+        body.details.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+    );
+
+    sqlx::query(
+        "INSERT INTO reports (id, reporter_id, target_type, target_id, reason, details, status,
+         action_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)",
+    )
+    .bind(&report.id)
+    .bind(&report.reporter_id)
+    .bind(report.target_type)
+    .bind(&report.target_id)
+    .bind(report.reason)
+    .bind(&report.details)
+    .bind(report.status)
+    .bind(&report.created_at)
+    .bind(&report.updated_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(report))
+}
+
+// ─── GET /api/moderation/queue (moderador) ───────────────────
+// Denúncias abertas agrupadas por alvo; mais denunciados primeiro.
+async fn get_queue(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<QueueFilters>,
+) -> Result<HttpResponse, AppError> {
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let conditions = "status = 'OPEN' AND (?1 IS NULL OR target_type = ?1)";
+
+    let total = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT COUNT(*) FROM (SELECT 1 FROM reports WHERE {} GROUP BY target_type, target_id)",
+        conditions
+    ))
+    .bind(filters.target_type)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let items = sqlx::query_as::<_, QueueItem>(&format!(
+        "SELECT target_type, target_id,
+                COUNT(*)                      AS open_reports,
+                GROUP_CONCAT(DISTINCT reason) AS reasons,
+                MIN(created_at)               AS first_reported_at,
+                MAX(created_at)               AS last_reported_at
+         FROM reports
+         WHERE {}
+         GROUP BY target_type, target_id
+         ORDER BY open_reports DESC, first_reported_at ASC
+         LIMIT ?2 OFFSET ?3",
+        conditions
+    ))
+    .bind(filters.target_type)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(QueueResponse { items, total, page }))
+}
+
+// ─── GET /api/moderation/reports (moderador) ─────────────────
+// Denúncias individuais de um alvo (?target_type=&target_id=).
+async fn list_reports(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<AuditFilters>,
+) -> Result<HttpResponse, AppError> {
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let reports = sqlx::query_as::<_, Report>(
+        "SELECT * FROM reports
+         WHERE (?1 IS NULL OR target_type = ?1)
+           AND (?2 IS NULL OR target_id = ?2)
+           AND (?3 IS NULL OR reporter_id = ?3)
+         ORDER BY created_at DESC
+         LIMIT ?4 OFFSET ?5",
+    )
+    .bind(filters.target_type)
+    .bind(&filters.target_id)
+    .bind(&filters.user_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(reports))
+}
+
+// ─── POST /api/moderation/reports/:id/dismiss (moderador) ────
+async fn dismiss_report(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<DismissReportRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let id = path.into_inner();
+    let report = sqlx::query_as::<_, Report>("SELECT * FROM reports WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(pool.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Denúncia não encontrada".into()))?;
+
+    let action = ModerationAction::new(
+        claims.address.clone(),
+        ModerationActionKind::Dismiss,
+        ModerationTarget::Report,
+        report.id.clone(),
+        Some(report.reporter_id.clone()),
+        required_reason(&body.reason)?,
+    );
+
+    let affected = sqlx::query(
+        "UPDATE reports SET status = 'DISMISSED', action_id = ?, updated_at = ?
+         WHERE id = ? AND status = 'OPEN'",
+    )
+    .bind(&action.id)
+    .bind(&action.created_at)
+    .bind(&id)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    if affected == 0 {
+        return Err(AppError::Conflict("Denúncia já foi tratada".into()));
+    }
+
+    record_action(pool.as_ref(), &action).await?;
+
+    Ok(HttpResponse::Ok().json(action))
+}
+
+// ─── POST /api/moderation/actions (moderador) ────────────────
+// HIDE | REMOVE | WARN | SUSPEND | RESTORE sobre anúncio, avaliação
+// ou usuário. Resolve as denúncias abertas do alvo.
+async fn take_action(
+    pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
+    req:  HttpRequest,
+    body: web::Json<ModerationActionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let reason = required_reason(&body.reason)?;
+    let owner = target_owner(pool.as_ref(), body.target_type, &body.target_id).await?;
+
+    let action = ModerationAction::new(
+        claims.address.clone(),
+        body.action,
+        body.target_type,
+        body.target_id.clone(),
+        Some(owner),
+        reason,
+    );
+
+    let action = apply_action(pool.as_ref(), hub.as_ref(), action).await?;
+
+    Ok(HttpResponse::Created().json(action))
+}
+
+// ─── GET /api/moderation/actions (moderador) ─────────────────
+// Trilha de auditoria (?target_type=&target_id=&user_id=).
+async fn list_actions(
+    pool:    web::Data<SqlitePool>,
+    filters: web::Query<AuditFilters>,
+) -> Result<HttpResponse, AppError> {
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let conditions = "(?1 IS NULL OR target_type = ?1)
+         AND (?2 IS NULL OR target_id = ?2)
+         AND (?3 IS NULL OR target_user_id = ?3)";
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM moderation_actions WHERE {}", conditions),
+    )
+    .bind(filters.target_type)
+    .bind(&filters.target_id)
+    .bind(&filters.user_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let actions = sqlx::query_as::<_, ModerationAction>(&format!(
+        "SELECT * FROM moderation_actions WHERE {} ORDER BY created_at DESC LIMIT ?4 OFFSET ?5",
+        conditions
+    ))
+    .bind(filters.target_type)
+    .bind(&filters.target_id)
+    .bind(&filters.user_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(AuditResponse { actions, total, page }))
+}
+
+// ─── GET /api/moderation/banned-terms (moderador) ────────────
+async fn list_banned_terms(
+    pool: web::Data<SqlitePool>,
+) -> Result<HttpResponse, AppError> {
+    let terms = sqlx::query_as::<_, BannedTerm>("SELECT * FROM banned_terms ORDER BY term ASC")
+        .fetch_all(pool.as_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(terms))
+}
+
+// ─── POST /api/moderation/banned-terms (moderador) ───────────
+// Vale para criações e edições futuras; anúncios existentes não mudam.
+async fn ban_term(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<BanTermRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let term = normalize_term(&body.term);
+    if term.is_empty() || term.chars().count() > 60 {
+        return Err(AppError::Validation("Termo deve ter entre 1 e 60 caracteres".into()));
+    }
+
+    let exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM banned_terms WHERE term = ?")
+        .bind(&term)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    if exists > 0 {
+        return Err(AppError::AlreadyExists(format!("Termo '{}' já está na lista", term)));
+    }
+
+    let banned = BannedTerm::new(term, claims.address.clone());
+
+    sqlx::query("INSERT INTO banned_terms (id, term, created_by, created_at) VALUES (?, ?, ?, ?)")
+        .bind(&banned.id)
+        .bind(&banned.term)
+        .bind(&banned.created_by)
+        .bind(&banned.created_at)
+        .execute(pool.as_ref())
+        .await?;
+
+    record_action(pool.as_ref(), &ModerationAction::new(
+        claims.address.clone(),
+        ModerationActionKind::BanTerm,
+        ModerationTarget::Term,
+        banned.id.clone(),
+        None,
+        banned.term.clone(),
+    ))
+    .await?;
+
+    Ok(HttpResponse::Created().json(banned))
+}
+
+// ─── DELETE /api/moderation/banned-terms/:id (moderador) ─────
+async fn unban_term(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let id = path.into_inner();
+    let banned = sqlx::query_as::<_, BannedTerm>("SELECT * FROM banned_terms WHERE id = ?")
+        .bind(&id)
+        .fetch_optional(pool.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Termo não encontrado".into()))?;
+
+    sqlx::query("DELETE FROM banned_terms WHERE id = ?")
+        .bind(&id)
+        .execute(pool.as_ref())
+        .await?;
+
+    record_action(pool.as_ref(), &ModerationAction::new(
+        claims.address.clone(),
+        ModerationActionKind::UnbanTerm,
+        ModerationTarget::Term,
+        banned.id,
+        None,
+        banned.term,
+    ))
+    .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Termo removido da lista" })))
+}
+
+// ─── Motivo obrigatório (vai para a auditoria) ───────────────
+fn required_reason(reason: &str) -> Result<String, AppError> {
+    let reason = reason.trim();
+    if reason.is_empty() || reason.chars().count() > 500 {
+        return Err(AppError::Validation("Motivo deve ter entre 1 e 500 caracteres".into()));
+    }
+    Ok(reason.to_string())
+}
@@ -0,0 +1,142 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::notification::{Notification, NotificationFilters, NotificationListResponse};
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/notifications")
+            .route("",           web::get().to(list_notifications))
+            .route("/read-all",  web::post().to(mark_all_read))
+            .route("/{id}/read", web::post().to(mark_read)),
+    );
+}
+
+// ─── GET /api/notifications ──────────────────────────────────
+async fn list_notifications(
+    pool:    web::Data<SqlitePool>,
+    req:     HttpRequest,
+    filters: web::Query<NotificationFilters>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+    let unread_only = filters.unread.unwrap_or(false);
+
+    let conditions = "user_id = ?1 AND (?2 = 0 OR read_at IS NULL)";
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM notifications WHERE {}", conditions),
+    )
+    .bind(&user_id)
+    .bind(unread_only)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let unread = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = ? AND read_at IS NULL",
+    )
+    .bind(&user_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let notifications = sqlx::query_as::<_, Notification>(&format!(
+        "SELECT * FROM notifications WHERE {} ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
+        conditions
+    ))
+    .bind(&user_id)
+    .bind(unread_only)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(NotificationListResponse {
+        notifications,
+        total,
+        unread,
+        page,
+    }))
+}
+
+// ─── POST /api/notifications/:id/read ────────────────────────
+async fn mark_read(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let notification = sqlx::query_as::<_, Notification>(
+        "SELECT * FROM notifications WHERE id = ? AND user_id = ?",
+    )
+    .bind(path.into_inner())
+    .bind(&user_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Notificação não encontrada".into()))?;
+
+    // Idempotente: a data da primeira leitura é preservada
+    sqlx::query("UPDATE notifications SET read_at = ? WHERE id = ? AND read_at IS NULL")
+        .bind(Utc::now().to_rfc3339())
+        .bind(&notification.id)
+        .execute(pool.as_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Notificação lida" })))
+}
+
+// ─── POST /api/notifications/read-all ────────────────────────
+async fn mark_all_read(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let marked = sqlx::query(
+        "UPDATE notifications SET read_at = ? WHERE user_id = ? AND read_at IS NULL",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(&user_id)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "marked": marked })))
+}
+
+// ─── user_id do usuário autenticado ──────────────────────────
+async fn user_id_for(pool: &SqlitePool, claims: &Claims) -> Result<String, AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user_id)
+}
@@ -0,0 +1,408 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::contract::Contract;
+use crate::models::offer::{
+    offer_message, CounterOfferRequest, CreateOfferRequest, Offer, OfferFilters,
+    OfferListResponse, OfferParty, OfferResponse, OfferStatus, Proposal,
+};
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::offers::{
+    accept_offer, find_offer, offer_expiry_blocks, set_offer_status,
+};
+use crate::crypto::signing::verify_signature;
+use crate::ws::hub::Hub;
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/offers")
+            .route("",              web::post().to(create_offer))
+            .route("",              web::get().to(list_offers))
+            .route("/{id}",         web::get().to(get_offer))
+            .route("/{id}/accept",  web::post().to(accept))
+            .route("/{id}/reject",  web::post().to(reject))
+            .route("/{id}/counter", web::post().to(counter)),
+    );
+}
+
+// ─── POST /api/offers ────────────────────────────────────────
+async fn create_offer(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateOfferRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    // 1. Produto ativo
+    let (seller_id, price_sats) = sqlx::query_as::<_, (String, i64)>(
+        "SELECT seller_id, price_sats FROM products WHERE id = ? AND status = 'active'",
+    )
+    .bind(&body.product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    // 2. Validações
+    if body.amount_sats <= 0 || body.amount_sats >= price_sats {
+        return Err(AppError::Validation(format!(
+            "Oferta deve ser maior que zero e menor que o preço ({} sats)",
+            price_sats
+        )));
+    }
+
+    let buyer_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if buyer_id == seller_id {
+        return Err(AppError::Validation("Você não pode fazer oferta no próprio produto".into()));
+    }
+
+    let in_auction = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM auctions WHERE product_id = ? AND status = 'OPEN'",
+    )
+    .bind(&body.product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if in_auction > 0 {
+        return Err(AppError::Conflict("Produto está em leilão".into()));
+    }
+
+    // Uma negociação aberta por comprador e produto
+    let pending = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM offers WHERE product_id = ? AND buyer_id = ? AND status = 'PENDING'",
+    )
+    .bind(&body.product_id)
+    .bind(&buyer_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if pending > 0 {
+        return Err(AppError::AlreadyExists(
+            "Já existe uma oferta pendente para este produto".into(),
+        ));
+    }
+
+    // 3. Compromisso assinado com o valor
+    verify_offer_signature(pool.as_ref(), &buyer_id, &body.product_id, body.amount_sats, &body.signature)
+        .await?;
+
+    // 4. Salvar oferta
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+
+    let offer = Offer::new(
+        body.product_id.clone(),
+        buyer_id,
+        seller_id,
+        None,
+        Proposal {
+            proposer:    OfferParty::Buyer,
+            amount_sats: body.amount_sats,
+            signature:   body.signature.clone(),
+        },
+        current_height + offer_expiry_blocks(),
+    );
+
+    insert_offer(pool.as_ref(), &offer).await?;
+
+    Ok(HttpResponse::Created().json(offer))
+}
+
+// ─── GET /api/offers ─────────────────────────────────────────
+async fn list_offers(
+    pool:    web::Data<SqlitePool>,
+    req:     HttpRequest,
+    filters: web::Query<OfferFilters>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    // Montar filtro: papel específico ou comprador/vendedor
+    let party = match filters.role {
+        Some(role) => format!("{} = ?1", role.user_column()),
+        None => "(buyer_id = ?1 OR seller_id = ?1)".into(),
+    };
+    let conditions = format!("{} AND (?2 IS NULL OR status = ?2)", party);
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM offers WHERE {}", conditions),
+    )
+    .bind(&user_id)
+    .bind(filters.status)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let offers = sqlx::query_as::<_, Offer>(&format!(
+        "SELECT * FROM offers WHERE {} ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
+        conditions
+    ))
+    .bind(&user_id)
+    .bind(filters.status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(OfferListResponse { offers, total, page }))
+}
+
+// ─── GET /api/offers/:id ─────────────────────────────────────
+async fn get_offer(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (offer, _) = load_offer(pool.as_ref(), &path.into_inner(), &claims).await?;
+
+    // Negociação completa entre este comprador e o vendedor
+    let thread = sqlx::query_as::<_, Offer>(
+        "SELECT * FROM offers WHERE product_id = ? AND buyer_id = ? ORDER BY created_at ASC",
+    )
+    .bind(&offer.product_id)
+    .bind(&offer.buyer_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    let contract = match &offer.contract_id {
+        Some(contract_id) => sqlx::query_as::<_, Contract>("SELECT * FROM contracts WHERE id = ?")
+            .bind(contract_id)
+            .fetch_optional(pool.as_ref())
+            .await?,
+        None => None,
+    };
+
+    Ok(HttpResponse::Ok().json(OfferResponse { offer, thread, contract }))
+}
+
+// ─── POST /api/offers/:id/accept ─────────────────────────────
+async fn accept(
+    pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let offer = load_pending_for_responder(pool.as_ref(), &path.into_inner(), &claims).await?;
+    let contract = accept_offer(pool.as_ref(), hub.as_ref(), &offer).await?;
+    let offer = find_offer(pool.as_ref(), &offer.id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "offer":    offer,
+        "contract": contract,
+    })))
+}
+
+// ─── POST /api/offers/:id/reject ─────────────────────────────
+async fn reject(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let offer = load_pending_for_responder(pool.as_ref(), &path.into_inner(), &claims).await?;
+    set_offer_status(pool.as_ref(), &offer, OfferStatus::Rejected).await?;
+    let offer = find_offer(pool.as_ref(), &offer.id).await?;
+
+    Ok(HttpResponse::Ok().json(offer))
+}
+
+// ─── POST /api/offers/:id/counter ────────────────────────────
+async fn counter(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<CounterOfferRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let offer = load_pending_for_responder(pool.as_ref(), &path.into_inner(), &claims).await?;
+
+    // 1. Validar valor contra o preço de lista
+    let price_sats = sqlx::query_scalar::<_, i64>(
+        "SELECT price_sats FROM products WHERE id = ? AND status = 'active'",
+    )
+    .bind(&offer.product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    let proposer = offer.proposer.counterparty();
+    let within_price = match proposer {
+        OfferParty::Buyer  => body.amount_sats < price_sats,
+        OfferParty::Seller => body.amount_sats <= price_sats,
+    };
+
+    if body.amount_sats <= 0 || !within_price {
+        return Err(AppError::Validation(format!(
+            "Contraproposta deve ser maior que zero e não exceder o preço ({} sats)",
+            price_sats
+        )));
+    }
+    if body.amount_sats == offer.amount_sats {
+        return Err(AppError::Validation(
+            "Contraproposta igual à oferta — use /accept".into(),
+        ));
+    }
+
+    // 2. Compromisso assinado de quem contrapropõe
+    let proposer_id = offer.responder_id().to_string();
+    verify_offer_signature(pool.as_ref(), &proposer_id, &offer.product_id, body.amount_sats, &body.signature)
+        .await?;
+
+    // 3. Encerrar a oferta respondida e abrir a contraproposta
+    set_offer_status(pool.as_ref(), &offer, OfferStatus::Countered).await?;
+
+    let (_, current_height, _) = get_latest_block(pool.as_ref()).await?;
+
+    let counter_offer = Offer::new(
+        offer.product_id.clone(),
+        offer.buyer_id.clone(),
+        offer.seller_id.clone(),
+        Some(offer.id.clone()),
+        Proposal {
+            proposer,
+            amount_sats: body.amount_sats,
+            signature:   body.signature.clone(),
+        },
+        current_height + offer_expiry_blocks(),
+    );
+
+    insert_offer(pool.as_ref(), &counter_offer).await?;
+
+    Ok(HttpResponse::Created().json(counter_offer))
+}
+
+// ─── Carregar oferta (apenas comprador ou vendedor) ──────────
+async fn load_offer(
+    pool:   &SqlitePool,
+    id:     &str,
+    claims: &Claims,
+) -> Result<(Offer, String), AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    let offer = find_offer(pool, id).await?;
+
+    if offer.buyer_id != user_id && offer.seller_id != user_id {
+        return Err(AppError::Unauthorized);
+    }
+
+    Ok((offer, user_id))
+}
+
+// ─── Carregar oferta pendente que o usuário deve responder ───
+async fn load_pending_for_responder(
+    pool:   &SqlitePool,
+    id:     &str,
+    claims: &Claims,
+) -> Result<Offer, AppError> {
+    let (offer, user_id) = load_offer(pool, id, claims).await?;
+
+    if offer.responder_id() != user_id {
+        return Err(AppError::Unauthorized);
+    }
+    if offer.status != OfferStatus::Pending {
+        return Err(AppError::Validation(format!("Oferta já está {}", offer.status)));
+    }
+
+    let (_, current_height, _) = get_latest_block(pool).await?;
+    if offer.is_expired(current_height) {
+        set_offer_status(pool, &offer, OfferStatus::Expired).await?;
+        return Err(AppError::Validation("Oferta expirada".into()));
+    }
+
+    Ok(offer)
+}
+
+// ─── Verificar assinatura de `offer_message` ─────────────────
+async fn verify_offer_signature(
+    pool:        &SqlitePool,
+    user_id:     &str,
+    product_id:  &str,
+    amount_sats: i64,
+    signature:   &str,
+) -> Result<(), AppError> {
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE user_id = ?",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await?;
+
+    verify_signature(&offer_message(product_id, amount_sats), signature, &pubkey)
+        .map_err(|_| AppError::InvalidSignature)
+}
+
+// ─── Salvar oferta ───────────────────────────────────────────
+async fn insert_offer(pool: &SqlitePool, offer: &Offer) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO offers (id, product_id, buyer_id, seller_id, parent_id, proposer, amount_sats,
+         signature, status, expires_at_block, contract_id, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)",
+    )
+    .bind(&offer.id)
+    .bind(&offer.product_id)
+    .bind(&offer.buyer_id)
+    .bind(&offer.seller_id)
+    .bind(&offer.parent_id)
+    .bind(offer.proposer)
+    .bind(offer.amount_sats)
+    .bind(&offer.signature)
+    .bind(offer.status)
+    .bind(offer.expires_at_block)
+    .bind(&offer.created_at)
+    .bind(&offer.updated_at)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,338 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::order::{
+    CreateOrderRequest, FundOrderRequest, Order, OrderFilters, OrderListResponse,
+    OrderResponse, OrderStatus,
+};
+use crate::models::contract::{Contract, ContractState};
+use crate::blockchain::contracts::{find_contract, fund_escrow, open_escrow, transition_contract};
+use crate::blockchain::inventory::attach_order;
+
+// ─── Configuração das rotas ──────────────────────────────────
+// Envio e entrega (SHIPPED/DELIVERED) só via /api/contracts/:id/shipment,
+// que grava o registro de envio junto com o status da ordem.
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/orders")
+            .route("",              web::post().to(create_order))
+            .route("",              web::get().to(list_orders))
+            .route("/{id}",         web::get().to(get_order))
+            .route("/{id}/fund",    web::post().to(fund_order))
+            .route("/{id}/cancel",  web::post().to(cancel_order)),
+    );
+}
+
+// ─── POST /api/orders ────────────────────────────────────────
+async fn create_order(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateOrderRequest>,
+) -> Result<HttpResponse, AppError> {
+
+    // 1. Extrair claims do JWT (comprador)
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    // 2. Validações
+    if body.quantity <= 0 {
+        return Err(AppError::Validation("Quantidade deve ser maior que zero".into()));
+    }
+    if body.shipping_address_hash.len() != 64
+        || !body.shipping_address_hash.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(AppError::Validation(
+            "shipping_address_hash deve ser um SHA-256 em hex".into(),
+        ));
+    }
+
+    // 3. Buscar produto ativo (snapshot do preço)
+    let (seller_id, unit_price_sats) = sqlx::query_as::<_, (String, i64)>(
+        "SELECT seller_id, price_sats FROM products WHERE id = ? AND status = 'active'",
+    )
+    .bind(&body.product_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    let total_sats = unit_price_sats
+        .checked_mul(body.quantity)
+        .ok_or_else(|| AppError::Validation("Valor total excede o limite".into()))?;
+
+    // Produto em leilão aberto só é vendido pelo leilão
+    let in_auction = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM auctions WHERE product_id = ? AND status = 'OPEN'",
+    )
+    .bind(&body.product_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if in_auction > 0 {
+        return Err(AppError::Conflict("Produto está em leilão".into()));
+    }
+
+    let buyer_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    // 4. Abrir escrow (reserva o estoque) e salvar ordem
+    let (order, contract) = open_order(
+        pool.as_ref(),
+        &claims.address,
+        &body,
+        buyer_id,
+        seller_id,
+        unit_price_sats,
+        total_sats,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(OrderResponse { order, contract }))
+}
+
+// ─── Abrir escrow, salvar ordem e vincular a reserva ─────────
+// Se a ordem não for gravada, o escrow recém-aberto é reembolsado, o
+// que devolve a reserva.
+async fn open_order(
+    pool:            &SqlitePool,
+    buyer_address:   &str,
+    body:            &CreateOrderRequest,
+    buyer_id:        String,
+    seller_id:       String,
+    unit_price_sats: i64,
+    total_sats:      i64,
+) -> Result<(Order, Contract), AppError> {
+    let contract = open_escrow(
+        pool,
+        buyer_address,
+        &body.product_id,
+        total_sats,
+        body.quantity,
+    )
+    .await?;
+
+    let order = Order::new(
+        body.product_id.clone(),
+        buyer_id,
+        seller_id,
+        contract.id.clone(),
+        body.quantity,
+        unit_price_sats,
+        body.shipping_address_hash.to_lowercase(),
+    );
+
+    if let Err(e) = insert_order(pool, &order).await {
+        transition_contract(
+            pool,
+            &contract,
+            ContractState::Refunded,
+            None,
+            "Ordem não pôde ser registrada. Reserva devolvida.".into(),
+        )
+        .await?;
+        return Err(e);
+    }
+
+    Ok((order, contract))
+}
+
+// ─── Gravar ordem e vincular a reserva do escrow ─────────────
+async fn insert_order(pool: &SqlitePool, order: &Order) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO orders (id, product_id, buyer_id, seller_id, contract_id, quantity,
+         unit_price_sats, total_sats, shipping_address_hash, status, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&order.id)
+    .bind(&order.product_id)
+    .bind(&order.buyer_id)
+    .bind(&order.seller_id)
+    .bind(&order.contract_id)
+    .bind(order.quantity)
+    .bind(order.unit_price_sats)
+    .bind(order.total_sats)
+    .bind(&order.shipping_address_hash)
+    .bind(order.status)
+    .bind(&order.created_at)
+    .bind(&order.updated_at)
+    .execute(pool)
+    .await?;
+
+    attach_order(pool, &order.contract_id, &order.id).await
+}
+
+// ─── GET /api/orders ─────────────────────────────────────────
+async fn list_orders(
+    pool:    web::Data<SqlitePool>,
+    req:     HttpRequest,
+    filters: web::Query<OrderFilters>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    // Montar filtro: papel específico ou comprador/vendedor
+    let party = match filters.role {
+        Some(role) => format!("{} = ?1", role.user_column()),
+        None => "(buyer_id = ?1 OR seller_id = ?1)".into(),
+    };
+    let conditions = format!("{} AND (?2 IS NULL OR status = ?2)", party);
+
+    let total = sqlx::query_scalar::<_, i64>(
+        &format!("SELECT COUNT(*) FROM orders WHERE {}", conditions),
+    )
+    .bind(&user_id)
+    .bind(filters.status)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    let orders = sqlx::query_as::<_, Order>(&format!(
+        "SELECT * FROM orders WHERE {} ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
+        conditions
+    ))
+    .bind(&user_id)
+    .bind(filters.status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(OrderListResponse { orders, total, page }))
+}
+
+// ─── GET /api/orders/:id ─────────────────────────────────────
+async fn get_order(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (order, user_id) = load_order(pool.as_ref(), &path.into_inner(), &claims).await?;
+    if user_id != order.buyer_id && user_id != order.seller_id {
+        return Err(AppError::Unauthorized);
+    }
+
+    let contract = find_contract(pool.as_ref(), &order.contract_id).await?;
+
+    Ok(HttpResponse::Ok().json(OrderResponse { order, contract }))
+}
+
+// ─── POST /api/orders/:id/fund ───────────────────────────────
+async fn fund_order(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<FundOrderRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (order, user_id) = load_order(pool.as_ref(), &path.into_inner(), &claims).await?;
+    if user_id != order.buyer_id {
+        return Err(AppError::Unauthorized);
+    }
+    if !order.status.can_transition_to(OrderStatus::Funded) {
+        return Err(AppError::InvalidContractState(format!(
+            "Ordem em estado {} não pode ser paga",
+            order.status
+        )));
+    }
+
+    // Validar a TX de bloqueio e travar o escrow (a ordem vira FUNDED)
+    let contract = find_contract(pool.as_ref(), &order.contract_id).await?;
+    fund_escrow(pool.as_ref(), &contract, &claims.address, &body.lock_tx_id).await?;
+
+    let (order, _) = load_order(pool.as_ref(), &order.id, &claims).await?;
+    let contract   = find_contract(pool.as_ref(), &order.contract_id).await?;
+
+    Ok(HttpResponse::Ok().json(OrderResponse { order, contract }))
+}
+
+// ─── POST /api/orders/:id/cancel ─────────────────────────────
+async fn cancel_order(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let (order, user_id) = load_order(pool.as_ref(), &path.into_inner(), &claims).await?;
+    if user_id != order.buyer_id {
+        return Err(AppError::Unauthorized);
+    }
+    if !order.status.can_transition_to(OrderStatus::Cancelled) {
+        return Err(AppError::InvalidContractState(
+            "Só é possível cancelar ordens ainda não pagas".into(),
+        ));
+    }
+
+    // Encerrar o escrow sem fundos (PENDING → REFUNDED) — a ordem vira CANCELLED
+    let contract = find_contract(pool.as_ref(), &order.contract_id).await?;
+    transition_contract(
+        pool.as_ref(),
+        &contract,
+        ContractState::Refunded,
+        None,
+        "Ordem cancelada pelo comprador antes do pagamento.".into(),
+    )
+    .await?;
+
+    let (order, _) = load_order(pool.as_ref(), &order.id, &claims).await?;
+
+    Ok(HttpResponse::Ok().json(order))
+}
+
+// ─── Buscar ordem + user_id do usuário autenticado ──────────
+async fn load_order(
+    pool:   &SqlitePool,
+    id:     &str,
+    claims: &Claims,
+) -> Result<(Order, String), AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    let order = sqlx::query_as::<_, Order>("SELECT * FROM orders WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Ordem não encontrada".into()))?;
+
+    Ok((order, user_id))
+}
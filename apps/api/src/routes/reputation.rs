@@ -0,0 +1,38 @@
+use actix_web::{web, HttpResponse};
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::reputation::ReputationResponse;
+use crate::blockchain::reputation::{reputation_entries, reputation_proof};
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/reputation")
+            .route("/{address}", web::get().to(get_reputation)),
+    );
+}
+
+// ─── GET /api/reputation/:address ────────────────────────────
+// Score calculado agora + prova de inclusão na última root ancorada.
+async fn get_reputation(
+    pool: web::Data<SqlitePool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let address = path.into_inner();
+
+    let components = reputation_entries(pool.as_ref(), Some(&address))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("Endereço não encontrado".into()))?;
+
+    let proof = reputation_proof(pool.as_ref(), &address).await?;
+
+    Ok(HttpResponse::Ok().json(ReputationResponse {
+        address,
+        score: components.score(),
+        components,
+        proof,
+    }))
+}
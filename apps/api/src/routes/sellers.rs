@@ -0,0 +1,178 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::product::ProductResponse;
+use crate::models::seller::{
+    validate_profile, SellerProfile, SellerStats, StorefrontFilters, StorefrontResponse,
+    UpdateProfileRequest,
+};
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/sellers")
+            .route("/{username}", web::get().to(get_storefront))
+            .route("/{username}", web::put().to(update_profile)),
+    );
+}
+
+// ─── GET /api/sellers/:username ──────────────────────────────
+// Vitrine pública: perfil, métricas e produtos ativos (?page=&limit=).
+async fn get_storefront(
+    pool:    web::Data<SqlitePool>,
+    path:    web::Path<String>,
+    filters: web::Query<StorefrontFilters>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+
+    let page   = filters.page.unwrap_or(1).max(1);
+    let limit  = filters.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let profile = find_profile(pool.as_ref(), &username).await?;
+
+    let disputed = "EXISTS (SELECT 1 FROM contract_events e
+                            WHERE e.contract_id = c.id AND e.event_type = 'DISPUTED')";
+
+    let stats = sqlx::query_as::<_, SellerStats>(&format!(
+        "SELECT
+            (SELECT COUNT(*) FROM products p
+             WHERE p.seller_id = w.user_id AND p.status = 'active') AS active_products,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.state = 'RELEASED') AS sales_count,
+            (SELECT AVG(r.rating) FROM reviews r JOIN products p ON p.id = r.product_id
+             WHERE p.seller_id = w.user_id AND r.hidden_at IS NULL) AS avg_rating,
+            (SELECT COUNT(*) FROM reviews r JOIN products p ON p.id = r.product_id
+             WHERE p.seller_id = w.user_id AND r.hidden_at IS NULL) AS review_count,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND c.lock_tx_id IS NOT NULL) AS escrows,
+            (SELECT COUNT(*) FROM contracts c
+             WHERE c.seller_pubkey = w.pubkey AND {d}) AS disputes,
+            (SELECT CAST(AVG((julianday(s.signed_at) - julianday(c.created_at)) * 86400) AS INTEGER)
+             FROM contracts c JOIN contract_signatures s
+               ON s.contract_id = c.id AND s.role = 'seller'
+             WHERE c.seller_pubkey = w.pubkey) AS avg_response_secs
+         FROM wallets w
+         WHERE w.address = ?",
+        d = disputed
+    ))
+    .bind(&profile.address)
+    .fetch_one(pool.as_ref())
+    .await?
+    .with_dispute_rate();
+
+    let products = sqlx::query_as::<_, ProductResponse>(
+        "SELECT p.*, u.username AS seller,
+                r.avg_rating, COALESCE(r.review_count, 0) AS review_count,
+                (SELECT m.thumb_sha256 FROM product_media m
+                 WHERE m.product_id = p.id AND m.kind = 'IMAGE'
+                 ORDER BY m.position ASC LIMIT 1) AS thumbnail
+         FROM products p
+         JOIN users u ON u.id = p.seller_id
+         LEFT JOIN (
+             SELECT product_id, AVG(rating) AS avg_rating, COUNT(*) AS review_count
+             FROM reviews WHERE hidden_at IS NULL GROUP BY product_id
+         ) r ON r.product_id = p.id
+         WHERE u.username = ? AND p.status = 'active'
+         ORDER BY p.created_at DESC, p.id DESC
+         LIMIT ? OFFSET ?",
+    )
+    .bind(&username)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(StorefrontResponse {
+        profile,
+        stats,
+        products,
+        page,
+    }))
+}
+
+// ─── PUT /api/sellers/:username ──────────────────────────────
+// Só o próprio usuário edita o perfil.
+async fn update_profile(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<UpdateProfileRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let username = path.into_inner();
+    if claims.username != username {
+        return Err(AppError::Unauthorized);
+    }
+
+    validate_profile(&body).map_err(AppError::Validation)?;
+
+    // Avatar precisa ser uma imagem já enviada em um produto do vendedor
+    // (só arquivos vinculados são servidos por GET /api/media/:sha256)
+    if let Some(avatar) = body.avatar_sha256.as_deref().filter(|a| !a.is_empty()) {
+        let owned = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM product_media m
+             JOIN products p ON p.id = m.product_id
+             JOIN users u    ON u.id = p.seller_id
+             WHERE m.sha256 = ? AND m.kind = 'IMAGE' AND u.username = ?",
+        )
+        .bind(avatar)
+        .bind(&username)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+        if owned == 0 {
+            return Err(AppError::Validation(
+                "Avatar deve ser uma imagem enviada em um dos seus produtos".into(),
+            ));
+        }
+    }
+
+    let current = find_profile(pool.as_ref(), &username).await?;
+
+    // Ausente mantém, "" limpa
+    let merge = |new: &Option<String>, old: Option<String>| match new {
+        Some(value) if value.trim().is_empty() => None,
+        Some(value)                            => Some(value.trim().to_string()),
+        None                                   => old,
+    };
+
+    sqlx::query(
+        "UPDATE users SET bio = ?, avatar_sha256 = ?, policies = ?, profile_updated_at = ?
+         WHERE username = ?",
+    )
+    .bind(merge(&body.bio, current.bio))
+    .bind(merge(&body.avatar_sha256, current.avatar_sha256))
+    .bind(merge(&body.policies, current.policies))
+    .bind(Utc::now().to_rfc3339())
+    .bind(&username)
+    .execute(pool.as_ref())
+    .await?;
+
+    let profile = find_profile(pool.as_ref(), &username).await?;
+
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// ─── Buscar perfil ou 404 ────────────────────────────────────
+async fn find_profile(pool: &SqlitePool, username: &str) -> Result<SellerProfile, AppError> {
+    sqlx::query_as::<_, SellerProfile>(
+        "SELECT u.username, w.address, u.bio, u.avatar_sha256, u.policies,
+                u.created_at AS member_since
+         FROM users u
+         JOIN wallets w ON w.user_id = u.id
+         WHERE u.username = ?",
+    )
+    .bind(username)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Vendedor não encontrado".into()))
+}
@@ -0,0 +1,288 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::contract::{Contract, ContractEvent, ContractState};
+use crate::models::shipment::{
+    validate_tracking, CreateShipmentRequest, Shipment, ShipmentEvent, ShipmentEventRequest,
+    ShipmentEventType, ShipmentResponse,
+};
+use crate::blockchain::chain::get_latest_block;
+use crate::blockchain::contracts::{find_contract, record_event};
+use crate::blockchain::shipping::{
+    find_shipment, record_shipment_event, shipping_dispute_window_blocks,
+    shipping_max_delivery_blocks,
+};
+
+// Rotas registradas em contracts::config (/contracts/:id/shipment)
+
+// ─── POST /api/contracts/:id/shipment ────────────────────────
+// Vendedor informa o envio de um escrow LOCKED. Inicia a contagem
+// para a liberação automática e estende a validade do contrato.
+pub async fn create_shipment(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<CreateShipmentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let contract = find_contract(pool.as_ref(), &path.into_inner()).await?;
+    if party_role(pool.as_ref(), &contract, &claims).await? != "seller" {
+        return Err(AppError::Unauthorized);
+    }
+    if contract.state != ContractState::Locked {
+        return Err(AppError::InvalidContractState(
+            "Só é possível registrar envio de contratos LOCKED".into(),
+        ));
+    }
+
+    validate_tracking(&body.carrier, &body.tracking_code).map_err(AppError::Validation)?;
+    let max_blocks = shipping_max_delivery_blocks();
+    if !(1..=max_blocks).contains(&body.estimated_delivery_blocks) {
+        return Err(AppError::Validation(format!(
+            "Prazo de entrega deve ficar entre 1 e {} blocos",
+            max_blocks
+        )));
+    }
+
+    let exists = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM shipments WHERE contract_id = ?")
+        .bind(&contract.id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    if exists > 0 {
+        return Err(AppError::AlreadyExists("Envio já registrado para este contrato".into()));
+    }
+
+    let (_, height, _) = get_latest_block(pool.as_ref()).await?;
+
+    let shipment = Shipment::new(
+        contract.id.clone(),
+        body.carrier.trim().to_string(),
+        body.tracking_code.trim().to_uppercase(),
+        height,
+        body.estimated_delivery_blocks,
+        shipping_dispute_window_blocks(),
+    );
+
+    sqlx::query(
+        "INSERT INTO shipments (id, contract_id, carrier, tracking_code, shipped_at_block,
+         estimated_delivery_blocks, auto_release_block, delivered_at_block, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, NULL, ?, ?)",
+    )
+    .bind(&shipment.id)
+    .bind(&shipment.contract_id)
+    .bind(&shipment.carrier)
+    .bind(&shipment.tracking_code)
+    .bind(shipment.shipped_at_block)
+    .bind(shipment.estimated_delivery_blocks)
+    .bind(shipment.auto_release_block)
+    .bind(&shipment.created_at)
+    .bind(&shipment.updated_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    record_shipment_event(pool.as_ref(), &ShipmentEvent::new(
+        shipment.id.clone(),
+        ShipmentEventType::Shipped,
+        "seller".into(),
+        Some(format!("{} {}", shipment.carrier, shipment.tracking_code)),
+        height,
+    ))
+    .await?;
+
+    // Em trânsito o escrow não pode expirar antes da liberação automática
+    sqlx::query(
+        "UPDATE contracts SET expires_at_block = MAX(expires_at_block, ?), updated_at = ?
+         WHERE id = ? AND state = 'LOCKED'",
+    )
+    .bind(shipment.auto_release_block + 1)
+    .bind(&shipment.updated_at)
+    .bind(&contract.id)
+    .execute(pool.as_ref())
+    .await?;
+
+    record_event(pool.as_ref(), &ContractEvent::new(
+        contract.id.clone(),
+        "SHIPPED".into(),
+        Some(format!(
+            "Enviado via {} ({}). Liberação automática no bloco {} sem disputa.",
+            shipment.carrier, shipment.tracking_code, shipment.auto_release_block
+        )),
+    ))
+    .await?;
+
+    // A ordem (se houver) acompanha o envio
+    sqlx::query(
+        "UPDATE orders SET status = 'SHIPPED', updated_at = ?
+         WHERE contract_id = ? AND status = 'FUNDED'",
+    )
+    .bind(&shipment.updated_at)
+    .bind(&contract.id)
+    .execute(pool.as_ref())
+    .await?;
+
+    let response = shipment_response(pool.as_ref(), shipment, &contract, height).await?;
+
+    Ok(HttpResponse::Created().json(response))
+}
+
+// ─── GET /api/contracts/:id/shipment ─────────────────────────
+async fn get_shipment_inner(
+    pool:   &SqlitePool,
+    id:     &str,
+    claims: &Claims,
+) -> Result<ShipmentResponse, AppError> {
+    let contract = find_contract(pool, id).await?;
+    party_role(pool, &contract, claims).await?;
+
+    let shipment = find_shipment(pool, &contract.id).await?;
+    let (_, height, _) = get_latest_block(pool).await?;
+
+    shipment_response(pool, shipment, &contract, height).await
+}
+
+pub async fn get_shipment(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let response = get_shipment_inner(pool.as_ref(), &path.into_inner(), &claims).await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+// ─── POST /api/contracts/:id/shipment/events ─────────────────
+// Vendedor atualiza o rastreio; comprador confirma o recebimento.
+// Qualquer um relata problema (EXCEPTION) — a disputa continua
+// sendo o caminho para travar a liberação automática.
+pub async fn add_shipment_event(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<ShipmentEventRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let contract = find_contract(pool.as_ref(), &path.into_inner()).await?;
+    let role = party_role(pool.as_ref(), &contract, &claims).await?;
+
+    if !body.event_type.allowed_for(role) {
+        return Err(AppError::Unauthorized);
+    }
+    if body.description.as_deref().is_some_and(|d| d.chars().count() > 500) {
+        return Err(AppError::Validation("Descrição deve ter até 500 caracteres".into()));
+    }
+
+    let shipment = find_shipment(pool.as_ref(), &contract.id).await?;
+    if shipment.delivered_at_block.is_some() {
+        return Err(AppError::Conflict("Entrega já confirmada pelo comprador".into()));
+    }
+
+    let (_, height, _) = get_latest_block(pool.as_ref()).await?;
+    let now = Utc::now().to_rfc3339();
+
+    let event = ShipmentEvent::new(
+        shipment.id.clone(),
+        body.event_type,
+        role.into(),
+        body.description.as_deref().map(str::trim).filter(|d| !d.is_empty()).map(String::from),
+        height,
+    );
+
+    if body.event_type == ShipmentEventType::DeliveryConfirmed {
+        sqlx::query(
+            "UPDATE shipments SET delivered_at_block = ?, updated_at = ?
+             WHERE id = ? AND delivered_at_block IS NULL",
+        )
+        .bind(height)
+        .bind(&now)
+        .bind(&shipment.id)
+        .execute(pool.as_ref())
+        .await?;
+
+        sqlx::query(
+            "UPDATE orders SET status = 'DELIVERED', updated_at = ?
+             WHERE contract_id = ? AND status = 'SHIPPED'",
+        )
+        .bind(&now)
+        .bind(&contract.id)
+        .execute(pool.as_ref())
+        .await?;
+    } else {
+        sqlx::query("UPDATE shipments SET updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&shipment.id)
+            .execute(pool.as_ref())
+            .await?;
+    }
+
+    record_shipment_event(pool.as_ref(), &event).await?;
+
+    Ok(HttpResponse::Created().json(event))
+}
+
+// ─── Papel do usuário no contrato (árbitro só consulta) ──────
+async fn party_role(
+    pool:     &SqlitePool,
+    contract: &Contract,
+    claims:   &Claims,
+) -> Result<&'static str, AppError> {
+    let pubkey = sqlx::query_scalar::<_, String>(
+        "SELECT pubkey FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    if pubkey == contract.buyer_pubkey {
+        Ok("buyer")
+    } else if pubkey == contract.seller_pubkey {
+        Ok("seller")
+    } else if pubkey == contract.arbiter_pubkey {
+        Ok("arbiter")
+    } else {
+        Err(AppError::Unauthorized)
+    }
+}
+
+// ─── Envio + linha do tempo + blocos até a liberação ─────────
+async fn shipment_response(
+    pool:     &SqlitePool,
+    shipment: Shipment,
+    contract: &Contract,
+    height:   i64,
+) -> Result<ShipmentResponse, AppError> {
+    let events = sqlx::query_as::<_, ShipmentEvent>(
+        "SELECT * FROM shipment_events WHERE shipment_id = ? ORDER BY created_at ASC",
+    )
+    .bind(&shipment.id)
+    .fetch_all(pool)
+    .await?;
+
+    let blocks_to_release = (contract.state == ContractState::Locked)
+        .then(|| (shipment.auto_release_block - height).max(0));
+
+    Ok(ShipmentResponse {
+        shipment,
+        events,
+        blocks_to_release,
+    })
+}
@@ -0,0 +1,213 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::middleware::client_ip::client_ip;
+use crate::crypto::totp::{generate_secret, otpauth_uri};
+use crate::models::user::Claims;
+use crate::models::security::{SecurityEvent, SecurityEventKind};
+use crate::models::two_factor::{
+    generate_backup_codes, BackupCodesResponse, TotpCodeRequest, TotpSetupResponse, TwoFactorStatus,
+};
+use crate::blockchain::security::record_security_event;
+use crate::blockchain::two_factor::{
+    check_second_factor, check_totp_code, find_totp, remaining_backup_codes, replace_backup_codes,
+    require_fresh_totp, totp_issuer,
+};
+
+// Rotas registradas em auth::config (/auth/2fa)
+
+// ─── GET /api/auth/2fa ───────────────────────────────────────
+pub async fn status(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_from(&req)?;
+
+    let totp = find_totp(pool.as_ref(), &claims.sub).await?;
+    let enabled_at = totp.and_then(|t| t.enabled_at);
+    let backup_codes_remaining = if enabled_at.is_some() {
+        remaining_backup_codes(pool.as_ref(), &claims.sub).await?
+    } else {
+        0
+    };
+
+    Ok(HttpResponse::Ok().json(TwoFactorStatus {
+        enabled: enabled_at.is_some(),
+        enabled_at,
+        backup_codes_remaining,
+    }))
+}
+
+// ─── POST /api/auth/2fa/setup ────────────────────────────────
+// Gera um segredo novo (substitui setup pendente). Só passa a
+// valer depois de /enable com um código do app.
+pub async fn setup(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_from(&req)?;
+
+    if find_totp(pool.as_ref(), &claims.sub).await?.is_some_and(|t| t.is_enabled()) {
+        return Err(AppError::Conflict("2FA já está ativo — desative antes de trocar o segredo".into()));
+    }
+
+    let secret = generate_secret();
+
+    sqlx::query(
+        "INSERT INTO user_totp (user_id, secret, enabled_at, last_used_step, created_at)
+         VALUES (?, ?, NULL, -1, ?)
+         ON CONFLICT(user_id) DO UPDATE SET secret = excluded.secret, last_used_step = -1,
+             created_at = excluded.created_at
+         WHERE user_totp.enabled_at IS NULL",
+    )
+    .bind(&claims.sub)
+    .bind(&secret)
+    .bind(Utc::now().to_rfc3339())
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(TotpSetupResponse {
+        otpauth_uri: otpauth_uri(&totp_issuer(), &claims.username, &secret),
+        secret,
+    }))
+}
+
+// ─── POST /api/auth/2fa/enable ───────────────────────────────
+// Confirma o setup com o primeiro código e devolve os códigos de
+// recuperação (exibidos UMA vez).
+pub async fn enable(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<TotpCodeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_from(&req)?;
+
+    let totp = find_totp(pool.as_ref(), &claims.sub)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Nenhum setup de 2FA pendente".into()))?;
+
+    if totp.is_enabled() {
+        return Err(AppError::Conflict("2FA já está ativo".into()));
+    }
+    if !check_totp_code(pool.as_ref(), &totp, &body.code).await? {
+        return Err(AppError::TwoFactorRequired);
+    }
+
+    let activated = sqlx::query(
+        "UPDATE user_totp SET enabled_at = ? WHERE user_id = ? AND enabled_at IS NULL",
+    )
+    .bind(Utc::now().to_rfc3339())
+    .bind(&claims.sub)
+    .execute(pool.as_ref())
+    .await?
+    .rows_affected();
+
+    if activated == 0 {
+        return Err(AppError::Conflict("2FA já está ativo".into()));
+    }
+
+    let backup_codes = generate_backup_codes();
+    replace_backup_codes(pool.as_ref(), &claims.sub, &backup_codes).await?;
+
+    record_event(pool.as_ref(), &req, &claims, SecurityEventKind::TotpEnabled).await?;
+
+    Ok(HttpResponse::Ok().json(BackupCodesResponse { backup_codes }))
+}
+
+// ─── POST /api/auth/2fa/disable ──────────────────────────────
+// Exige o segundo fator (TOTP ou código de recuperação — quem
+// perdeu o celular precisa conseguir desligar).
+pub async fn disable(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<TotpCodeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_from(&req)?;
+
+    if !find_totp(pool.as_ref(), &claims.sub).await?.is_some_and(|t| t.is_enabled()) {
+        return Err(AppError::NotFound("2FA não está ativo".into()));
+    }
+
+    check_second_factor(pool.as_ref(), &claims.sub, Some(&body.code), true).await?;
+
+    sqlx::query("DELETE FROM totp_backup_codes WHERE user_id = ?")
+        .bind(&claims.sub)
+        .execute(pool.as_ref())
+        .await?;
+
+    sqlx::query("DELETE FROM user_totp WHERE user_id = ?")
+        .bind(&claims.sub)
+        .execute(pool.as_ref())
+        .await?;
+
+    record_event(pool.as_ref(), &req, &claims, SecurityEventKind::TotpDisabled).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "2FA desativado" })))
+}
+
+// ─── POST /api/auth/2fa/backup-codes ─────────────────────────
+// Gera um novo lote com um código TOTP atual; o lote anterior cai.
+pub async fn regenerate_backup_codes(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<TotpCodeRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = claims_from(&req)?;
+
+    if !find_totp(pool.as_ref(), &claims.sub).await?.is_some_and(|t| t.is_enabled()) {
+        return Err(AppError::NotFound("2FA não está ativo".into()));
+    }
+
+    require_fresh_totp(pool.as_ref(), &claims.sub, Some(&body.code)).await?;
+
+    let backup_codes = generate_backup_codes();
+    replace_backup_codes(pool.as_ref(), &claims.sub, &backup_codes).await?;
+
+    record_event(pool.as_ref(), &req, &claims, SecurityEventKind::BackupCodesRegenerated).await?;
+
+    Ok(HttpResponse::Ok().json(BackupCodesResponse { backup_codes }))
+}
+
+// ─── TOTP recente em ações sensíveis ─────────────────────────
+// Lido do cabeçalho `X-TOTP-Code`; sem 2FA ativo não exige nada.
+pub const TOTP_HEADER: &str = "X-TOTP-Code";
+
+pub async fn require_totp_header(
+    pool:   &SqlitePool,
+    req:    &HttpRequest,
+    claims: &Claims,
+) -> Result<(), AppError> {
+    let code = req
+        .headers()
+        .get(TOTP_HEADER)
+        .and_then(|value| value.to_str().ok());
+
+    require_fresh_totp(pool, &claims.sub, code).await
+}
+
+fn claims_from(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)
+}
+
+async fn record_event(
+    pool:   &SqlitePool,
+    req:    &HttpRequest,
+    claims: &Claims,
+    kind:   SecurityEventKind,
+) -> Result<(), AppError> {
+    let ip_address = client_ip(req);
+
+    record_security_event(pool, &SecurityEvent::new(
+        Some(claims.sub.clone()),
+        Some(claims.address.clone()),
+        kind,
+        ip_address,
+        None,
+    ))
+    .await
+}
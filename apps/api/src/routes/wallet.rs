@@ -2,12 +2,15 @@ use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use sqlx::SqlitePool;
 
 use crate::errors::AppError;
+use crate::middleware::rate_limit::limited;
+use crate::models::rate_limit::LimitedRoute;
 use crate::models::user::Claims;
 use crate::models::transaction::{
     BalanceResponse, SendRequest, SendResponse, Transaction, TxHistoryResponse, Utxo,
 };
 use crate::crypto::signing::verify_signature;
 use crate::blockchain::utxo::get_balance;
+use crate::routes::two_factor::require_totp_header;
 
 // ─── Configuração das rotas ──────────────────────────────────
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -15,7 +18,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/wallet")
             .route("/{address}",      web::get().to(get_wallet))
             .route("/{address}/txs",  web::get().to(get_transactions))
-            .route("/send",           web::post().to(send)),
+            .service(limited("/send", LimitedRoute::TwoFactor).route(web::post().to(send))),
     );
 }
 
@@ -158,7 +161,10 @@ async fn send(
     verify_signature(&message, &body.signature, &sender_pubkey)
         .map_err(|_| AppError::InvalidSignature)?;
 
-    // 5. Gerar hash da TX
+    // 5. Com 2FA ativo, transferência exige código TOTP atual
+    require_totp_header(pool.as_ref(), &req, &claims).await?;
+
+    // 6. Gerar hash da TX
     let tx_data = format!(
         "{}{}{}{}{}",
         claims.address, body.receiver, body.amount_sats, body.fee_sats,
@@ -166,7 +172,7 @@ async fn send(
     );
     let tx_id = crate::blockchain::pow::sha256_hex(&tx_data);
 
-    // 6. Salvar TX na mempool
+    // 7. Salvar TX na mempool
     let tx = Transaction::new(
         tx_id.clone(),
         claims.address.clone(),
@@ -191,7 +197,7 @@ async fn send(
     .execute(pool.as_ref())
     .await?;
 
-    // 7. Marcar UTXOs como gastos
+    // 8. Marcar UTXOs como gastos
     sqlx::query(
         "UPDATE utxos SET spent = 1, spent_tx_id = ?
          WHERE owner = ? AND spent = 0
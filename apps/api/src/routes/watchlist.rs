@@ -0,0 +1,252 @@
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use sqlx::SqlitePool;
+
+use crate::errors::AppError;
+use crate::models::user::Claims;
+use crate::models::product::{Product, ProductStatus};
+use crate::models::watchlist::{
+    validate_saved_search, AddWatchRequest, CreateSavedSearchRequest, SavedSearch,
+    WatchlistEntry, WatchlistItem, MAX_SAVED_SEARCHES,
+};
+
+// ─── Configuração das rotas ──────────────────────────────────
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/watchlist")
+            .route("",              web::get().to(list_watchlist))
+            .route("",              web::post().to(add_watch))
+            .route("/{product_id}", web::delete().to(remove_watch)),
+    );
+    cfg.service(
+        web::scope("/saved-searches")
+            .route("",      web::get().to(list_saved_searches))
+            .route("",      web::post().to(create_saved_search))
+            .route("/{id}", web::delete().to(delete_saved_search)),
+    );
+}
+
+// ─── GET /api/watchlist ──────────────────────────────────────
+async fn list_watchlist(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let entries = sqlx::query_as::<_, WatchlistEntry>(
+        "SELECT w.created_at AS watched_since, w.last_price_sats,
+                p.*, u.username AS seller,
+                r.avg_rating, COALESCE(r.review_count, 0) AS review_count,
+                (SELECT m.thumb_sha256 FROM product_media m
+                 WHERE m.product_id = p.id AND m.kind = 'IMAGE'
+                 ORDER BY m.position ASC LIMIT 1) AS thumbnail
+         FROM watchlist w
+         JOIN products p ON p.id = w.product_id
+         JOIN users u    ON u.id = p.seller_id
+         LEFT JOIN (
+             SELECT product_id, AVG(rating) AS avg_rating, COUNT(*) AS review_count
+             FROM reviews WHERE hidden_at IS NULL GROUP BY product_id
+         ) r ON r.product_id = p.id
+         WHERE w.user_id = ?
+         ORDER BY w.created_at DESC",
+    )
+    .bind(&user_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+// ─── POST /api/watchlist ─────────────────────────────────────
+async fn add_watch(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<AddWatchRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let product = sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = ?")
+        .bind(&body.product_id)
+        .fetch_optional(pool.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound("Produto não encontrado".into()))?;
+
+    if product.status == ProductStatus::Removed {
+        return Err(AppError::NotFound("Produto não encontrado".into()));
+    }
+    if product.seller_id == user_id {
+        return Err(AppError::Validation("Não é possível acompanhar o próprio produto".into()));
+    }
+
+    let item = WatchlistItem::new(user_id, product.id, product.price_sats);
+
+    // Acompanhar de novo mantém a data e a base de preço originais
+    sqlx::query(
+        "INSERT OR IGNORE INTO watchlist (user_id, product_id, last_price_sats, created_at)
+         VALUES (?, ?, ?, ?)",
+    )
+    .bind(&item.user_id)
+    .bind(&item.product_id)
+    .bind(item.last_price_sats)
+    .bind(&item.created_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(item))
+}
+
+// ─── DELETE /api/watchlist/:product_id ───────────────────────
+async fn remove_watch(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let removed = sqlx::query("DELETE FROM watchlist WHERE user_id = ? AND product_id = ?")
+        .bind(&user_id)
+        .bind(path.into_inner())
+        .execute(pool.as_ref())
+        .await?
+        .rows_affected();
+
+    if removed == 0 {
+        return Err(AppError::NotFound("Produto não está na watchlist".into()));
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Produto removido da watchlist" })))
+}
+
+// ─── GET /api/saved-searches ─────────────────────────────────
+async fn list_saved_searches(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let searches = sqlx::query_as::<_, SavedSearch>(
+        "SELECT * FROM saved_searches WHERE user_id = ? ORDER BY created_at DESC",
+    )
+    .bind(&user_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(searches))
+}
+
+// ─── POST /api/saved-searches ────────────────────────────────
+async fn create_saved_search(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    body: web::Json<CreateSavedSearchRequest>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    validate_saved_search(&body).map_err(AppError::Validation)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM saved_searches WHERE user_id = ?",
+    )
+    .bind(&user_id)
+    .fetch_one(pool.as_ref())
+    .await?;
+
+    if count >= MAX_SAVED_SEARCHES {
+        return Err(AppError::Validation(format!(
+            "Limite de {} buscas salvas atingido",
+            MAX_SAVED_SEARCHES
+        )));
+    }
+
+    let search = SavedSearch::new(user_id, body.into_inner());
+
+    sqlx::query(
+        "INSERT INTO saved_searches (id, user_id, name, query, fts_query, category, condition,
+         state, min_price, max_price, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&search.id)
+    .bind(&search.user_id)
+    .bind(&search.name)
+    .bind(&search.query)
+    .bind(&search.fts_query)
+    .bind(&search.category)
+    .bind(&search.condition)
+    .bind(&search.state)
+    .bind(search.min_price)
+    .bind(search.max_price)
+    .bind(&search.created_at)
+    .execute(pool.as_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(search))
+}
+
+// ─── DELETE /api/saved-searches/:id ──────────────────────────
+async fn delete_saved_search(
+    pool: web::Data<SqlitePool>,
+    req:  HttpRequest,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or(AppError::Unauthorized)?;
+
+    let user_id = user_id_for(pool.as_ref(), &claims).await?;
+
+    let removed = sqlx::query("DELETE FROM saved_searches WHERE id = ? AND user_id = ?")
+        .bind(path.into_inner())
+        .bind(&user_id)
+        .execute(pool.as_ref())
+        .await?
+        .rows_affected();
+
+    if removed == 0 {
+        return Err(AppError::NotFound("Busca salva não encontrada".into()));
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "message": "Busca salva removida" })))
+}
+
+// ─── user_id do usuário autenticado ──────────────────────────
+async fn user_id_for(pool: &SqlitePool, claims: &Claims) -> Result<String, AppError> {
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM wallets WHERE address = ?",
+    )
+    .bind(&claims.address)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user_id)
+}
@@ -0,0 +1,84 @@
+use image::ImageFormat;
+use std::env;
+use std::io::Cursor;
+
+/// Lado maior da miniatura em pixels (`MEDIA_THUMB_SIZE`, padrão 320)
+pub fn thumb_size() -> u32 {
+    env::var("MEDIA_THUMB_SIZE")
+        .unwrap_or_else(|_| "320".into())
+        .parse()
+        .unwrap_or(320)
+}
+
+/// Limite de pixels para decodificar (evita "bombas" de descompressão)
+const MAX_PIXELS: u64 = 40_000_000;
+
+// ─── Imagem decodificada ─────────────────────────────────────
+#[derive(Debug)]
+pub struct ImageInfo {
+    pub width:     u32,
+    pub height:    u32,
+    pub thumbnail: Vec<u8>,   // PNG, proporção preservada
+}
+
+/// Decodifica a imagem (garante que o arquivo é válido, não só o
+/// cabeçalho) e gera a miniatura. Custo de CPU — chamar em `web::block`.
+pub fn inspect(bytes: &[u8]) -> Result<ImageInfo, String> {
+    let reader = image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+
+    let (width, height) = reader
+        .into_dimensions()
+        .map_err(|_| "Imagem corrompida ou em formato não suportado".to_string())?;
+
+    if width as u64 * height as u64 > MAX_PIXELS {
+        return Err(format!("Imagem muito grande ({}x{})", width, height));
+    }
+
+    let img = image::load_from_memory(bytes)
+        .map_err(|_| "Imagem corrompida ou em formato não suportado".to_string())?;
+
+    let size  = thumb_size();
+    let thumb = if img.width() > size || img.height() > size {
+        img.thumbnail(size, size)
+    } else {
+        img
+    };
+
+    let mut thumbnail = Vec::new();
+    thumb
+        .write_to(&mut Cursor::new(&mut thumbnail), ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+
+    Ok(ImageInfo { width, height, thumbnail })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, GenericImageView, RgbImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_thumbnail_keeps_aspect_ratio() {
+        let info = inspect(&png(1280, 640)).unwrap();
+        assert_eq!((info.width, info.height), (1280, 640));
+
+        let thumb = image::load_from_memory(&info.thumbnail).unwrap();
+        assert_eq!(thumb.dimensions(), (320, 160));
+    }
+
+    #[test]
+    fn test_rejects_truncated_image() {
+        let bytes = png(64, 64);
+        assert!(inspect(&bytes[..bytes.len() / 2]).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use sha2::{Digest, Sha256};
+use std::env;
+use std::io;
+use std::path::PathBuf;
+
+/// Tamanho máximo de uma imagem (`MEDIA_MAX_IMAGE_BYTES`, padrão 5 MiB)
+pub fn max_image_bytes() -> usize {
+    env::var("MEDIA_MAX_IMAGE_BYTES")
+        .unwrap_or_else(|_| "5242880".into())
+        .parse()
+        .unwrap_or(5 * 1024 * 1024)
+}
+
+/// Tamanho máximo de um anexo (`MEDIA_MAX_ATTACHMENT_BYTES`, padrão 10 MiB)
+pub fn max_attachment_bytes() -> usize {
+    env::var("MEDIA_MAX_ATTACHMENT_BYTES")
+        .unwrap_or_else(|_| "10485760".into())
+        .parse()
+        .unwrap_or(10 * 1024 * 1024)
+}
+
+/// Limite de arquivos por produto (`MEDIA_MAX_PER_PRODUCT`, padrão 10)
+pub fn max_media_per_product() -> i64 {
+    env::var("MEDIA_MAX_PER_PRODUCT")
+        .unwrap_or_else(|_| "10".into())
+        .parse()
+        .unwrap_or(10)
+}
+
+// ─── Armazenamento endereçado por conteúdo ───────────────────
+// Cada arquivo é gravado em `MEDIA_DIR/ab/abcdef…` (SHA-256 do
+// conteúdo). Mesmo arquivo enviado duas vezes ocupa um só lugar.
+// Um bucket S3 local (MinIO) montado em MEDIA_DIR serve igual.
+#[derive(Debug, Clone)]
+pub struct MediaStore {
+    root: PathBuf,
+}
+
+impl MediaStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Diretório vindo de `MEDIA_DIR` (padrão `./media`)
+    pub fn from_env() -> Self {
+        Self::new(env::var("MEDIA_DIR").unwrap_or_else(|_| "./media".into()))
+    }
+
+    /// Grava o conteúdo e devolve o SHA-256 (hex)
+    pub fn put(&self, bytes: &[u8]) -> io::Result<String> {
+        let sha256 = hex::encode(Sha256::digest(bytes));
+        let path = self.path_for(&sha256).expect("hash gerado é sempre válido");
+
+        if path.exists() {
+            return Ok(sha256);
+        }
+
+        std::fs::create_dir_all(path.parent().expect("caminho tem diretório"))?;
+
+        // Grava em arquivo temporário e renomeia: leitores nunca veem arquivo pela metade
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &path)?;
+
+        Ok(sha256)
+    }
+
+    /// Lê o conteúdo; `None` se a chave não existe ou é inválida
+    pub fn get(&self, sha256: &str) -> io::Result<Option<Vec<u8>>> {
+        let Some(path) = self.path_for(sha256) else {
+            return Ok(None);
+        };
+
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Só aceita 64 caracteres hex minúsculos — impede `../` na chave
+    fn path_for(&self, sha256: &str) -> Option<PathBuf> {
+        let valid = sha256.len() == 64
+            && sha256.chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c));
+
+        valid.then(|| self.root.join(&sha256[..2]).join(sha256))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_put_get_roundtrip() {
+        let dir = env::temp_dir().join(format!("papermarket-media-{}", uuid::Uuid::new_v4()));
+        let store = MediaStore::new(&dir);
+
+        let sha256 = store.put(b"foto").unwrap();
+        assert_eq!(sha256, hex::encode(Sha256::digest(b"foto")));
+        assert_eq!(store.put(b"foto").unwrap(), sha256);
+        assert_eq!(store.get(&sha256).unwrap(), Some(b"foto".to_vec()));
+
+        assert_eq!(store.get(&"0".repeat(64)).unwrap(), None);
+        assert_eq!(store.get("../../etc/passwd").unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
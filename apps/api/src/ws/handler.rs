@@ -1,34 +1,54 @@
 use actix_web::{web, HttpRequest, HttpResponse};
 use actix_ws::Message;
 use sqlx::SqlitePool;
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::{interval, Duration};
 use tracing::info;
 
+use crate::errors::AppError;
 use crate::blockchain::mempool::{mempool_count, average_fee};
 use crate::blockchain::chain::get_latest_block;
-
-// ─── Módulo WebSocket ────────────────────────────────────────
-pub mod ws {
-    pub mod handler;
-}
+use crate::blockchain::sessions::is_token_revoked;
+use crate::routes::auth::decode_token;
+use crate::ws::hub::Hub;
 
 // ─── Handler principal do WebSocket ──────────────────────────
 pub async fn ws_handler(
     req:  HttpRequest,
     body: web::Payload,
     pool: web::Data<SqlitePool>,
+    hub:  web::Data<Hub>,
 ) -> Result<HttpResponse, actix_web::Error> {
     let (response, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
 
     let pool = pool.into_inner();
+    let hub  = hub.into_inner();
 
     actix_web::rt::spawn(async move {
         let mut ticker = interval(Duration::from_secs(5));
 
+        // Eventos da carteira — só depois de `{"type": "auth"}`
+        let mut inbox: Option<UnboundedReceiver<String>> = None;
+        // jti e expiração do token que abriu a assinatura
+        let mut auth: Option<(String, usize)> = None;
+
         loop {
             tokio::select! {
                 // ─── Tick a cada 5 segundos ──────────────────
                 _ = ticker.tick() => {
+                    // Logout, rotação ou expiração encerram a assinatura
+                    if let Some((jti, exp)) = &auth {
+                        if !token_still_valid(&pool, jti, *exp).await {
+                            inbox = None;
+                            auth  = None;
+                            hub.prune();
+                            let msg = serde_json::json!({ "type": "auth_expired" });
+                            if session.text(msg.to_string()).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+
                     // Buscar estado atual da chain
                     let chain_state = get_chain_state(&pool).await;
 
@@ -46,6 +66,13 @@ pub async fn ws_handler(
                     }
                 }
 
+                // ─── Eventos endereçados ao usuário ──────────
+                Some(event) = next_event(&mut inbox) => {
+                    if session.text(event).await.is_err() {
+                        break;
+                    }
+                }
+
                 // ─── Mensagens do cliente ────────────────────
                 Some(Ok(msg)) = msg_stream.recv() => {
                     match msg {
@@ -53,6 +80,9 @@ pub async fn ws_handler(
                             handle_client_message(
                                 &mut session,
                                 &pool,
+                                &hub,
+                                &mut inbox,
+                                &mut auth,
                                 &text,
                             ).await;
                         }
@@ -71,6 +101,10 @@ pub async fn ws_handler(
             }
         }
 
+        // Socket encerrado: a fila do hub morre com ele
+        drop(inbox);
+        hub.prune();
+
         let _ = session.close(None).await;
     });
 
@@ -81,6 +115,9 @@ pub async fn ws_handler(
 async fn handle_client_message(
     session: &mut actix_ws::Session,
     pool:    &SqlitePool,
+    hub:     &Hub,
+    inbox:   &mut Option<UnboundedReceiver<String>>,
+    auth:    &mut Option<(String, usize)>,
     text:    &str,
 ) {
     let Ok(msg) = serde_json::from_str::<serde_json::Value>(text) else {
@@ -117,6 +154,40 @@ async fn handle_client_message(
             }).to_string()).await;
         }
 
+        // Autenticar o socket para receber eventos da carteira.
+        // Mesma regra do middleware: token revogado não assina nada.
+        "auth" => {
+            let token = msg["token"].as_str().unwrap_or("");
+
+            let decoded = match decode_token(token) {
+                Ok(claims) if token_still_valid(pool, &claims.jti, claims.exp).await => Ok(claims),
+                Ok(_)      => Err(AppError::InvalidToken),
+                Err(e)     => Err(e),
+            };
+
+            let reply = match decoded {
+                Ok(claims) => {
+                    // Reautenticação troca a fila: a anterior sai do hub
+                    let previous = inbox.replace(hub.subscribe(&claims.address));
+                    if previous.is_some() {
+                        drop(previous);
+                        hub.prune();
+                    }
+                    *auth  = Some((claims.jti.clone(), claims.exp));
+                    serde_json::json!({
+                        "type":    "auth_ok",
+                        "address": claims.address,
+                    })
+                }
+                Err(e) => serde_json::json!({
+                    "type":    "error",
+                    "message": e.to_string(),
+                }),
+            };
+
+            let _ = session.text(reply.to_string()).await;
+        }
+
         // Ping manual
         "ping" => {
             let _ = session.text(serde_json::json!({
@@ -133,6 +204,22 @@ async fn handle_client_message(
     }
 }
 
+// ─── Token ainda vale? (não expirado, com jti, não revogado) ──
+async fn token_still_valid(pool: &SqlitePool, jti: &str, exp: usize) -> bool {
+    if jti.is_empty() || exp < chrono::Utc::now().timestamp() as usize {
+        return false;
+    }
+    !is_token_revoked(pool, jti).await.unwrap_or(true)
+}
+
+// ─── Próximo evento da carteira (pendente até autenticar) ─────
+async fn next_event(inbox: &mut Option<UnboundedReceiver<String>>) -> Option<String> {
+    match inbox {
+        Some(rx) => rx.recv().await,
+        None     => std::future::pending().await,
+    }
+}
+
 // ─── Montar estado atual da chain ────────────────────────────
 async fn get_chain_state(pool: &SqlitePool) -> serde_json::Value {
     let (best_hash, height, difficulty) = get_latest_block(pool)
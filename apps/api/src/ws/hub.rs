@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+// ─── Hub de sessões WebSocket autenticadas ───────────────────
+// Cada socket que envia `{"type": "auth"}` com um JWT válido passa a
+// receber eventos endereçados à carteira do usuário (ex: mensagens).
+#[derive(Clone, Default)]
+pub struct Hub {
+    sessions: Arc<Mutex<HashMap<String, Vec<UnboundedSender<String>>>>>,
+}
+
+impl Hub {
+    /// Registra um socket para o endereço e devolve a fila de eventos
+    pub fn subscribe(&self, address: &str) -> UnboundedReceiver<String> {
+        let (tx, rx) = unbounded_channel();
+
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(address.to_string())
+            .or_default()
+            .push(tx);
+
+        rx
+    }
+
+    /// Descarta as filas cujo socket fechou (receptor descartado).
+    /// Chamado quando um socket encerra ou troca de assinatura, para que
+    /// endereços que não recebem eventos não acumulem filas mortas.
+    pub fn prune(&self) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+
+        sessions.retain(|_, senders| {
+            senders.retain(|tx| !tx.is_closed());
+            !senders.is_empty()
+        });
+    }
+
+    /// Envia o evento a todos os sockets do endereço; descarta os fechados.
+    /// Retorna quantos sockets receberam.
+    pub fn send(&self, address: &str, event: &serde_json::Value) -> usize {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+
+        let Some(senders) = sessions.get_mut(address) else {
+            return 0;
+        };
+
+        let payload = event.to_string();
+        senders.retain(|tx| tx.send(payload.clone()).is_ok());
+        let delivered = senders.len();
+
+        if senders.is_empty() {
+            sessions.remove(address);
+        }
+
+        delivered
+    }
+}
@@ -1 +1,2 @@
-pub mod handler;
\ No newline at end of file
+pub mod handler;
+pub mod hub;
\ No newline at end of file